@@ -0,0 +1,29 @@
+#![no_main]
+
+use crosshair_overlay::config::color::Color;
+use crosshair_overlay::config::format::ConfigFormat;
+use crosshair_overlay::config::share::code;
+use crosshair_overlay::config::share::cs2::Cs2Crosshair;
+use crosshair_overlay::config::share::valorant::ValorantCrosshair;
+use crosshair_overlay::config::CrosshairConfig;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|text: &str| {
+    // Chaque format de fichier, puis la désérialisation vers la configuration
+    for format in [ConfigFormat::Toml, ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Ron] {
+        if let Ok(table) = format.parse(text) {
+            let _ = toml::Value::Table(table).try_into::<CrosshairConfig>();
+        }
+    }
+
+    // Codes de partage et couleurs
+    let base = CrosshairConfig::default();
+    let _ = code::decode(text);
+    if let Ok(crosshair) = Cs2Crosshair::decode(text) {
+        let _ = crosshair.to_config(&base);
+    }
+    if let Ok(crosshair) = ValorantCrosshair::parse(text) {
+        let _ = crosshair.to_config(&base);
+    }
+    let _ = text.parse::<Color>();
+});
@@ -0,0 +1,45 @@
+#![no_main]
+
+use arbitrary::Arbitrary;
+use crosshair_overlay::clock::ManualClock;
+use crosshair_overlay::config::effects::mouse::MouseState;
+use crosshair_overlay::config::CrosshairConfig;
+use crosshair_overlay::render::render_frame_with_mouse_state;
+use libfuzzer_sys::fuzz_target;
+
+/// Pixels ajoutés après l'image pour détecter les écritures hors limites
+const SENTINEL_LEN: usize = 64;
+const SENTINEL: u32 = 0xDEADBEEF;
+
+#[derive(Debug, Arbitrary)]
+struct Input {
+    config: CrosshairConfig,
+    width: u8,
+    height: u8,
+    time: f32,
+    left_pressed: bool,
+    right_pressed: bool,
+    press_time: Option<f32>,
+}
+
+fuzz_target!(|input: Input| {
+    let width = usize::from(input.width);
+    let height = usize::from(input.height);
+    let len = width * height;
+    let clock = ManualClock::new(input.time);
+    let mouse_state = MouseState {
+        left_pressed: input.left_pressed,
+        right_pressed: input.right_pressed,
+        press_time: input.press_time,
+        ..MouseState::default()
+    };
+
+    let mut first = vec![SENTINEL; len + SENTINEL_LEN];
+    render_frame_with_mouse_state(&input.config, &mut first, width, height, &clock, &mouse_state);
+    assert!(first[len..].iter().all(|&pixel| pixel == SENTINEL), "écriture hors de l'image");
+
+    // Même image quel que soit le contenu précédent du buffer
+    let mut second = vec![!SENTINEL; len];
+    render_frame_with_mouse_state(&input.config, &mut second, width, height, &clock, &mouse_state);
+    assert_eq!(first[..len], second[..], "rendu non déterministe");
+});
@@ -0,0 +1,444 @@
+//! Overlay Wayland natif avec le protocole wlr-layer-shell
+//!
+//! Sous Wayland, la plupart des compositeurs ignorent « toujours au premier
+//! plan » et le click-through de winit. Une surface de la couche `overlay`
+//! reste au-dessus des fenêtres, jeux plein écran compris, et une région
+//! d'entrée vide laisse passer tous les clics.
+//!
+//! Compositeurs compatibles : Sway, KDE Plasma, Hyprland, river, labwc...
+//! (pas GNOME). Pour tester sans écran, lancer un compositeur headless
+//! (`WLR_BACKENDS=headless sway`) puis `cargo test -- --ignored` avec son
+//! `WAYLAND_DISPLAY`.
+
+use std::error::Error;
+use std::time::{Duration, Instant};
+
+use smithay_client_toolkit::{
+    compositor::{CompositorHandler, CompositorState, Region},
+    delegate_compositor, delegate_layer, delegate_output, delegate_registry, delegate_shm,
+    output::{OutputHandler, OutputInfo, OutputState},
+    reexports::calloop::{channel, EventLoop},
+    reexports::calloop_wayland_source::WaylandSource,
+    reexports::client::{
+        globals::registry_queue_init,
+        protocol::{wl_output, wl_shm, wl_surface},
+        Connection, EventQueue, QueueHandle,
+    },
+    registry::{ProvidesRegistryState, RegistryState},
+    registry_handlers,
+    shell::{
+        wlr_layer::{
+            Anchor, KeyboardInteractivity, Layer, LayerShell, LayerShellHandler, LayerSurface,
+            LayerSurfaceConfigure,
+        },
+        WaylandSurface,
+    },
+    shm::{slot::SlotPool, Shm, ShmHandler},
+};
+
+use crosshair_overlay::clock::RealClock;
+use crosshair_overlay::config::CrosshairConfig;
+use crosshair_overlay::monitor::{self, MonitorInfo, MonitorLayout};
+use crosshair_overlay::{render, tr};
+
+use super::premultiply;
+use crate::controller::{AppEvent, Changes, Controller, EventRelay};
+
+/// Intervalle entre deux images quand des effets sont animés (~30 FPS, comme la fenêtre winit)
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+/// Espace de noms de la surface, pour les règles des compositeurs
+const NAMESPACE: &str = "crosshair-overlay";
+
+/// Place de la surface sur sa sortie, en pixels logiques
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Placement {
+    margin: (i32, i32), // Gauche, haut
+    side: u32,
+    buffer_scale: i32,
+}
+
+/// Overlay affiché dans une surface layer-shell
+pub struct LayerOverlay {
+    connection: Connection,
+    registry_state: RegistryState,
+    output_state: OutputState,
+    compositor: CompositorState,
+    layer_shell: LayerShell,
+    shm: Shm,
+    pool: Option<SlotPool>,
+    config: CrosshairConfig,
+    clock: RealClock,
+    layer: Option<LayerSurface>,
+    output: Option<wl_output::WlOutput>,
+    placement: Option<Placement>,
+    configured: bool,
+    pixels: Vec<u32>,
+    hidden: bool, // Image transparente : masqué par un raccourci ou une règle [focus]
+    events: Vec<AppEvent>, // Raccourcis et changements de focus reçus pendant la distribution
+    exit: bool,
+}
+
+impl LayerOverlay {
+    /// Se connecte au compositeur et crée la surface sur la sortie configurée
+    ///
+    /// Échoue si la session n'est pas Wayland ou si le compositeur ne gère pas
+    /// wlr-layer-shell (GNOME) : l'appelant peut alors revenir à winit.
+    pub fn connect(config: CrosshairConfig) -> Result<(Self, EventQueue<Self>), Box<dyn Error>> {
+        let connection = Connection::connect_to_env()?;
+        let (globals, mut event_queue) = registry_queue_init(&connection)?;
+        let qh = event_queue.handle();
+
+        let compositor = CompositorState::bind(&globals, &qh)?;
+        let layer_shell = LayerShell::bind(&globals, &qh).map_err(|e| tr!("layer_shell.unsupported", error = e))?;
+        let shm = Shm::bind(&globals, &qh)?;
+
+        let mut overlay = Self {
+            connection: connection.clone(),
+            registry_state: RegistryState::new(&globals),
+            output_state: OutputState::new(&globals, &qh),
+            compositor,
+            layer_shell,
+            shm,
+            pool: None,
+            config,
+            clock: RealClock::new(),
+            layer: None,
+            output: None,
+            placement: None,
+            configured: false,
+            pixels: Vec::new(),
+            hidden: false,
+            events: Vec::new(),
+            exit: false,
+        };
+
+        // Recevoir la description des sorties (nom, taille, échelle) avant d'en choisir une
+        event_queue.roundtrip(&mut overlay)?;
+        overlay.create_surface(&qh);
+        Ok((overlay, event_queue))
+    }
+
+    /// Affiche l'overlay jusqu'à la fermeture de la surface ou au raccourci Quitter
+    ///
+    /// Les raccourcis globaux et les règles `[focus]` arrivent par `relay` et
+    /// passent par le `controller`, comme pour la fenêtre winit.
+    pub fn run(mut self, event_queue: EventQueue<Self>, controller: &mut Controller, relay: &EventRelay) -> Result<(), Box<dyn Error>> {
+        let qh = event_queue.handle();
+        let mut event_loop: EventLoop<Self> = EventLoop::try_new()?;
+        WaylandSource::new(self.connection.clone(), event_queue)
+            .insert(event_loop.handle())
+            .map_err(|e| e.error)?;
+        let (sender, receiver) = channel::channel();
+        event_loop
+            .handle()
+            .insert_source(receiver, |event, _, overlay: &mut Self| {
+                if let channel::Event::Msg(event) = event {
+                    overlay.events.push(event);
+                }
+            })
+            .map_err(|e| e.error)?;
+        relay.connect(move |event| {
+            let _ = sender.send(event);
+        });
+        self.hidden = !controller.is_visible();
+
+        let mut next_frame = Instant::now() + FRAME_INTERVAL;
+        while !self.exit {
+            // Sans animation, attendre les événements du compositeur et les raccourcis
+            let animated = !self.hidden && self.config.effects.has_animated_effects();
+            let timeout = animated.then(|| next_frame.saturating_duration_since(Instant::now()));
+            event_loop.dispatch(timeout, &mut self)?;
+
+            for event in std::mem::take(&mut self.events) {
+                let changes = controller.handle(event);
+                self.apply_changes(controller, changes, &qh);
+            }
+
+            if animated && Instant::now() >= next_frame {
+                self.draw();
+                next_frame = Instant::now() + FRAME_INTERVAL;
+            }
+        }
+        Ok(())
+    }
+
+    /// Applique le résultat d'une action du contrôleur à la surface
+    fn apply_changes(&mut self, controller: &Controller, changes: Changes, qh: &QueueHandle<Self>) {
+        if changes.quit {
+            self.exit = true;
+            return;
+        }
+        if changes == Changes::default() {
+            return;
+        }
+        let previous = std::mem::replace(&mut self.config, controller.config.clone());
+        self.hidden = !controller.is_visible();
+        if changes.placement {
+            if self.config.window.monitor != previous.window.monitor {
+                // Autre écran : la sortie d'une surface layer-shell est fixée à sa création
+                self.layer = None;
+                self.create_surface(qh);
+            } else {
+                self.place_surface();
+            }
+        }
+        self.draw();
+    }
+
+    /// Crée la surface layer-shell sur la sortie choisie par `window.monitor`
+    fn create_surface(&mut self, qh: &QueueHandle<Self>) {
+        let outputs: Vec<(wl_output::WlOutput, OutputInfo)> = self
+            .output_state
+            .outputs()
+            .filter_map(|output| self.output_state.info(&output).map(|info| (output, info)))
+            .collect();
+        // Wayland ne désigne pas d'écran principal : « primary » donne la première sortie
+        let layout = MonitorLayout {
+            monitors: outputs.iter().map(|(_, info)| monitor_info(info)).collect(),
+            primary: None,
+        };
+        let (selected, warning) = layout.select(&self.config.window.monitor, None);
+        if let Some(monitor) = warning {
+            eprintln!("{}", tr!("app.monitor_not_found", monitor = monitor));
+        }
+        let index = selected.and_then(|selected| layout.monitors.iter().position(|m| std::ptr::eq(m, selected)));
+        self.output = index.map(|index| outputs[index].0.clone());
+        let name = index.and_then(|index| outputs[index].1.name.clone()).unwrap_or_else(|| "?".to_string());
+
+        let surface = self.compositor.create_surface(qh);
+        // Région d'entrée vide : tous les clics traversent la surface
+        if let Ok(region) = Region::new(&self.compositor) {
+            surface.set_input_region(Some(region.wl_region()));
+        }
+
+        let layer = self.layer_shell.create_layer_surface(qh, surface, Layer::Overlay, Some(NAMESPACE), self.output.as_ref());
+        layer.set_keyboard_interactivity(KeyboardInteractivity::None);
+        // Ignorer les zones réservées des barres : le centre est celui de l'écran
+        layer.set_exclusive_zone(-1);
+        self.layer = Some(layer);
+        self.configured = false;
+        self.place_surface();
+
+        println!("{}", tr!("app.layer_shell_active", output = name));
+    }
+
+    /// Applique la taille et la position de la configuration à la surface
+    fn place_surface(&mut self) {
+        let Some(layer) = &self.layer else {
+            return;
+        };
+        let output = self.output.as_ref().and_then(|output| self.output_state.info(output));
+        self.placement = output.map(|info| surface_placement(&self.config, &monitor_info(&info)));
+
+        match self.placement {
+            Some(placement) => {
+                layer.set_anchor(Anchor::TOP | Anchor::LEFT);
+                layer.set_margin(placement.margin.1, 0, 0, placement.margin.0);
+                layer.set_size(placement.side, placement.side);
+                layer.wl_surface().set_buffer_scale(placement.buffer_scale);
+            }
+            None => {
+                // Sortie inconnue : sans ancre, le compositeur centre la surface
+                let side = self.config.window_side(1.0);
+                layer.set_anchor(Anchor::empty());
+                layer.set_size(side, side);
+            }
+        }
+        layer.commit();
+    }
+
+    /// Dessine une image et la présente au compositeur
+    fn draw(&mut self) {
+        let Some(layer) = &self.layer else {
+            return;
+        };
+        if !self.configured {
+            return;
+        }
+        let (logical_side, scale) = self
+            .placement
+            .map_or((self.config.window_side(1.0), 1), |placement| (placement.side, placement.buffer_scale));
+        let side = logical_side.saturating_mul(scale.max(1) as u32).max(1) as usize;
+        let Ok(length) = i32::try_from(side * side * 4) else {
+            return;
+        };
+
+        self.pixels.resize(side * side, 0);
+        if self.hidden {
+            // Une surface layer-shell ne se masque pas : présenter une image transparente
+            self.pixels.fill(0);
+        } else {
+            render::render_frame(&self.config, &mut self.pixels, side, side, &self.clock);
+        }
+
+        let pool = match self.pool.as_mut() {
+            Some(pool) => pool,
+            None => match SlotPool::new(length as usize, &self.shm) {
+                Ok(pool) => self.pool.insert(pool),
+                Err(e) => {
+                    eprintln!("{}", tr!("layer_shell.draw_failed", error = e));
+                    self.exit = true;
+                    return;
+                }
+            },
+        };
+        let stride = side as i32 * 4;
+        let (buffer, canvas) = match pool.create_buffer(side as i32, side as i32, stride, wl_shm::Format::Argb8888) {
+            Ok(created) => created,
+            Err(e) => {
+                eprintln!("{}", tr!("layer_shell.draw_failed", error = e));
+                self.exit = true;
+                return;
+            }
+        };
+        for (bytes, pixel) in canvas.chunks_exact_mut(4).zip(&self.pixels) {
+            bytes.copy_from_slice(&premultiply(*pixel).to_le_bytes());
+        }
+
+        let surface = layer.wl_surface();
+        surface.damage_buffer(0, 0, side as i32, side as i32);
+        if buffer.attach_to(surface).is_ok() {
+            layer.commit();
+        }
+    }
+}
+
+/// Description d'une sortie Wayland pour le placement
+fn monitor_info(info: &OutputInfo) -> MonitorInfo {
+    let scale = info.scale_factor.max(1);
+    // La taille logique tient compte de la rotation de l'écran, pas le mode
+    let size = info
+        .logical_size
+        .map(|(width, height)| (width.saturating_mul(scale), height.saturating_mul(scale)))
+        .or_else(|| info.modes.iter().find(|mode| mode.current).map(|mode| mode.dimensions))
+        .unwrap_or((0, 0));
+    MonitorInfo {
+        name: info.name.clone(),
+        position: info.logical_position.unwrap_or(info.location),
+        size: (size.0.max(0) as u32, size.1.max(0) as u32),
+        scale_factor: f64::from(scale),
+    }
+}
+
+/// Place de la surface : le placement de la fenêtre winit, relatif à la sortie et en pixels logiques
+fn surface_placement(config: &CrosshairConfig, output: &MonitorInfo) -> Placement {
+    let local = MonitorInfo { position: (0, 0), ..output.clone() };
+    let ((x, y), (side, _)) = monitor::window_placement(config, &local);
+    let buffer_scale = (output.scale_factor.round() as i32).max(1);
+    Placement {
+        margin: (x.div_euclid(buffer_scale), y.div_euclid(buffer_scale)),
+        side: side.div_ceil(buffer_scale as u32).max(1),
+        buffer_scale,
+    }
+}
+
+impl CompositorHandler for LayerOverlay {
+    fn scale_factor_changed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: i32) {
+        self.place_surface();
+    }
+
+    fn transform_changed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: wl_output::Transform) {}
+
+    fn frame(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: u32) {}
+
+    fn surface_enter(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: &wl_output::WlOutput) {}
+
+    fn surface_leave(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &wl_surface::WlSurface, _: &wl_output::WlOutput) {}
+}
+
+impl OutputHandler for LayerOverlay {
+    fn output_state(&mut self) -> &mut OutputState {
+        &mut self.output_state
+    }
+
+    fn new_output(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_output::WlOutput) {}
+
+    fn update_output(&mut self, _: &Connection, _: &QueueHandle<Self>, output: wl_output::WlOutput) {
+        // Changement de résolution ou d'échelle de notre sortie : replacer la surface
+        if self.output.as_ref() == Some(&output) {
+            self.place_surface();
+        }
+    }
+
+    fn output_destroyed(&mut self, _: &Connection, _: &QueueHandle<Self>, _: wl_output::WlOutput) {}
+}
+
+impl LayerShellHandler for LayerOverlay {
+    fn closed(&mut self, _: &Connection, qh: &QueueHandle<Self>, _: &LayerSurface) {
+        // Sortie débranchée : recréer la surface sur une autre sortie
+        self.layer = None;
+        self.output = None;
+        if self.output_state.outputs().next().is_some() {
+            self.create_surface(qh);
+        } else {
+            self.exit = true;
+        }
+    }
+
+    fn configure(&mut self, _: &Connection, _: &QueueHandle<Self>, _: &LayerSurface, configure: LayerSurfaceConfigure, _: u32) {
+        // Taille imposée par le compositeur (0 = notre choix)
+        if let Some(placement) = &mut self.placement {
+            if configure.new_size.0 > 0 && configure.new_size.0 == configure.new_size.1 {
+                placement.side = configure.new_size.0;
+            }
+        }
+        self.configured = true;
+        self.draw();
+    }
+}
+
+impl ShmHandler for LayerOverlay {
+    fn shm_state(&mut self) -> &mut Shm {
+        &mut self.shm
+    }
+}
+
+impl ProvidesRegistryState for LayerOverlay {
+    fn registry(&mut self) -> &mut RegistryState {
+        &mut self.registry_state
+    }
+    registry_handlers![OutputState];
+}
+
+delegate_compositor!(LayerOverlay);
+delegate_output!(LayerOverlay);
+delegate_shm!(LayerOverlay);
+delegate_layer!(LayerOverlay);
+delegate_registry!(LayerOverlay);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn output(size: (u32, u32), scale_factor: f64) -> MonitorInfo {
+        MonitorInfo { name: Some("DP-1".to_string()), position: (1920, 0), size, scale_factor }
+    }
+
+    #[test]
+    fn surface_is_placed_in_logical_pixels_on_its_output() {
+        let config = CrosshairConfig::default();
+        let expected = Placement { margin: (910, 490), side: 100, buffer_scale: 1 };
+        assert_eq!(surface_placement(&config, &output((1920, 1080), 1.0)), expected);
+        // Sortie à 200 % : même place logique, buffer deux fois plus grand
+        let expected = Placement { buffer_scale: 2, ..expected };
+        assert_eq!(surface_placement(&config, &output((3840, 2160), 2.0)), expected);
+    }
+
+    /// Nécessite un compositeur avec wlr-layer-shell, par exemple `WLR_BACKENDS=headless sway`
+    #[test]
+    #[ignore]
+    fn overlay_is_presented_by_a_wayland_compositor() {
+        let (mut overlay, mut event_queue) = LayerOverlay::connect(CrosshairConfig::default())
+            .expect("compositeur Wayland avec wlr-layer-shell (WAYLAND_DISPLAY)");
+        for _ in 0..10 {
+            if overlay.configured {
+                break;
+            }
+            event_queue.roundtrip(&mut overlay).unwrap();
+        }
+        assert!(overlay.configured, "le compositeur doit configurer la surface");
+        // Première image dessinée dans la mémoire partagée
+        assert!(overlay.pool.is_some());
+        assert_eq!(overlay.pixels.len(), 100 * 100);
+    }
+}
@@ -0,0 +1,47 @@
+//! Systèmes d'affichage de l'overlay autres que la fenêtre winit
+//!
+//! Chaque backend reprend le même rendu ([`crosshair_overlay::render`]) et le
+//! même placement ([`crosshair_overlay::monitor`]) que la fenêtre winit.
+
+use crosshair_overlay::config::window::Backend;
+
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+pub mod layer_shell;
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+pub mod x11;
+
+/// Backend natif (`LayerShell` ou `X11`) à essayer avant la fenêtre winit
+///
+/// En mode automatique : layer-shell dans une session Wayland (`WAYLAND_DISPLAY`).
+/// Sous X11, la fenêtre winit reste le choix automatique : la fenêtre
+/// override-redirect ne reçoit ni le clavier ni les effets de souris.
+pub fn native_backend(backend: Backend) -> Option<Backend> {
+    let is_set = |name: &str| std::env::var_os(name).is_some_and(|value| !value.is_empty());
+    match backend {
+        Backend::LayerShell | Backend::X11 => Some(backend),
+        Backend::Winit => None,
+        Backend::Auto if !cfg!(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android")))) => None,
+        Backend::Auto if is_set("WAYLAND_DISPLAY") => Some(Backend::LayerShell),
+        Backend::Auto => None,
+    }
+}
+
+/// Pixel ARGB vers l'ARGB prémultiplié attendu par les compositeurs (wl_shm, visuel ARGB X11)
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+pub fn premultiply(pixel: u32) -> u32 {
+    let alpha = pixel >> 24;
+    let channel = |shift: u32| (((pixel >> shift) & 0xFF) * alpha + 127) / 255;
+    (alpha << 24) | (channel(16) << 16) | (channel(8) << 8) | channel(0)
+}
+
+#[cfg(all(test, unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pixels_are_premultiplied() {
+        assert_eq!(premultiply(0xFF12_3456), 0xFF12_3456);
+        assert_eq!(premultiply(0x00FF_FFFF), 0x0000_0000);
+        assert_eq!(premultiply(0x80FF_0080), 0x8080_0040);
+    }
+}
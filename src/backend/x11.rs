@@ -0,0 +1,383 @@
+//! Overlay X11 natif : fenêtre override-redirect ARGB
+//!
+//! Une fenêtre override-redirect échappe au gestionnaire de fenêtres : pas de
+//! décoration, pas de focus, et aucun déplacement sous les jeux plein écran.
+//! Elle doit en contrepartie se remettre elle-même au premier plan quand une
+//! autre fenêtre est empilée au-dessus. Une région d'entrée XShape vide laisse
+//! passer tous les clics ; la transparence nécessite un compositeur (picom,
+//! KWin, Mutter...), sans lui le fond est noir.
+//!
+//! Pour tester sans écran : `Xvfb :99 &` puis `DISPLAY=:99 cargo test -- --ignored`
+//! (ou `xvfb-run cargo test -- --ignored`).
+
+use std::error::Error;
+use std::ops::Range;
+use std::sync::mpsc::{self, Receiver};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use x11rb::connection::{Connection, RequestConnection};
+use x11rb::protocol::randr::{self, ConnectionExt as _};
+use x11rb::protocol::shape::{self, ConnectionExt as _};
+use x11rb::protocol::xproto::{
+    AtomEnum, ChangeWindowAttributesAux, ClientMessageEvent, ClipOrdering, ColormapAlloc, ConfigureWindowAux, ConnectionExt as _, CreateGCAux, CreateWindowAux,
+    EventMask, ImageFormat, ImageOrder, PropMode, StackMode, Visibility, VisualClass, Window, WindowClass,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+use x11rb::wrapper::ConnectionExt as _;
+
+use crosshair_overlay::clock::RealClock;
+use crosshair_overlay::config::CrosshairConfig;
+use crosshair_overlay::monitor::{self, MonitorInfo, MonitorLayout};
+use crosshair_overlay::{render, tr};
+
+use super::premultiply;
+use crate::controller::{AppEvent, Changes, Controller, EventRelay};
+
+/// Intervalle entre deux images quand des effets sont animés (~30 FPS, comme la fenêtre winit)
+const FRAME_INTERVAL: Duration = Duration::from_millis(33);
+/// Titre de la fenêtre, pour les outils comme xwininfo
+const WINDOW_NAME: &[u8] = b"crosshair-overlay";
+/// Taille de l'en-tête d'une requête PutImage
+const PUT_IMAGE_HEADER: usize = 24;
+
+/// Overlay affiché dans une fenêtre override-redirect
+pub struct X11Overlay {
+    connection: Arc<RustConnection>, // Partagée avec le relais, qui réveille la boucle
+    root: Window,
+    window: Window,
+    gc: u32,
+    little_endian: bool,
+    config: CrosshairConfig,
+    clock: RealClock,
+    side: u32,
+    pixels: Vec<u32>,
+    bytes: Vec<u8>,
+    hidden: bool, // Fenêtre démappée par un raccourci ou une règle [focus]
+    exit: bool,
+}
+
+impl X11Overlay {
+    /// Se connecte au serveur X et affiche la fenêtre sur l'écran configuré
+    ///
+    /// Échoue sans serveur X (`DISPLAY`), sans visuel ARGB 32 bits ou sans
+    /// l'extension SHAPE : l'appelant peut alors revenir à winit.
+    pub fn connect(config: CrosshairConfig) -> Result<Self, Box<dyn Error>> {
+        let (connection, screen_num) = x11rb::connect(None)?;
+        let screen = &connection.setup().roots[screen_num];
+        let root = screen.root;
+        let visual = screen
+            .allowed_depths
+            .iter()
+            .filter(|depth| depth.depth == 32)
+            .flat_map(|depth| &depth.visuals)
+            .find(|visual| visual.class == VisualClass::TRUE_COLOR)
+            .map(|visual| visual.visual_id)
+            .ok_or_else(|| tr!("x11.no_argb_visual"))?;
+        if connection.extension_information(shape::X11_EXTENSION_NAME)?.is_none() {
+            return Err(tr!("x11.no_shape").into());
+        }
+        let little_endian = connection.setup().image_byte_order == ImageOrder::LSB_FIRST;
+
+        let colormap = connection.generate_id()?;
+        connection.create_colormap(ColormapAlloc::NONE, colormap, root, visual)?;
+        let window = connection.generate_id()?;
+        let side = config.window_side(1.0).clamp(1, u32::from(u16::MAX)) as u16;
+        let attributes = CreateWindowAux::new()
+            .override_redirect(1)
+            .background_pixel(0)
+            .border_pixel(0)
+            .colormap(colormap)
+            .event_mask(EventMask::EXPOSURE | EventMask::STRUCTURE_NOTIFY | EventMask::VISIBILITY_CHANGE);
+        connection.create_window(32, window, root, 0, 0, side, side, 0, WindowClass::INPUT_OUTPUT, visual, &attributes)?;
+        connection.change_property8(PropMode::REPLACE, window, AtomEnum::WM_NAME, AtomEnum::STRING, WINDOW_NAME)?;
+        // Région d'entrée vide : tous les clics traversent la fenêtre
+        connection.shape_rectangles(shape::SO::SET, shape::SK::INPUT, ClipOrdering::UNSORTED, window, 0, 0, &[])?;
+
+        // Fenêtres empilées au-dessus de la nôtre et changements d'écrans
+        connection.change_window_attributes(root, &ChangeWindowAttributesAux::new().event_mask(EventMask::SUBSTRUCTURE_NOTIFY))?;
+        if connection.extension_information(randr::X11_EXTENSION_NAME)?.is_some() {
+            connection.randr_select_input(root, randr::NotifyMask::SCREEN_CHANGE)?;
+        }
+
+        let gc = connection.generate_id()?;
+        connection.create_gc(gc, window, &CreateGCAux::new().graphics_exposures(0))?;
+
+        let mut overlay = Self {
+            connection: Arc::new(connection),
+            root,
+            window,
+            gc,
+            little_endian,
+            config,
+            clock: RealClock::new(),
+            side: u32::from(side),
+            pixels: Vec::new(),
+            bytes: Vec::new(),
+            hidden: false,
+            exit: false,
+        };
+        overlay.place_window()?;
+        overlay.connection.map_window(window)?;
+        overlay.connection.flush()?;
+        Ok(overlay)
+    }
+
+    /// Affiche l'overlay jusqu'à la destruction de la fenêtre ou au raccourci Quitter
+    ///
+    /// Les raccourcis globaux et les règles `[focus]` arrivent par `relay` et
+    /// passent par le `controller`, comme pour la fenêtre winit.
+    pub fn run(mut self, controller: &mut Controller, relay: &EventRelay) -> Result<(), Box<dyn Error>> {
+        let (sender, receiver) = mpsc::channel();
+        let connection = self.connection.clone();
+        let window = self.window;
+        relay.connect(move |event| {
+            // Un message à notre fenêtre réveille la boucle bloquée dans wait_for_event
+            if sender.send(event).is_ok() {
+                let message = ClientMessageEvent::new(32, window, AtomEnum::NONE, [0u32; 5]);
+                let _ = connection.send_event(false, window, EventMask::NO_EVENT, message);
+                let _ = connection.flush();
+            }
+        });
+        self.set_visible(controller.is_visible())?;
+
+        let mut next_frame = Instant::now() + FRAME_INTERVAL;
+        while !self.exit {
+            if !self.hidden && self.config.effects.has_animated_effects() {
+                self.process_events()?;
+                self.process_actions(controller, &receiver)?;
+                if Instant::now() >= next_frame {
+                    self.draw()?;
+                    next_frame = Instant::now() + FRAME_INTERVAL;
+                }
+                self.connection.flush()?;
+                std::thread::sleep(next_frame.saturating_duration_since(Instant::now()));
+            } else {
+                // Sans animation, attendre les événements du serveur
+                self.connection.flush()?;
+                let event = self.connection.wait_for_event()?;
+                self.handle_event(event)?;
+                self.process_actions(controller, &receiver)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Transmet au contrôleur les raccourcis et changements de focus reçus, puis applique le résultat
+    fn process_actions(&mut self, controller: &mut Controller, receiver: &Receiver<AppEvent>) -> Result<(), Box<dyn Error>> {
+        while let Ok(event) = receiver.try_recv() {
+            let changes = controller.handle(event);
+            if changes.quit {
+                self.exit = true;
+                return Ok(());
+            }
+            if changes == Changes::default() {
+                continue;
+            }
+            self.config = controller.config.clone();
+            if changes.placement {
+                self.place_window()?;
+            }
+            self.set_visible(controller.is_visible())?;
+            if !self.hidden {
+                self.draw()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Mappe ou démappe la fenêtre
+    fn set_visible(&mut self, visible: bool) -> Result<(), Box<dyn Error>> {
+        if visible != self.hidden {
+            return Ok(());
+        }
+        self.hidden = !visible;
+        if visible {
+            self.connection.map_window(self.window)?;
+            self.raise()?;
+        } else {
+            self.connection.unmap_window(self.window)?;
+        }
+        Ok(())
+    }
+
+    /// Traite les événements déjà reçus, sans attendre
+    fn process_events(&mut self) -> Result<(), Box<dyn Error>> {
+        while let Some(event) = self.connection.poll_for_event()? {
+            self.handle_event(event)?;
+        }
+        Ok(())
+    }
+
+    fn handle_event(&mut self, event: Event) -> Result<(), Box<dyn Error>> {
+        match event {
+            Event::Expose(expose) if expose.window == self.window && expose.count == 0 => self.draw()?,
+            // Fenêtre (partiellement) recouverte, ou une autre fenêtre vient de
+            // s'afficher ou de passer juste au-dessus de la nôtre
+            Event::VisibilityNotify(visibility) if visibility.window == self.window && visibility.state != Visibility::UNOBSCURED => {
+                self.raise()?
+            }
+            Event::MapNotify(map) if map.event == self.root && map.window != self.window => self.raise()?,
+            Event::ConfigureNotify(configure)
+                if configure.event == self.root && configure.window != self.window && configure.above_sibling == self.window =>
+            {
+                self.raise()?
+            }
+            Event::DestroyNotify(destroy) if destroy.window == self.window => self.exit = true,
+            // Réveil envoyé par le relais : les actions sont traitées après l'événement
+            Event::ClientMessage(message) if message.window == self.window => {}
+            Event::RandrScreenChangeNotify(_) => self.place_window()?,
+            Event::Error(error) => eprintln!("{}", tr!("x11.protocol_error", error = format!("{error:?}"))),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Remet la fenêtre au sommet de la pile
+    fn raise(&self) -> Result<(), Box<dyn Error>> {
+        self.connection.configure_window(self.window, &ConfigureWindowAux::new().stack_mode(StackMode::ABOVE))?;
+        Ok(())
+    }
+
+    /// Applique la taille et la position de la configuration à la fenêtre
+    fn place_window(&mut self) -> Result<(), Box<dyn Error>> {
+        let layout = self.monitor_layout()?;
+        let cursor = self
+            .connection
+            .query_pointer(self.root)?
+            .reply()
+            .ok()
+            .map(|pointer| (i32::from(pointer.root_x), i32::from(pointer.root_y)));
+        let (selected, warning) = layout.select(&self.config.window.monitor, cursor);
+        if let Some(monitor) = warning {
+            eprintln!("{}", tr!("app.monitor_not_found", monitor = monitor));
+        }
+        let Some(monitor) = selected else {
+            return Ok(());
+        };
+
+        let ((x, y), (side, _)) = monitor::window_placement(&self.config, monitor);
+        self.side = side.clamp(1, u32::from(u16::MAX));
+        let aux = ConfigureWindowAux::new().x(x).y(y).width(self.side).height(self.side);
+        self.connection.configure_window(self.window, &aux)?;
+        println!("{}", tr!("app.x11_active", output = monitor.name.as_deref().unwrap_or("?")));
+        Ok(())
+    }
+
+    /// Écrans RandR actifs, ou l'écran X entier sans RandR
+    ///
+    /// X11 n'a pas de mise à l'échelle par écran : les tailles sont en pixels.
+    fn monitor_layout(&self) -> Result<MonitorLayout, Box<dyn Error>> {
+        let monitors = match self.connection.extension_information(randr::X11_EXTENSION_NAME)? {
+            Some(_) => self.connection.randr_get_monitors(self.root, true)?.reply()?.monitors,
+            None => Vec::new(),
+        };
+        let mut layout = MonitorLayout {
+            primary: monitors.iter().position(|monitor| monitor.primary),
+            monitors: Vec::with_capacity(monitors.len()),
+        };
+        for monitor in &monitors {
+            let name = self.connection.get_atom_name(monitor.name)?.reply().ok();
+            layout.monitors.push(MonitorInfo {
+                name: name.map(|name| String::from_utf8_lossy(&name.name).into_owned()),
+                position: (i32::from(monitor.x), i32::from(monitor.y)),
+                size: (u32::from(monitor.width), u32::from(monitor.height)),
+                scale_factor: 1.0,
+            });
+        }
+        if layout.monitors.is_empty() {
+            let screen = self.connection.setup().roots.iter().find(|screen| screen.root == self.root);
+            layout.monitors.extend(screen.map(|screen| MonitorInfo {
+                name: None,
+                position: (0, 0),
+                size: (u32::from(screen.width_in_pixels), u32::from(screen.height_in_pixels)),
+                scale_factor: 1.0,
+            }));
+        }
+        Ok(layout)
+    }
+
+    /// Dessine une image et l'envoie au serveur, en bandes de lignes si elle dépasse une requête
+    fn draw(&mut self) -> Result<(), Box<dyn Error>> {
+        let side = self.side as usize;
+        self.pixels.resize(side * side, 0);
+        render::render_frame(&self.config, &mut self.pixels, side, side, &self.clock);
+
+        self.bytes.clear();
+        for pixel in &self.pixels {
+            let pixel = premultiply(*pixel);
+            let bytes = if self.little_endian { pixel.to_le_bytes() } else { pixel.to_be_bytes() };
+            self.bytes.extend_from_slice(&bytes);
+        }
+
+        let max_request_bytes = self.connection.maximum_request_bytes();
+        for rows in row_bands(side, max_request_bytes) {
+            let data = &self.bytes[rows.start * side * 4..rows.end * side * 4];
+            self.connection.put_image(
+                ImageFormat::Z_PIXMAP,
+                self.window,
+                self.gc,
+                side as u16,
+                rows.len() as u16,
+                0,
+                rows.start as i16,
+                0,
+                32,
+                data,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Lignes de l'image envoyées par chaque requête PutImage (au moins une par requête)
+fn row_bands(side: usize, max_request_bytes: usize) -> impl Iterator<Item = Range<usize>> {
+    let row_bytes = (side * 4).max(1);
+    let rows = (max_request_bytes.saturating_sub(PUT_IMAGE_HEADER) / row_bytes).max(1);
+    (0..side).step_by(rows).map(move |start| start..(start + rows).min(side))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn images_are_split_in_bands_that_fit_a_request() {
+        let bands: Vec<Range<usize>> = row_bands(100, 256 * 1024).collect();
+        assert_eq!(bands, vec![0..100]);
+        // 400 octets par ligne : 10 lignes par requête de 4 Kio
+        let bands: Vec<Range<usize>> = row_bands(100, 4096).collect();
+        assert_eq!(bands.len(), 10);
+        assert_eq!(bands[9], 90..100);
+        assert!(bands.windows(2).all(|pair| pair[0].end == pair[1].start));
+    }
+
+    /// Nécessite un serveur X, par exemple `Xvfb :99` avec `DISPLAY=:99`
+    #[test]
+    #[ignore]
+    fn overlay_stays_above_and_lets_clicks_through_under_xvfb() {
+        let mut overlay = X11Overlay::connect(CrosshairConfig::default()).expect("serveur X (DISPLAY)");
+        let connection = &overlay.connection;
+        let attributes = connection.get_window_attributes(overlay.window).unwrap().reply().unwrap();
+        assert!(attributes.override_redirect);
+        let input = connection.shape_get_rectangles(overlay.window, shape::SK::INPUT).unwrap().reply().unwrap();
+        assert!(input.rectangles.is_empty(), "la région d'entrée doit être vide");
+        let geometry = connection.get_geometry(overlay.window).unwrap().reply().unwrap();
+        assert_eq!((geometry.width, geometry.height), (100, 100));
+
+        // Une autre fenêtre s'affiche au-dessus : l'overlay doit repasser devant
+        let other = connection.generate_id().unwrap();
+        connection
+            .create_window(0, other, overlay.root, 0, 0, 200, 200, 0, WindowClass::INPUT_OUTPUT, 0, &CreateWindowAux::new())
+            .unwrap();
+        connection.map_window(other).unwrap();
+        connection.get_input_focus().unwrap().reply().unwrap();
+        overlay.process_events().unwrap();
+        overlay.connection.get_input_focus().unwrap().reply().unwrap();
+
+        let tree = overlay.connection.query_tree(overlay.root).unwrap().reply().unwrap();
+        assert_eq!(tree.children.last(), Some(&overlay.window));
+        assert_eq!(overlay.pixels.len(), 100 * 100);
+    }
+}
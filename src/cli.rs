@@ -0,0 +1,626 @@
+use clap::{Args, Parser, Subcommand};
+use std::fs;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use toml::{Table, Value};
+
+use crate::clock::ManualClock;
+use crate::config::color::Color;
+use crate::config::format::ConfigFormat;
+use crate::config::layers::{ConfigLayers, Layer, LayeredConfig};
+use crate::config::presets::{PresetKind, PresetLibrary, PresetSource, PRESET_KEY};
+use crate::config::schema;
+use crate::config::share::code;
+use crate::config::share::cs2::Cs2Crosshair;
+use crate::config::share::valorant::ValorantCrosshair;
+use crate::config::share::Conversion;
+use crate::config::{ConfigLocation, CrosshairConfig, ProfileManager};
+use crate::crosshair::CrosshairStyle;
+use crate::i18n;
+use crate::render;
+use crate::tr;
+
+// Les textes d'aide viennent des catalogues de messages : ils sont évalués à
+// l'analyse des arguments, dans la langue de l'environnement.
+#[derive(Debug, Parser)]
+#[command(name = "crosshair-overlay", version, about = tr!("help.about"))]
+pub struct Cli {
+    #[arg(short, long, global = true, value_name = tr!("help.value.path"), help = tr!("help.config"))]
+    pub config: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    #[command(about = tr!("help.run"))]
+    Run(RunArgs),
+    #[command(about = tr!("help.render"))]
+    Render(RenderArgs),
+    #[command(about = tr!("help.validate"))]
+    Validate(ValidateArgs),
+    #[command(about = tr!("help.explain"))]
+    Explain(ExplainArgs),
+    #[command(about = tr!("help.print_default"))]
+    PrintDefault,
+    #[command(about = tr!("help.print_schema"))]
+    PrintSchema,
+    #[command(about = tr!("help.convert"))]
+    Convert(ConvertArgs),
+    #[command(about = tr!("help.presets"))]
+    Presets(PresetsArgs),
+    #[command(about = tr!("help.export_code"))]
+    ExportCode,
+    #[command(about = tr!("help.import_code"))]
+    ImportCode(ImportArgs),
+    #[command(about = tr!("help.import_cs2"))]
+    ImportCs2(ImportArgs),
+    #[command(about = tr!("help.export_cs2"))]
+    ExportCs2,
+    #[command(about = tr!("help.import_valorant"))]
+    ImportValorant(ImportArgs),
+    #[command(about = tr!("help.export_valorant"))]
+    ExportValorant,
+}
+
+#[derive(Debug, Default, Args)]
+pub struct RunArgs {
+    #[command(flatten)]
+    pub overrides: Overrides,
+}
+
+#[derive(Debug, Args)]
+pub struct RenderArgs {
+    #[arg(short, long, value_name = tr!("help.value.file"), default_value = "crosshair.png", help = tr!("help.render_output"))]
+    pub output: PathBuf,
+
+    #[arg(long, value_name = "PIXELS", value_parser = clap::value_parser!(u32).range(1..=i64::from(render::MAX_IMAGE_SIZE)), help = tr!("help.window_size"))]
+    pub window_size: Option<u32>,
+
+    #[arg(long, value_name = tr!("help.value.seconds"), default_value_t = 0.0, help = tr!("help.time"))]
+    pub time: f32,
+
+    #[command(flatten)]
+    pub overrides: Overrides,
+}
+
+#[derive(Debug, Args)]
+pub struct ValidateArgs {
+    #[arg(help = tr!("help.validate_path"))]
+    pub path: Option<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ExplainArgs {
+    #[arg(long, help = tr!("help.all"))]
+    pub all: bool,
+
+    #[command(flatten)]
+    pub overrides: Overrides,
+}
+
+#[derive(Debug, Args)]
+pub struct ConvertArgs {
+    #[arg(help = tr!("help.convert_input"))]
+    pub input: PathBuf,
+    #[arg(help = tr!("help.convert_output"))]
+    pub output: PathBuf,
+}
+
+#[derive(Debug, Args)]
+pub struct PresetsArgs {
+    #[arg(help = tr!("help.preset_kind"))]
+    pub kind: Option<PresetKind>,
+}
+
+#[derive(Debug, Args)]
+pub struct ImportArgs {
+    #[arg(help = tr!("help.code"))]
+    pub code: String,
+    #[arg(short, long, value_name = tr!("help.value.file"), help = tr!("help.import_output"))]
+    pub output: Option<PathBuf>,
+}
+
+/// Surcharges du crosshair passées en ligne de commande
+#[derive(Debug, Clone, Default, Args)]
+pub struct Overrides {
+    #[arg(long, value_name = "STYLE", help = tr!("help.style"))]
+    pub style: Option<CrosshairStyle>,
+
+    #[arg(long, value_name = tr!("help.value.color"), help = tr!("help.color"))]
+    pub color: Option<Color>,
+
+    #[arg(long, value_name = "PIXELS", help = tr!("help.size"))]
+    pub size: Option<u32>,
+
+    #[arg(long, value_name = tr!("help.value.opacity"), help = tr!("help.opacity"))]
+    pub opacity: Option<f32>,
+}
+
+impl Overrides {
+    /// Couche de configuration correspondant aux surcharges
+    pub fn to_table(&self) -> Table {
+        let mut crosshair = Table::new();
+        if let Some(style) = &self.style {
+            if let Ok(value) = Value::try_from(style) {
+                crosshair.insert("style".to_string(), value);
+            }
+        }
+        if let Some(color) = self.color {
+            crosshair.insert("color".to_string(), Value::String(color.to_string()));
+        }
+        if let Some(size) = self.size {
+            crosshair.insert("size".to_string(), Value::Integer(size.into()));
+        }
+
+        let mut window = Table::new();
+        if let Some(opacity) = self.opacity {
+            window.insert("opacity".to_string(), Value::Float(opacity.into()));
+        }
+
+        let mut table = Table::new();
+        if !crosshair.is_empty() {
+            table.insert("crosshair".to_string(), Value::Table(crosshair));
+        }
+        if !window.is_empty() {
+            table.insert("window".to_string(), Value::Table(window));
+        }
+        table
+    }
+}
+
+/// Charge une configuration et affiche l'erreur éventuelle
+fn load(path: &std::path::Path) -> Option<CrosshairConfig> {
+    match CrosshairConfig::load_from_file(path) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            None
+        }
+    }
+}
+
+/// Charge la configuration avec le profil actif, l'environnement et les options, en échouant à la première erreur
+fn load_layers(location: &ConfigLocation, overrides: &Overrides) -> Option<LayeredConfig> {
+    let path = &location.path;
+    let presets = PresetLibrary::load(&location.directory());
+    let mut layers = ConfigLayers::new();
+
+    if let Err(e) = layers.push_file(Layer::File(path.clone()), path, &presets) {
+        eprintln!("❌ {}", e);
+        return None;
+    }
+
+    // Profil actif, comme au démarrage de l'overlay (CROSSHAIR__PROFILES__ACTIVE compris)
+    let profiles = ProfileManager::new(&layers.profiles_settings(), &location.directory());
+    if let Some((name, profile_path)) = profiles.current_profile() {
+        if let Err(e) = layers.push_file(Layer::Profile(name.to_string()), profile_path, &presets) {
+            eprintln!("{}", tr!("cli.profile_error", name = name, error = e));
+            return None;
+        }
+    }
+
+    let errors = layers.push_environment();
+    if !errors.is_empty() {
+        for error in &errors {
+            eprintln!("❌ {}", error);
+        }
+        return None;
+    }
+    if let Err(e) = layers.push(Layer::CommandLine, overrides.to_table()) {
+        eprintln!("❌ {}", e);
+        return None;
+    }
+    Some(layers.build())
+}
+
+/// Choisit la langue des messages d'après `interface.language` (fichier et environnement)
+///
+/// Les erreurs sont ignorées ici : elles seront affichées, dans cette langue,
+/// par la commande elle-même.
+pub fn set_language(location: &ConfigLocation) {
+    let mut layers = ConfigLayers::new();
+    let _ = layers.push_file(Layer::File(location.path.clone()), &location.path, &PresetLibrary::builtin());
+    layers.push_environment();
+    i18n::set_language(layers.build().config.interface.language);
+}
+
+/// Commande `render` : image PNG de la configuration
+pub fn render(location: &ConfigLocation, args: &RenderArgs) -> ExitCode {
+    let Some(LayeredConfig { config, .. }) = load_layers(location, &args.overrides) else {
+        return ExitCode::FAILURE;
+    };
+
+    let size = args.window_size.unwrap_or_else(|| config.window_side(1.0));
+    if size == 0 || size > render::MAX_IMAGE_SIZE {
+        eprintln!("{}", tr!("cli.image_size_invalid", size = size, max = render::MAX_IMAGE_SIZE));
+        return ExitCode::FAILURE;
+    }
+
+    match render::render_to_png(&config, &args.output, size, &ManualClock::new(args.time)) {
+        Ok(()) => {
+            println!("{}", tr!("cli.image_saved", size = size, path = args.output.display()));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", tr!("cli.write_failed", path = args.output.display(), error = e));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Commande `validate` : charge la configuration et liste les erreurs
+///
+/// Sans chemin, la configuration active est vérifiée avec le profil actif et
+/// l'environnement. Un fichier donné est vérifié seul (parents, fragments et
+/// presets compris), sur les valeurs par défaut.
+pub fn validate(location: &ConfigLocation, args: &ValidateArgs) -> ExitCode {
+    let (path, config) = match &args.path {
+        Some(path) => (path, load(path)),
+        None => (&location.path, load_layers(location, &Overrides::default()).map(|layered| layered.config)),
+    };
+    let Some(config) = config else {
+        return ExitCode::FAILURE;
+    };
+
+    let errors = config.validate();
+    if errors.is_empty() {
+        println!("{}", tr!("cli.valid", path = path.display()));
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{}", tr!("cli.invalid", count = errors.len(), path = path.display()));
+        for error in &errors {
+            eprintln!("   • {}", error);
+        }
+        ExitCode::FAILURE
+    }
+}
+
+/// Commande `explain` : valeurs effectives et couche qui fournit chacune
+pub fn explain(location: &ConfigLocation, args: &ExplainArgs) -> ExitCode {
+    let Some(layered) = load_layers(location, &args.overrides) else {
+        return ExitCode::FAILURE;
+    };
+
+    let values: Vec<_> = layered
+        .values()
+        .into_iter()
+        .filter(|(_, _, layer)| args.all || **layer != Layer::Defaults)
+        .collect();
+    let width = values.iter().map(|(key, _, _)| key.len()).max().unwrap_or(0);
+    for (key, value, layer) in &values {
+        println!("{:width$} = {:<12} ({})", key, value.to_string(), layer, width = width);
+    }
+    ExitCode::SUCCESS
+}
+
+/// Commande `print-default` : modèle de configuration commenté
+pub fn print_default() -> ExitCode {
+    print!("{}", schema::template());
+    ExitCode::SUCCESS
+}
+
+/// Commande `print-schema` : schéma JSON de la configuration
+pub fn print_schema() -> ExitCode {
+    print!("{}", schema::json_schema());
+    ExitCode::SUCCESS
+}
+
+/// Commande `convert` : relit une configuration et l'écrit dans un autre format
+pub fn convert(args: &ConvertArgs) -> ExitCode {
+    let format = match ConfigFormat::from_path(&args.output) {
+        Ok(format) => format,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(config) = load(&args.input) else {
+        return ExitCode::FAILURE;
+    };
+
+    let result = format
+        .serialize(&config)
+        .and_then(|content| Ok(fs::write(&args.output, content)?));
+
+    match result {
+        Ok(()) => {
+            println!("{}", tr!("cli.converted", input = args.input.display(), output = args.output.display()));
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", tr!("cli.write_failed", path = args.output.display(), error = e));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Commande `presets` : liste des presets utilisables avec la clé `preset`
+pub fn presets(location: &ConfigLocation, args: &PresetsArgs) -> ExitCode {
+    let library = PresetLibrary::load(&location.directory());
+    let kinds: Vec<PresetKind> = match args.kind {
+        Some(kind) => vec![kind],
+        None => PresetKind::ALL.to_vec(),
+    };
+
+    for kind in kinds {
+        let section = match kind {
+            PresetKind::Config => tr!("cli.preset_at_root"),
+            _ => tr!("cli.preset_in_section", section = kind.section()),
+        };
+        println!("{}", tr!("cli.preset_kind", kind = kind, key = PRESET_KEY, location = section));
+        for preset in library.presets(kind) {
+            let description = preset.description.as_deref().unwrap_or("");
+            match &preset.source {
+                PresetSource::Builtin => println!("   • {:<18} {}", preset.name, description),
+                PresetSource::User(path) => {
+                    println!("   • {:<18} {}", preset.name, tr!("cli.user_preset", description = description, path = path.display()))
+                }
+            }
+        }
+        println!();
+    }
+    ExitCode::SUCCESS
+}
+
+/// Affiche les réglages perdus ou approximés lors d'une conversion
+fn report_lossy<T>(conversion: &Conversion<T>) {
+    if !conversion.lossy.is_empty() {
+        eprintln!("{}", tr!("cli.lossy_conversion"));
+        for message in &conversion.lossy {
+            eprintln!("   • {}", message);
+        }
+    }
+}
+
+/// Écrit une configuration importée dans un fichier, ou l'affiche en TOML
+fn write_imported(config: &CrosshairConfig, output: Option<&std::path::Path>) -> ExitCode {
+    match output {
+        Some(path) => match config.save_to_file(path) {
+            Ok(()) => {
+                println!("{}", tr!("cli.imported", path = path.display()));
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("{}", tr!("cli.write_failed", path = path.display(), error = e));
+                ExitCode::FAILURE
+            }
+        },
+        None => match ConfigFormat::Toml.serialize(config) {
+            Ok(content) => {
+                print!("{}", content);
+                ExitCode::SUCCESS
+            }
+            Err(e) => {
+                eprintln!("❌ {}", e);
+                ExitCode::FAILURE
+            }
+        },
+    }
+}
+
+/// Commande `export-code` : code de partage de la configuration active
+pub fn export_code(location: &ConfigLocation) -> ExitCode {
+    let Some(LayeredConfig { config, .. }) = load_layers(location, &Overrides::default()) else {
+        return ExitCode::FAILURE;
+    };
+
+    match code::encode(&config) {
+        Ok(code) => {
+            println!("{}", code);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Commande `import-code` : configuration à partir d'un code de partage, validée
+pub fn import_code(args: &ImportArgs) -> ExitCode {
+    let config = match code::decode(&args.code) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let errors = config.validate();
+    if !errors.is_empty() {
+        eprintln!("{}", tr!("cli.invalid_code", count = errors.len()));
+        for error in &errors {
+            eprintln!("   • {}", error);
+        }
+        return ExitCode::FAILURE;
+    }
+
+    write_imported(&config, args.output.as_deref())
+}
+
+/// Commande `import-cs2` : configuration à partir d'un code de partage CS2
+pub fn import_cs2(args: &ImportArgs) -> ExitCode {
+    let crosshair = match Cs2Crosshair::decode(&args.code) {
+        Ok(crosshair) => crosshair,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let conversion = crosshair.to_config(&CrosshairConfig::default());
+    report_lossy(&conversion);
+    write_imported(&conversion.value, args.output.as_deref())
+}
+
+/// Commande `export-cs2` : code de partage CS2 de la configuration active
+pub fn export_cs2(location: &ConfigLocation) -> ExitCode {
+    let Some(LayeredConfig { config, .. }) = load_layers(location, &Overrides::default()) else {
+        return ExitCode::FAILURE;
+    };
+
+    let conversion = Cs2Crosshair::from_config(&config);
+    report_lossy(&conversion);
+    println!("{}", conversion.value.encode());
+    ExitCode::SUCCESS
+}
+
+/// Commande `import-valorant` : configuration à partir d'un code de profil Valorant
+pub fn import_valorant(args: &ImportArgs) -> ExitCode {
+    let crosshair = match ValorantCrosshair::parse(&args.code) {
+        Ok(crosshair) => crosshair,
+        Err(e) => {
+            eprintln!("❌ {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let conversion = crosshair.to_config(&CrosshairConfig::default());
+    report_lossy(&conversion);
+    write_imported(&conversion.value, args.output.as_deref())
+}
+
+/// Commande `export-valorant` : code de profil Valorant de la configuration active
+pub fn export_valorant(location: &ConfigLocation) -> ExitCode {
+    let Some(LayeredConfig { config, .. }) = load_layers(location, &Overrides::default()) else {
+        return ExitCode::FAILURE;
+    };
+
+    let conversion = ValorantCrosshair::from_config(&config);
+    report_lossy(&conversion);
+    println!("{}", conversion.value.serialize());
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::locations::ConfigSource;
+    use clap::CommandFactory;
+
+    fn location(path: PathBuf) -> ConfigLocation {
+        ConfigLocation { path, source: ConfigSource::CommandLine }
+    }
+
+    #[test]
+    fn command_line_is_well_formed() {
+        Cli::command().debug_assert();
+    }
+
+    #[test]
+    fn help_comes_from_the_message_catalog() {
+        let command = Cli::command();
+        assert_eq!(command.get_about().map(ToString::to_string), Some(tr!("help.about")));
+        let render = command.find_subcommand("render").unwrap();
+        assert_eq!(render.get_about().map(ToString::to_string), Some(tr!("help.render")));
+        let time = render.get_arguments().find(|arg| arg.get_id() == "time").unwrap();
+        assert_eq!(time.get_help().map(ToString::to_string), Some(tr!("help.time")));
+    }
+
+    #[test]
+    fn options_become_a_command_line_layer() {
+        let cli = Cli::parse_from([
+            "crosshair-overlay", "render", "--style", "dot", "--color", "red", "--size", "12", "--opacity", "0.5",
+            "--config", "custom.toml",
+        ]);
+        // --config est global : accepté après la sous-commande
+        assert_eq!(cli.config, Some(PathBuf::from("custom.toml")));
+        let Some(Command::Render(args)) = cli.command else {
+            panic!("sous-commande render attendue");
+        };
+        assert_eq!(args.output, PathBuf::from("crosshair.png"));
+
+        let expected: Table = toml::from_str(
+            r##"
+            crosshair = { style = "dot", color = "#FF0000", size = 12 }
+            window = { opacity = 0.5 }
+            "##,
+        )
+        .unwrap();
+        assert_eq!(args.overrides.to_table(), expected);
+        assert!(Overrides::default().to_table().is_empty());
+
+        assert!(Cli::parse_from(["crosshair-overlay"]).command.is_none());
+        assert!(Cli::try_parse_from(["crosshair-overlay", "run", "--color", "not-a-color"]).is_err());
+        assert!(Cli::try_parse_from(["crosshair-overlay", "presets", "unknown"]).is_err());
+        for size in ["0", "100000"] {
+            assert!(Cli::try_parse_from(["crosshair-overlay", "render", "--window-size", size]).is_err(), "{}", size);
+        }
+    }
+
+    #[test]
+    fn options_override_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[crosshair]\nsize = 30\nthickness = 4\n").unwrap();
+
+        let overrides = Overrides { size: Some(8), ..Overrides::default() };
+        let layered = load_layers(&location(path.clone()), &overrides).unwrap();
+        assert_eq!(layered.config.crosshair.size, 8);
+        assert_eq!(layered.config.crosshair.thickness, 4);
+
+        let origin = |key: &str| {
+            layered.values().into_iter().find(|(name, _, _)| name == key).map(|(_, _, layer)| layer.clone())
+        };
+        assert_eq!(origin("crosshair.size"), Some(Layer::CommandLine));
+        assert_eq!(origin("crosshair.thickness"), Some(Layer::File(path)));
+        assert_eq!(origin("crosshair.gap"), Some(Layer::Defaults));
+
+        // Un fichier illisible fait échouer la commande
+        fs::write(dir.path().join("broken.toml"), "[crosshair\n").unwrap();
+        assert!(load_layers(&location(dir.path().join("broken.toml")), &Overrides::default()).is_none());
+    }
+
+    #[test]
+    fn convert_writes_the_format_of_the_output_extension() {
+        let dir = tempfile::tempdir().unwrap();
+        let input = dir.path().join("config.toml");
+        fs::write(&input, "[crosshair]\nsize = 30\n").unwrap();
+
+        for extension in ["json", "yaml", "ron"] {
+            let output = dir.path().join(format!("config.{}", extension));
+            let args = ConvertArgs { input: input.clone(), output: output.clone() };
+            assert_eq!(convert(&args), ExitCode::SUCCESS, "{}", extension);
+            assert_eq!(CrosshairConfig::load_from_file(&output).unwrap().crosshair.size, 30, "{}", extension);
+        }
+
+        let args = ConvertArgs { input, output: dir.path().join("config.txt") };
+        assert_eq!(convert(&args), ExitCode::FAILURE);
+        assert!(!args.output.exists());
+    }
+
+    #[test]
+    fn imported_code_is_written_to_the_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut config = CrosshairConfig::default();
+        config.crosshair.size = 17;
+
+        let output = dir.path().join("shared.json");
+        let args = ImportArgs { code: code::encode(&config).unwrap(), output: Some(output.clone()) };
+        assert_eq!(import_code(&args), ExitCode::SUCCESS);
+        assert_eq!(CrosshairConfig::load_from_file(&output).unwrap().crosshair.size, 17);
+
+        let args = ImportArgs { code: "XH1-invalid".to_string(), output: Some(dir.path().join("invalid.toml")) };
+        assert_eq!(import_code(&args), ExitCode::FAILURE);
+        assert!(!dir.path().join("invalid.toml").exists());
+    }
+
+    #[test]
+    fn validate_checks_a_given_file_alone() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[profiles]\nactive = \"broken\"\n").unwrap();
+        fs::create_dir(dir.path().join("profiles")).unwrap();
+        fs::write(dir.path().join("profiles/broken.toml"), "[crosshair]\nsize = 0\n").unwrap();
+
+        // La configuration active inclut le profil invalide, le fichier seul est valide
+        let active = ValidateArgs { path: None };
+        assert_eq!(validate(&location(path.clone()), &active), ExitCode::FAILURE);
+        let alone = ValidateArgs { path: Some(path.clone()) };
+        assert_eq!(validate(&location(dir.path().join("other.toml")), &alone), ExitCode::SUCCESS);
+        let profile = ValidateArgs { path: Some(dir.path().join("profiles/broken.toml")) };
+        assert_eq!(validate(&location(path), &profile), ExitCode::FAILURE);
+    }
+}
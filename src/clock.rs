@@ -0,0 +1,74 @@
+use std::cell::Cell;
+use std::time::Instant;
+
+/// Source du temps des animations et des effets de souris
+///
+/// Le temps est exprimé en secondes depuis l'origine de l'horloge. Les effets
+/// ne lisent jamais l'heure système directement : avec une même horloge, une
+/// image est toujours rendue de la même façon.
+pub trait Clock {
+    /// Secondes écoulées depuis l'origine de l'horloge
+    fn now(&self) -> f32;
+
+    /// Convertit un instant système (ex: début d'un clic capturé par le hook) en temps de l'horloge
+    fn time_of(&self, instant: Instant) -> f32 {
+        self.now() - instant.elapsed().as_secs_f32()
+    }
+}
+
+/// Horloge réelle de l'overlay, démarrée à sa création
+#[derive(Debug, Clone, Copy)]
+pub struct RealClock {
+    start: Instant,
+}
+
+impl RealClock {
+    pub fn new() -> Self {
+        Self { start: Instant::now() }
+    }
+}
+
+impl Default for RealClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for RealClock {
+    fn now(&self) -> f32 {
+        self.start.elapsed().as_secs_f32()
+    }
+
+    fn time_of(&self, instant: Instant) -> f32 {
+        instant.saturating_duration_since(self.start).as_secs_f32()
+    }
+}
+
+/// Horloge avancée à la main : tests, rejeu et export d'images
+#[derive(Debug, Clone, Default)]
+pub struct ManualClock {
+    time: Cell<f32>,
+}
+
+impl ManualClock {
+    /// Horloge arrêtée sur `time` secondes
+    pub fn new(time: f32) -> Self {
+        Self { time: Cell::new(time) }
+    }
+
+    /// Place l'horloge sur `time` secondes
+    pub fn set(&self, time: f32) {
+        self.time.set(time);
+    }
+
+    /// Avance l'horloge de `seconds` secondes
+    pub fn advance(&self, seconds: f32) {
+        self.time.set(self.time.get() + seconds);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> f32 {
+        self.time.get()
+    }
+}
@@ -0,0 +1,486 @@
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+use crate::tr;
+
+/// Couleur RGBA partagée par le crosshair, l'outline, le point central et le fond
+///
+/// Syntaxes acceptées (insensibles à la casse) :
+/// `#RGB`, `#RGBA`, `#RRGGBB`, `#RRGGBBAA`, `rgb(255, 0, 0)`, `rgba(255, 0, 0, 0.5)`,
+/// `hsl(120, 100%, 50%)`, `hsla(120, 100%, 50%, 0.5)`, la syntaxe CSS sans virgules
+/// (`rgb(255 0 0 / 50%)`) et les noms de couleurs CSS (`red`, `dodgerblue`...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Color {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Color {
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    pub const RED: Self = Self::rgb(255, 0, 0);
+    pub const GREEN: Self = Self::rgb(0, 255, 0);
+
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    pub const fn rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    /// Convertit en pixel ARGB u32, l'alpha de la couleur étant multiplié par `alpha`
+    pub fn to_argb(self, alpha: f32) -> u32 {
+        let alpha = (self.a as f32 / 255.0) * alpha.clamp(0.0, 1.0);
+        let alpha_u8 = (alpha * 255.0).round() as u32;
+        (alpha_u8 << 24) | ((self.r as u32) << 16) | ((self.g as u32) << 8) | self.b as u32
+    }
+
+    /// Couleur à partir d'une teinte (degrés), saturation et luminosité (0.0-1.0)
+    fn from_hsl(hue: f32, saturation: f32, lightness: f32, alpha: u8) -> Self {
+        let hue = hue.rem_euclid(360.0) / 60.0;
+        let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+        let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+        let m = lightness - chroma / 2.0;
+
+        let (r, g, b) = match hue as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        let to_u8 = |v: f32| ((v + m).clamp(0.0, 1.0) * 255.0).round() as u8;
+        Self::rgba(to_u8(r), to_u8(g), to_u8(b), alpha)
+    }
+
+    fn parse_hex(hex: &str) -> Option<Self> {
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        // Chaque chiffre est doublé pour les formes courtes (#F80 -> #FF8800)
+        let digit = |i: usize| u8::from_str_radix(&hex[i..=i], 16).ok().map(|v| v * 17);
+        let pair = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+        match hex.len() {
+            3 => Some(Self::rgb(digit(0)?, digit(1)?, digit(2)?)),
+            4 => Some(Self::rgba(digit(0)?, digit(1)?, digit(2)?, digit(3)?)),
+            6 => Some(Self::rgb(pair(0)?, pair(2)?, pair(4)?)),
+            8 => Some(Self::rgba(pair(0)?, pair(2)?, pair(4)?, pair(6)?)),
+            _ => None,
+        }
+    }
+
+    fn parse_function(name: &str, args: &str) -> Result<Self, String> {
+        // "255, 0, 0, 0.5" ou "255 0 0 / 50%"
+        let (components, alpha) = match args.split_once('/') {
+            Some((components, alpha)) => (components, Some(alpha.trim())),
+            None => (args, None),
+        };
+        let mut parts: Vec<&str> = components
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|part| !part.is_empty())
+            .collect();
+
+        let alpha = match (alpha, parts.len()) {
+            (Some(alpha), 3) => Some(alpha),
+            (None, 4) => parts.pop(),
+            (None, 3) => None,
+            _ => return Err(tr!("color.components", function = name)),
+        };
+        let alpha = match alpha {
+            Some(alpha) => (parse_fraction(alpha, 1.0)? * 255.0).round() as u8,
+            None => 255,
+        };
+
+        match name {
+            "rgb" | "rgba" => {
+                let channel = |part: &str| -> Result<u8, String> {
+                    Ok((parse_fraction(part, 255.0)? * 255.0).round() as u8)
+                };
+                Ok(Self::rgba(channel(parts[0])?, channel(parts[1])?, channel(parts[2])?, alpha))
+            }
+            "hsl" | "hsla" => {
+                let hue = parts[0].strip_suffix("deg").unwrap_or(parts[0]);
+                let hue: f32 = hue
+                    .parse()
+                    .ok()
+                    .filter(|h: &f32| h.is_finite())
+                    .ok_or_else(|| tr!("color.hue", value = parts[0]))?;
+                let percent = |part: &str| -> Result<f32, String> {
+                    if part.ends_with('%') {
+                        parse_fraction(part, 1.0)
+                    } else {
+                        Err(tr!("color.percentage", value = part))
+                    }
+                };
+                Ok(Self::from_hsl(hue, percent(parts[1])?, percent(parts[2])?, alpha))
+            }
+            _ => Err(tr!("color.unknown_function", function = name)),
+        }
+    }
+}
+
+/// Lit une valeur entre 0 et `max`, ou un pourcentage, et la ramène entre 0.0 et 1.0
+fn parse_fraction(value: &str, max: f32) -> Result<f32, String> {
+    let (number, scale) = match value.strip_suffix('%') {
+        Some(number) => (number, 100.0),
+        None => (value, max),
+    };
+    number
+        .trim()
+        .parse::<f32>()
+        .ok()
+        .filter(|v| v.is_finite() && (0.0..=scale).contains(v))
+        .map(|v| v / scale)
+        .ok_or_else(|| {
+            if scale == 100.0 {
+                tr!("color.percentage_range", value = value)
+            } else {
+                tr!("color.range", value = value, max = max)
+            }
+        })
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.trim().to_lowercase();
+        let invalid = |detail: String| tr!("color.invalid", value = s, detail = detail);
+
+        if let Some(hex) = value.strip_prefix('#') {
+            return Self::parse_hex(hex)
+                .ok_or_else(|| invalid(tr!("color.hex_length")));
+        }
+
+        if let Some((name, rest)) = value.split_once('(') {
+            let args = rest
+                .strip_suffix(')')
+                .ok_or_else(|| invalid(tr!("color.closing_parenthesis")))?;
+            return Self::parse_function(name.trim(), args).map_err(invalid);
+        }
+
+        named_color(&value).ok_or_else(|| {
+            invalid(tr!("color.expected"))
+        })
+    }
+}
+
+impl fmt::Display for Color {
+    /// Forme canonique : `#RRGGBB`, ou `#RRGGBBAA` si la couleur est transparente
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:02X}{:02X}{:02X}", self.r, self.g, self.b)?;
+        if self.a != 255 {
+            write!(f, "{:02X}", self.a)?;
+        }
+        Ok(())
+    }
+}
+
+/// Texte (`"#00FF00"`) dans les fichiers de configuration, 4 octets RGBA
+/// dans les formats binaires comme les codes de partage
+impl Serialize for Color {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            [self.r, self.g, self.b, self.a].serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Color {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let text = String::deserialize(deserializer)?;
+            text.parse().map_err(serde::de::Error::custom)
+        } else {
+            let [r, g, b, a] = <[u8; 4]>::deserialize(deserializer)?;
+            Ok(Self::rgba(r, g, b, a))
+        }
+    }
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for Color {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let [r, g, b, a] = u.arbitrary()?;
+        Ok(Self::rgba(r, g, b, a))
+    }
+}
+
+/// Dans le schéma JSON, une couleur est un texte (la syntaxe n'est pas vérifiée par l'éditeur)
+impl JsonSchema for Color {
+    fn schema_name() -> Cow<'static, str> {
+        "Color".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Hex (#RGB, #RGBA, #RRGGBB, #RRGGBBAA), rgb(), rgba(), hsl(), hsla() or a CSS color name",
+            "examples": ["#00FF00", "rgb(0, 255, 0)", "hsl(120, 100%, 50%)", "lime"]
+        })
+    }
+}
+
+/// Couleurs nommées CSS (CSS Color Module Level 4)
+fn named_color(name: &str) -> Option<Color> {
+    let rgb = match name {
+        "transparent" => return Some(Color::rgba(0, 0, 0, 0)),
+        "aliceblue" => 0xF0F8FF,
+        "antiquewhite" => 0xFAEBD7,
+        "aqua" | "cyan" => 0x00FFFF,
+        "aquamarine" => 0x7FFFD4,
+        "azure" => 0xF0FFFF,
+        "beige" => 0xF5F5DC,
+        "bisque" => 0xFFE4C4,
+        "black" => 0x000000,
+        "blanchedalmond" => 0xFFEBCD,
+        "blue" => 0x0000FF,
+        "blueviolet" => 0x8A2BE2,
+        "brown" => 0xA52A2A,
+        "burlywood" => 0xDEB887,
+        "cadetblue" => 0x5F9EA0,
+        "chartreuse" => 0x7FFF00,
+        "chocolate" => 0xD2691E,
+        "coral" => 0xFF7F50,
+        "cornflowerblue" => 0x6495ED,
+        "cornsilk" => 0xFFF8DC,
+        "crimson" => 0xDC143C,
+        "darkblue" => 0x00008B,
+        "darkcyan" => 0x008B8B,
+        "darkgoldenrod" => 0xB8860B,
+        "darkgray" | "darkgrey" => 0xA9A9A9,
+        "darkgreen" => 0x006400,
+        "darkkhaki" => 0xBDB76B,
+        "darkmagenta" => 0x8B008B,
+        "darkolivegreen" => 0x556B2F,
+        "darkorange" => 0xFF8C00,
+        "darkorchid" => 0x9932CC,
+        "darkred" => 0x8B0000,
+        "darksalmon" => 0xE9967A,
+        "darkseagreen" => 0x8FBC8F,
+        "darkslateblue" => 0x483D8B,
+        "darkslategray" | "darkslategrey" => 0x2F4F4F,
+        "darkturquoise" => 0x00CED1,
+        "darkviolet" => 0x9400D3,
+        "deeppink" => 0xFF1493,
+        "deepskyblue" => 0x00BFFF,
+        "dimgray" | "dimgrey" => 0x696969,
+        "dodgerblue" => 0x1E90FF,
+        "firebrick" => 0xB22222,
+        "floralwhite" => 0xFFFAF0,
+        "forestgreen" => 0x228B22,
+        "fuchsia" | "magenta" => 0xFF00FF,
+        "gainsboro" => 0xDCDCDC,
+        "ghostwhite" => 0xF8F8FF,
+        "gold" => 0xFFD700,
+        "goldenrod" => 0xDAA520,
+        "gray" | "grey" => 0x808080,
+        "green" => 0x008000,
+        "greenyellow" => 0xADFF2F,
+        "honeydew" => 0xF0FFF0,
+        "hotpink" => 0xFF69B4,
+        "indianred" => 0xCD5C5C,
+        "indigo" => 0x4B0082,
+        "ivory" => 0xFFFFF0,
+        "khaki" => 0xF0E68C,
+        "lavender" => 0xE6E6FA,
+        "lavenderblush" => 0xFFF0F5,
+        "lawngreen" => 0x7CFC00,
+        "lemonchiffon" => 0xFFFACD,
+        "lightblue" => 0xADD8E6,
+        "lightcoral" => 0xF08080,
+        "lightcyan" => 0xE0FFFF,
+        "lightgoldenrodyellow" => 0xFAFAD2,
+        "lightgray" | "lightgrey" => 0xD3D3D3,
+        "lightgreen" => 0x90EE90,
+        "lightpink" => 0xFFB6C1,
+        "lightsalmon" => 0xFFA07A,
+        "lightseagreen" => 0x20B2AA,
+        "lightskyblue" => 0x87CEFA,
+        "lightslategray" | "lightslategrey" => 0x778899,
+        "lightsteelblue" => 0xB0C4DE,
+        "lightyellow" => 0xFFFFE0,
+        "lime" => 0x00FF00,
+        "limegreen" => 0x32CD32,
+        "linen" => 0xFAF0E6,
+        "maroon" => 0x800000,
+        "mediumaquamarine" => 0x66CDAA,
+        "mediumblue" => 0x0000CD,
+        "mediumorchid" => 0xBA55D3,
+        "mediumpurple" => 0x9370DB,
+        "mediumseagreen" => 0x3CB371,
+        "mediumslateblue" => 0x7B68EE,
+        "mediumspringgreen" => 0x00FA9A,
+        "mediumturquoise" => 0x48D1CC,
+        "mediumvioletred" => 0xC71585,
+        "midnightblue" => 0x191970,
+        "mintcream" => 0xF5FFFA,
+        "mistyrose" => 0xFFE4E1,
+        "moccasin" => 0xFFE4B5,
+        "navajowhite" => 0xFFDEAD,
+        "navy" => 0x000080,
+        "oldlace" => 0xFDF5E6,
+        "olive" => 0x808000,
+        "olivedrab" => 0x6B8E23,
+        "orange" => 0xFFA500,
+        "orangered" => 0xFF4500,
+        "orchid" => 0xDA70D6,
+        "palegoldenrod" => 0xEEE8AA,
+        "palegreen" => 0x98FB98,
+        "paleturquoise" => 0xAFEEEE,
+        "palevioletred" => 0xDB7093,
+        "papayawhip" => 0xFFEFD5,
+        "peachpuff" => 0xFFDAB9,
+        "peru" => 0xCD853F,
+        "pink" => 0xFFC0CB,
+        "plum" => 0xDDA0DD,
+        "powderblue" => 0xB0E0E6,
+        "purple" => 0x800080,
+        "rebeccapurple" => 0x663399,
+        "red" => 0xFF0000,
+        "rosybrown" => 0xBC8F8F,
+        "royalblue" => 0x4169E1,
+        "saddlebrown" => 0x8B4513,
+        "salmon" => 0xFA8072,
+        "sandybrown" => 0xF4A460,
+        "seagreen" => 0x2E8B57,
+        "seashell" => 0xFFF5EE,
+        "sienna" => 0xA0522D,
+        "silver" => 0xC0C0C0,
+        "skyblue" => 0x87CEEB,
+        "slateblue" => 0x6A5ACD,
+        "slategray" | "slategrey" => 0x708090,
+        "snow" => 0xFFFAFA,
+        "springgreen" => 0x00FF7F,
+        "steelblue" => 0x4682B4,
+        "tan" => 0xD2B48C,
+        "teal" => 0x008080,
+        "thistle" => 0xD8BFD8,
+        "tomato" => 0xFF6347,
+        "turquoise" => 0x40E0D0,
+        "violet" => 0xEE82EE,
+        "wheat" => 0xF5DEB3,
+        "white" => 0xFFFFFF,
+        "whitesmoke" => 0xF5F5F5,
+        "yellow" => 0xFFFF00,
+        "yellowgreen" => 0x9ACD32,
+        _ => return None,
+    };
+    Some(Color::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(text: &str) -> Color {
+        text.parse().unwrap_or_else(|e| panic!("{} : {}", text, e))
+    }
+
+    #[test]
+    fn hex_colors_accept_every_length() {
+        for (text, expected) in [
+            ("#F80", Color::rgb(0xFF, 0x88, 0x00)),
+            ("#f808", Color::rgba(0xFF, 0x88, 0x00, 0x88)),
+            ("#1E90FF", Color::rgb(0x1E, 0x90, 0xFF)),
+            ("#1e90ff80", Color::rgba(0x1E, 0x90, 0xFF, 0x80)),
+            ("  #000000FF  ", Color::BLACK),
+        ] {
+            assert_eq!(parse(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn rgb_functions_accept_numbers_percentages_and_slash_alpha() {
+        for (text, expected) in [
+            ("rgb(255, 0, 0)", Color::RED),
+            ("RGB( 1 , 2 , 3 )", Color::rgb(1, 2, 3)),
+            ("rgba(0, 0, 255, 0.5)", Color::rgba(0, 0, 255, 128)),
+            ("rgba(0, 0, 255, 25%)", Color::rgba(0, 0, 255, 64)),
+            ("rgb(100%, 50%, 0%)", Color::rgb(255, 128, 0)),
+            ("rgb(255 128 0 / 50%)", Color::rgba(255, 128, 0, 128)),
+            ("rgba(0 255 0 / 0)", Color::rgba(0, 255, 0, 0)),
+        ] {
+            assert_eq!(parse(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn hsl_functions_convert_to_rgb() {
+        for (text, expected) in [
+            ("hsl(0, 100%, 50%)", Color::RED),
+            ("hsl(120, 100%, 50%)", Color::GREEN),
+            ("hsl(240deg, 100%, 50%)", Color::rgb(0, 0, 255)),
+            ("hsl(-120, 100%, 50%)", Color::rgb(0, 0, 255)),
+            ("hsl(420, 100%, 50%)", Color::rgb(255, 255, 0)),
+            ("hsl(180, 100%, 25%)", Color::rgb(0, 128, 128)),
+            ("hsl(300 100% 25%)", Color::rgb(128, 0, 128)),
+            ("hsl(0, 0%, 50%)", Color::rgb(128, 128, 128)),
+            ("hsl(0, 0%, 100%)", Color::rgb(255, 255, 255)),
+            ("hsla(120, 100%, 50%, 0.5)", Color::rgba(0, 255, 0, 128)),
+            ("hsl(120deg 100% 50% / 25%)", Color::rgba(0, 255, 0, 64)),
+        ] {
+            assert_eq!(parse(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn css_names_are_recognized() {
+        for (text, expected) in [
+            ("red", Color::RED),
+            ("Lime", Color::GREEN),
+            ("dodgerblue", Color::rgb(0x1E, 0x90, 0xFF)),
+            ("RebeccaPurple", Color::rgb(0x66, 0x33, 0x99)),
+            ("transparent", Color::rgba(0, 0, 0, 0)),
+        ] {
+            assert_eq!(parse(text), expected, "{}", text);
+        }
+    }
+
+    #[test]
+    fn invalid_colors_are_rejected() {
+        for text in [
+            "",
+            "#",
+            "#12",
+            "#12345",
+            "#1234567",
+            "#GGG",
+            "#ééé",
+            "rgb(1, 2)",
+            "rgb(1, 2, 3, 4, 5)",
+            "rgb(1, 2, 3 / 0.5, 1)",
+            "rgb(256, 0, 0)",
+            "rgb(-1, 0, 0)",
+            "rgb(101%, 0, 0)",
+            "rgb(nan, 0, 0)",
+            "rgba(0, 0, 0, 2)",
+            "rgb(0 0 0 / 150%)",
+            "rgb(1, 2, 3",
+            "hsl(120, 100, 50%)",
+            "hsl(green, 100%, 50%)",
+            "hsl(inf, 100%, 50%)",
+            "cmyk(0, 0, 0)",
+            "notacolor",
+        ] {
+            assert!(text.parse::<Color>().is_err(), "{} ne devrait pas être accepté", text);
+        }
+    }
+
+    #[test]
+    fn display_gives_the_canonical_form() {
+        for (text, expected) in [("#f80", "#FF8800"), ("rgba(255, 136, 0, 0.5)", "#FF880080"), ("lime", "#00FF00")] {
+            let color = parse(text);
+            assert_eq!(color.to_string(), expected);
+            assert_eq!(parse(expected), color);
+        }
+    }
+}
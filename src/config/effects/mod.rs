@@ -8,27 +8,23 @@ pub use shake::ShakeEffect;
 pub use rainbow::RainbowEffect;
 pub use mouse::MouseEffect;
 
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct Effects {
+    #[schemars(description = "Pulsing transparency")]
     pub pulse: PulseEffect,
+    #[schemars(description = "Trembling crosshair")]
     pub shake: ShakeEffect,
+    #[schemars(description = "Cycling rainbow colors")]
     pub rainbow: RainbowEffect,
+    #[schemars(description = "Effects triggered by mouse buttons")]
     pub mouse: MouseEffect,
 }
 
-impl Default for Effects {
-    fn default() -> Self {
-        Self {
-            pulse: PulseEffect::default(),
-            shake: ShakeEffect::default(),
-            rainbow: RainbowEffect::default(),
-            mouse: MouseEffect::default(),
-        }
-    }
-}
-
 impl Effects {
     /// Vérifie si des effets animés sont activés (qui nécessitent un redraw continu)
     pub fn has_animated_effects(&self) -> bool {
@@ -1,15 +1,18 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::{Mutex, OnceLock};
 use std::time::Instant;
+#[cfg(windows)]
 use windows::Win32::{
-    Foundation::{HINSTANCE, LPARAM, LRESULT, WPARAM},
+    Foundation::{HINSTANCE, LPARAM, LRESULT, POINT, WPARAM},
     UI::WindowsAndMessaging::{
-        CallNextHookEx, SetWindowsHookExW, UnhookWindowsHookEx,
-        HC_ACTION, HHOOK, WH_MOUSE_LL, WM_LBUTTONDOWN, WM_LBUTTONUP,
+        CallNextHookEx, GetCursorPos, SetWindowsHookExW, UnhookWindowsHookEx,
+        HC_ACTION, HHOOK, MSLLHOOKSTRUCT, WH_MOUSE_LL, WM_LBUTTONDOWN, WM_LBUTTONUP,
         WM_RBUTTONDOWN, WM_RBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP,
     },
 };
 use super::MouseState;
+use crate::clock::Clock;
+use crate::tr;
 
 // Variables atomiques pour l'état des boutons
 static LEFT_PRESSED: AtomicBool = AtomicBool::new(false);
@@ -19,18 +22,40 @@ static MIDDLE_PRESSED: AtomicBool = AtomicBool::new(false);
 // Variable pour détecter les changements d'état (pour optimiser les redraws)
 static STATE_CHANGED: AtomicBool = AtomicBool::new(false);
 
+// Position globale du curseur en pixels physiques de l'écran (mode follow_cursor)
+static CURSOR_X: AtomicI32 = AtomicI32::new(0);
+static CURSOR_Y: AtomicI32 = AtomicI32::new(0);
+static CURSOR_KNOWN: AtomicBool = AtomicBool::new(false);
+static CURSOR_MOVED: AtomicBool = AtomicBool::new(false);
+
 // Timing pour les effets
 static PRESS_START_TIME: OnceLock<Mutex<Option<Instant>>> = OnceLock::new();
 
 pub struct MouseCapture {
+    #[cfg(windows)]
     hook: Option<HHOOK>,
 }
 
+impl Default for MouseCapture {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl MouseCapture {
     pub fn new() -> Self {
-        Self { hook: None }
+        Self {
+            #[cfg(windows)]
+            hook: None,
+        }
     }
 
+    #[cfg(not(windows))]
+    pub fn start(&mut self) -> Result<(), String> {
+        Err(tr!("mouse.unsupported"))
+    }
+
+    #[cfg(windows)]
     pub fn start(&mut self) -> Result<(), String> {
         if self.hook.is_some() {
             return Ok(()); // Déjà démarré
@@ -45,7 +70,7 @@ impl MouseCapture {
             );
 
             if hook.is_err() {
-                return Err("Échec de l'installation du hook de souris".to_string());
+                return Err(tr!("mouse.hook_failed"));
             }
 
             self.hook = Some(hook.unwrap());
@@ -55,9 +80,10 @@ impl MouseCapture {
     }
 
     pub fn stop(&mut self) {
+        #[cfg(windows)]
         if let Some(hook) = self.hook.take() {
             unsafe {
-                UnhookWindowsHookEx(hook);
+                let _ = UnhookWindowsHookEx(hook);
             }
         }
         
@@ -66,6 +92,8 @@ impl MouseCapture {
         RIGHT_PRESSED.store(false, Ordering::Relaxed);
         MIDDLE_PRESSED.store(false, Ordering::Relaxed);
         
+        CURSOR_KNOWN.store(false, Ordering::Relaxed);
+
         // Réinitialiser le temps de clic
         if let Some(time_mutex) = PRESS_START_TIME.get() {
             if let Ok(mut time) = time_mutex.lock() {
@@ -74,8 +102,8 @@ impl MouseCapture {
         }
     }
 
-    /// Récupère l'état actuel de la souris pour les effets
-    pub fn get_mouse_state(&self) -> MouseState {
+    /// Récupère l'état actuel de la souris pour les effets, daté selon l'horloge
+    pub fn get_mouse_state(&self, clock: &dyn Clock) -> MouseState {
         let left = LEFT_PRESSED.load(Ordering::Relaxed);
         let right = RIGHT_PRESSED.load(Ordering::Relaxed);
         let middle = MIDDLE_PRESSED.load(Ordering::Relaxed);
@@ -85,7 +113,8 @@ impl MouseCapture {
             .get_or_init(|| Mutex::new(None))
             .lock()
             .ok()
-            .and_then(|guard| *guard);
+            .and_then(|guard| *guard)
+            .map(|instant| clock.time_of(instant));
 
         MouseState {
             left_pressed: left,
@@ -94,15 +123,12 @@ impl MouseCapture {
             press_time,
         }
     }
-
-    /// Vérifie si un bouton est actuellement pressé
-    pub fn is_any_button_pressed(&self) -> bool {
-        LEFT_PRESSED.load(Ordering::Relaxed) ||
-        RIGHT_PRESSED.load(Ordering::Relaxed) ||
-        MIDDLE_PRESSED.load(Ordering::Relaxed)
-    }
 }
 
+// Le hook n'est qu'un identifiant : il peut être libéré depuis n'importe quel thread
+#[cfg(windows)]
+unsafe impl Send for MouseCapture {}
+
 impl Drop for MouseCapture {
     fn drop(&mut self) {
         self.stop();
@@ -110,12 +136,17 @@ impl Drop for MouseCapture {
 }
 
 // Fonction de callback pour le hook de souris
+#[cfg(windows)]
 unsafe extern "system" fn mouse_hook_proc(
     code: i32,
     wparam: WPARAM,
     lparam: LPARAM,
 ) -> LRESULT {
     if code == HC_ACTION as i32 {
+        // Chaque événement (déplacement compris) donne la position du curseur
+        let info = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+        set_cursor_position(info.pt.x, info.pt.y);
+
         match wparam.0 as u32 {
             // Bouton gauche
             WM_LBUTTONDOWN => {
@@ -148,7 +179,19 @@ unsafe extern "system" fn mouse_hook_proc(
     CallNextHookEx(None, code, wparam, lparam)
 }
 
+/// Enregistre la position du curseur et signale un déplacement
+#[cfg_attr(not(windows), allow(dead_code))]
+fn set_cursor_position(x: i32, y: i32) {
+    let old_x = CURSOR_X.swap(x, Ordering::Relaxed);
+    let old_y = CURSOR_Y.swap(y, Ordering::Relaxed);
+    let was_known = CURSOR_KNOWN.swap(true, Ordering::Relaxed);
+    if !was_known || old_x != x || old_y != y {
+        CURSOR_MOVED.store(true, Ordering::Relaxed);
+    }
+}
+
 /// Helper pour gérer l'état des boutons et le timing
+#[cfg_attr(not(windows), allow(dead_code))]
 fn set_button_state(pressed: bool, is_left: bool, is_right: bool, is_middle: bool) {
     // Vérifier l'état actuel
     let any_was_pressed = LEFT_PRESSED.load(Ordering::Relaxed) ||
@@ -187,50 +230,65 @@ fn set_button_state(pressed: bool, is_left: bool, is_right: bool, is_middle: boo
 }
 
 // Instance globale de la capture de souris (pour éviter les problèmes de lifetime)
-static mut GLOBAL_MOUSE_CAPTURE: Option<MouseCapture> = None;
+static GLOBAL_MOUSE_CAPTURE: OnceLock<Mutex<Option<MouseCapture>>> = OnceLock::new();
+
+fn global_capture() -> std::sync::MutexGuard<'static, Option<MouseCapture>> {
+    let capture = GLOBAL_MOUSE_CAPTURE.get_or_init(|| Mutex::new(None));
+    // Un panic pendant le verrouillage ne rend pas l'état des boutons incohérent
+    capture.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
 
 /// Initialise la capture globale de souris
 pub fn initialize_global_mouse_capture() -> Result<(), String> {
-    unsafe {
-        if GLOBAL_MOUSE_CAPTURE.is_none() {
-            let mut capture = MouseCapture::new();
-            capture.start()?;
-            GLOBAL_MOUSE_CAPTURE = Some(capture);
-        }
+    let mut global = global_capture();
+    if global.is_none() {
+        let mut capture = MouseCapture::new();
+        capture.start()?;
+        *global = Some(capture);
     }
     Ok(())
 }
 
 /// Arrête la capture globale de souris
 pub fn shutdown_global_mouse_capture() {
-    unsafe {
-        if let Some(mut capture) = GLOBAL_MOUSE_CAPTURE.take() {
-            capture.stop();
-        }
+    if let Some(mut capture) = global_capture().take() {
+        capture.stop();
     }
 }
 
 /// Récupère l'état de la souris depuis la capture globale
-pub fn get_global_mouse_state() -> MouseState {
-    unsafe {
-        GLOBAL_MOUSE_CAPTURE
-            .as_ref()
-            .map(|capture| capture.get_mouse_state())
-            .unwrap_or_default()
-    }
-}
-
-/// Vérifie si un bouton est pressé depuis la capture globale
-pub fn is_any_button_pressed() -> bool {
-    unsafe {
-        GLOBAL_MOUSE_CAPTURE
-            .as_ref()
-            .map(|capture| capture.is_any_button_pressed())
-            .unwrap_or(false)
-    }
+pub fn get_global_mouse_state(clock: &dyn Clock) -> MouseState {
+    global_capture()
+        .as_ref()
+        .map(|capture| capture.get_mouse_state(clock))
+        .unwrap_or_default()
 }
 
 /// Vérifie si l'état de la souris a changé (pour optimiser les redraws)
 pub fn has_mouse_state_changed() -> bool {
     STATE_CHANGED.swap(false, Ordering::Relaxed)
-} 
\ No newline at end of file
+}
+
+/// Position globale du curseur (pixels physiques), None tant que la capture ne l'a pas reçue
+pub fn get_global_cursor_position() -> Option<(i32, i32)> {
+    CURSOR_KNOWN
+        .load(Ordering::Relaxed)
+        .then(|| (CURSOR_X.load(Ordering::Relaxed), CURSOR_Y.load(Ordering::Relaxed)))
+}
+
+/// Vérifie si le curseur a bougé depuis le dernier appel
+pub fn has_cursor_moved() -> bool {
+    CURSOR_MOVED.swap(false, Ordering::Relaxed)
+}
+
+/// Position actuelle du curseur : demandée au système si possible, sinon la dernière reçue par la capture
+pub fn query_cursor_position() -> Option<(i32, i32)> {
+    #[cfg(windows)]
+    {
+        let mut point = POINT::default();
+        if unsafe { GetCursorPos(&mut point) }.is_ok() {
+            return Some((point.x, point.y));
+        }
+    }
+    get_global_cursor_position()
+}
@@ -1,17 +1,28 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use super::MouseState;
+use crate::clock::Clock;
 
 /// Effet qui modifie le gap du crosshair selon les clics de souris
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Expand or contract the crosshair while a button is held")]
 pub struct GapEffect {
+    #[schemars(description = "Enable the gap effect")]
     pub enabled: bool,
+    #[schemars(description = "Default effect")]
     pub mode: GapMode,
+    #[schemars(description = "Effect multiplier", range(min = 0.0, max = 5.0))]
     pub intensity: f32,        // Multiplicateur de l'effet (0.0-5.0)
+    #[schemars(description = "Animate the change instead of jumping")]
     pub smooth_transition: bool, // Transition progressive ou instantanée
+    #[schemars(description = "Effect of each mouse button")]
     pub button_binding: GapButtonBinding,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[serde(rename_all = "lowercase")]
 pub enum GapMode {
     Expand,    // Écarte le crosshair (augmente le gap)
@@ -19,10 +30,18 @@ pub enum GapMode {
     Toggle,    // Alterne entre écarter et rapprocher
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct GapButtonBinding {
+    #[serde(with = "super::button")]
+    #[schemars(schema_with = "super::button::schema::<GapMode>", description = "Effect of the left button (\"none\" for no effect)")]
     pub left_click: Option<GapMode>,   // Effet pour clic gauche
+    #[serde(with = "super::button")]
+    #[schemars(schema_with = "super::button::schema::<GapMode>", description = "Effect of the right button (\"none\" for no effect)")]
     pub right_click: Option<GapMode>,  // Effet pour clic droit  
+    #[serde(with = "super::button")]
+    #[schemars(schema_with = "super::button::schema::<GapMode>", description = "Effect of the middle button, the wheel (\"none\" for no effect)", example = &"toggle")]
     pub middle_click: Option<GapMode>, // Effet pour clic molette
 }
 
@@ -50,7 +69,7 @@ impl Default for GapButtonBinding {
 
 impl GapEffect {
     /// Applique l'effet de gap selon l'état de la souris
-    pub fn apply_gap(&self, original_gap: u32, original_size: u32, mouse_state: &MouseState) -> (u32, u32) {
+    pub fn apply_gap(&self, original_gap: u32, original_size: u32, mouse_state: &MouseState, clock: &dyn Clock) -> (u32, u32) {
         if !self.enabled {
             return (original_gap, original_size);
         }
@@ -59,7 +78,7 @@ impl GapEffect {
         let active_mode = self.get_active_mode(mouse_state);
         
         if let Some(mode) = active_mode {
-            self.calculate_modified_gap_and_size(original_gap, original_size, mode, mouse_state)
+            self.calculate_modified_gap_and_size(original_gap, original_size, mode, mouse_state, clock)
         } else {
             (original_gap, original_size)
         }
@@ -84,12 +103,12 @@ impl GapEffect {
     }
 
     /// Calcule le gap et la taille modifiés selon le mode et l'intensité
-    fn calculate_modified_gap_and_size(&self, original_gap: u32, original_size: u32, mode: GapMode, mouse_state: &MouseState) -> (u32, u32) {
+    fn calculate_modified_gap_and_size(&self, original_gap: u32, original_size: u32, mode: GapMode, mouse_state: &MouseState, clock: &dyn Clock) -> (u32, u32) {
         let base_gap = original_gap as f32;
         let base_size = original_size as f32;
         let intensity_factor = if self.smooth_transition {
             // Transition progressive basée sur la durée du clic
-            let duration = mouse_state.press_duration();
+            let duration = mouse_state.press_duration(clock);
             (duration * 2.0).min(1.0) // Max 1.0 après 0.5 seconde
         } else {
             1.0 // Effet instantané
@@ -116,7 +135,7 @@ impl GapEffect {
             },
             GapMode::Toggle => {
                 // Alterner selon le temps
-                let cycle_time = mouse_state.press_duration() % 1.0; // Cycle de 1 seconde
+                let cycle_time = mouse_state.press_duration(clock) % 1.0; // Cycle de 1 seconde
                 if cycle_time < 0.5 {
                     let gap_increase = base_gap * self.intensity * intensity_factor * 0.5;
                     let size_increase = gap_increase;
@@ -1,5 +1,8 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use crate::clock::{Clock, ManualClock};
+
 // Modules pour chaque type d'effet de souris
 pub mod gap_effect;
 pub mod visibility_effect;
@@ -8,22 +11,58 @@ pub mod capture;
 pub use gap_effect::GapEffect;
 pub use visibility_effect::VisibilityEffect;
 pub use capture::{initialize_global_mouse_capture, shutdown_global_mouse_capture, get_global_mouse_state, has_mouse_state_changed};
+pub use capture::{get_global_cursor_position, has_cursor_moved, query_cursor_position};
 
 /// Configuration principale des effets de souris
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Effects triggered by mouse buttons")]
 pub struct MouseEffect {
+    #[schemars(description = "Enable mouse effects (global switch)")]
     pub enabled: bool,
+    #[schemars(description = "Expand or contract the crosshair while a button is held")]
     pub gap_effect: GapEffect,
+    #[schemars(description = "Hide parts of the crosshair while a button is held")]
     pub visibility_effect: VisibilityEffect,
 }
 
-impl Default for MouseEffect {
-    fn default() -> Self {
-        Self {
-            enabled: false,
-            gap_effect: GapEffect::default(),
-            visibility_effect: VisibilityEffect::default(),
+/// Effet optionnel d'un bouton, `"none"` quand le bouton n'a pas d'effet
+///
+/// Sans valeur explicite, un bouton sans effet disparaîtrait du fichier et
+/// reprendrait son effet par défaut à la relecture.
+mod button {
+    use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+    use serde::de::IntoDeserializer;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    /// Texte d'un bouton sans effet
+    const NONE: &str = "none";
+
+    /// `"none"` dans les fichiers de configuration, une option dans les formats binaires
+    pub fn serialize<T: Serialize, S: Serializer>(action: &Option<T>, serializer: S) -> Result<S::Ok, S::Error> {
+        if !serializer.is_human_readable() {
+            return action.serialize(serializer);
+        }
+        match action {
+            Some(action) => action.serialize(serializer),
+            None => serializer.serialize_str(NONE),
+        }
+    }
+
+    pub fn deserialize<'de, T: Deserialize<'de>, D: Deserializer<'de>>(deserializer: D) -> Result<Option<T>, D::Error> {
+        if !deserializer.is_human_readable() {
+            return Option::<T>::deserialize(deserializer);
+        }
+        let text = String::deserialize(deserializer)?;
+        if text.eq_ignore_ascii_case(NONE) {
+            return Ok(None);
         }
+        T::deserialize(text.into_deserializer()).map(Some)
+    }
+
+    pub fn schema<T: JsonSchema>(generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({ "anyOf": [generator.subschema_for::<T>(), { "const": NONE }] })
     }
 }
 
@@ -33,7 +72,8 @@ pub struct MouseState {
     pub left_pressed: bool,
     pub right_pressed: bool,
     pub middle_pressed: bool,
-    pub press_time: Option<std::time::Instant>,
+    /// Début du clic, en secondes de l'horloge des effets
+    pub press_time: Option<f32>,
 }
 
 impl MouseState {
@@ -41,9 +81,10 @@ impl MouseState {
         self.left_pressed || self.right_pressed || self.middle_pressed
     }
     
-    pub fn press_duration(&self) -> f32 {
+    /// Durée du clic en cours selon l'horloge
+    pub fn press_duration(&self, clock: &dyn Clock) -> f32 {
         self.press_time
-            .map(|time| time.elapsed().as_secs_f32())
+            .map(|time| (clock.now() - time).max(0.0))
             .unwrap_or(0.0)
     }
 }
@@ -54,23 +95,41 @@ impl MouseEffect {
         original_gap: u32,
         original_size: u32,
         original_alpha: f32,
-        mouse_state: &MouseState
+        mouse_state: &MouseState,
+        clock: &dyn Clock
     ) -> (u32, u32, f32, VisibilityMask) {
         if !self.enabled || !mouse_state.any_pressed() {
             return (original_gap, original_size, original_alpha, VisibilityMask::default());
         }
 
         // Appliquer l'effet de gap (qui peut aussi modifier la taille)
-        let (modified_gap, modified_size) = self.gap_effect.apply_gap(original_gap, original_size, mouse_state);
+        let (modified_gap, modified_size) = self.gap_effect.apply_gap(original_gap, original_size, mouse_state, clock);
         
         // Appliquer l'effet de visibilité
         let (modified_alpha, visibility_mask) = self.visibility_effect.apply_visibility(
             original_alpha, 
-            mouse_state
+            mouse_state,
+            clock
         );
 
         (modified_gap, modified_size, modified_alpha, visibility_mask)
     }
+
+    /// Plus grande taille atteinte pendant un clic, effet de gap complètement appliqué
+    pub fn max_size(&self, original_gap: u32, original_size: u32) -> u32 {
+        // Une seconde après le clic, la transition progressive est terminée
+        let clock = ManualClock::new(1.0);
+        let pressed = |left_pressed, right_pressed, middle_pressed| MouseState {
+            left_pressed,
+            right_pressed,
+            middle_pressed,
+            press_time: Some(0.0),
+        };
+        [pressed(true, false, false), pressed(false, true, false), pressed(false, false, true)]
+            .iter()
+            .map(|mouse_state| self.apply_effects(original_gap, original_size, 1.0, mouse_state, &clock).1)
+            .fold(original_size, u32::max)
+    }
 }
 
 /// Masque de visibilité pour contrôler quelles parties du crosshair sont visibles
@@ -1,18 +1,30 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use super::{MouseState, VisibilityMask};
+use crate::clock::Clock;
 
 /// Effet qui contrôle la visibilité des parties du crosshair selon les clics
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Hide parts of the crosshair while a button is held")]
 pub struct VisibilityEffect {
+    #[schemars(description = "Enable the visibility effect")]
     pub enabled: bool,
+    #[schemars(description = "Default part to hide")]
     pub hide_mode: HideMode,
+    #[schemars(description = "How much the hidden parts fade (1.0 = fully hidden)", range(min = 0.0, max = 1.0))]
     pub fade_percentage: f32,      // Pourcentage de disparition (0.0-1.0)
+    #[schemars(description = "Animate the fade instead of jumping")]
     pub smooth_fade: bool,         // Transition progressive
+    #[schemars(description = "Fade speed (higher = faster)", range(min = 0.1, max = 10.0))]
     pub fade_speed: f32,           // Vitesse de disparition (0.1-10.0, plus élevé = plus rapide)
+    #[schemars(description = "Part hidden by each mouse button")]
     pub button_binding: VisibilityButtonBinding,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[serde(rename_all = "lowercase")]
 pub enum HideMode {
     Full,      // Cache tout le crosshair
@@ -26,10 +38,18 @@ pub enum HideMode {
     Cross,     // Cache en forme de croix (garde les coins)
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct VisibilityButtonBinding {
+    #[serde(with = "super::button")]
+    #[schemars(schema_with = "super::button::schema::<HideMode>", description = "Part hidden by the left button (\"none\" for no effect)")]
     pub left_click: Option<HideMode>,   // Effet pour clic gauche
+    #[serde(with = "super::button")]
+    #[schemars(schema_with = "super::button::schema::<HideMode>", description = "Part hidden by the right button (\"none\" for no effect)")]
     pub right_click: Option<HideMode>,  // Effet pour clic droit
+    #[serde(with = "super::button")]
+    #[schemars(schema_with = "super::button::schema::<HideMode>", description = "Part hidden by the middle button, the wheel (\"none\" for no effect)", example = &"full")]
     pub middle_click: Option<HideMode>, // Effet pour clic molette
 }
 
@@ -58,7 +78,7 @@ impl Default for VisibilityButtonBinding {
 
 impl VisibilityEffect {
     /// Applique l'effet de visibilité selon l'état de la souris
-    pub fn apply_visibility(&self, original_alpha: f32, mouse_state: &MouseState) -> (f32, VisibilityMask) {
+    pub fn apply_visibility(&self, original_alpha: f32, mouse_state: &MouseState, clock: &dyn Clock) -> (f32, VisibilityMask) {
         if !self.enabled {
             return (original_alpha, VisibilityMask::default());
         }
@@ -67,8 +87,8 @@ impl VisibilityEffect {
         let active_mode = self.get_active_mode(mouse_state);
         
         if let Some(mode) = active_mode {
-            let visibility_mask = self.create_visibility_mask(mode, mouse_state);
-            let modified_alpha = self.calculate_alpha(original_alpha, mouse_state);
+            let visibility_mask = self.create_visibility_mask(mode, mouse_state, clock);
+            let modified_alpha = self.calculate_alpha(original_alpha, mouse_state, clock);
             (modified_alpha, visibility_mask)
         } else {
             (original_alpha, VisibilityMask::default())
@@ -94,10 +114,10 @@ impl VisibilityEffect {
     }
 
     /// Crée le masque de visibilité selon le mode
-    fn create_visibility_mask(&self, mode: HideMode, mouse_state: &MouseState) -> VisibilityMask {
+    fn create_visibility_mask(&self, mode: HideMode, mouse_state: &MouseState, clock: &dyn Clock) -> VisibilityMask {
         let intensity_factor = if self.smooth_fade {
             // Transition progressive basée sur la durée du clic et la vitesse configurée
-            let duration = mouse_state.press_duration();
+            let duration = mouse_state.press_duration(clock);
             (duration * self.fade_speed).min(1.0) // Vitesse configurable
         } else {
             1.0 // Effet instantané
@@ -160,9 +180,9 @@ impl VisibilityEffect {
     }
 
     /// Calcule l'alpha modifié
-    fn calculate_alpha(&self, original_alpha: f32, mouse_state: &MouseState) -> f32 {
+    fn calculate_alpha(&self, original_alpha: f32, mouse_state: &MouseState, clock: &dyn Clock) -> f32 {
         let intensity_factor = if self.smooth_fade {
-            let duration = mouse_state.press_duration();
+            let duration = mouse_state.press_duration(clock);
             (duration * self.fade_speed).min(1.0) // Utiliser la même vitesse que pour le masque
         } else {
             1.0
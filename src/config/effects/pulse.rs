@@ -1,11 +1,20 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct PulseEffect {
+    #[schemars(description = "Enable the pulse effect")]
     pub enabled: bool,
+    #[schemars(description = "Pulses per second")]
     pub speed: f32,        // Vitesse de pulsation (cycles par seconde)
+    #[schemars(description = "Minimum transparency", range(min = 0.0, max = 1.0))]
     pub min_alpha: f32,    // Alpha minimum (0.0-1.0)
+    #[schemars(description = "Maximum transparency", range(min = 0.0, max = 1.0))]
     pub max_alpha: f32,    // Alpha maximum (0.0-1.0)
+    #[schemars(description = "Pulse the opacity of the whole window (background included) instead of the crosshair")]
+    pub whole_window: bool, // Pulsation de l'opacité globale de la fenêtre
 }
 
 impl Default for PulseEffect {
@@ -15,23 +24,32 @@ impl Default for PulseEffect {
             speed: 2.0,      // 2 pulsations par seconde
             min_alpha: 0.3,  // Minimum 30% d'opacité
             max_alpha: 1.0,  // Maximum 100% d'opacité
+            whole_window: false,
         }
     }
 }
 
 impl PulseEffect {
+    /// Multiplicateur d'alpha de la pulsation à cet instant
+    fn alpha_multiplier(&self, time: f32) -> f32 {
+        // Calcul du facteur de pulsation (oscillation sinusoïdale)
+        let pulse_factor = (time * self.speed * 2.0 * std::f32::consts::PI).sin();
+        let pulse_factor = (pulse_factor + 1.0) / 2.0; // Normaliser entre 0 et 1
+
+        // Interpolation entre min_alpha et max_alpha
+        self.min_alpha + (self.max_alpha - self.min_alpha) * pulse_factor
+    }
+
     /// Applique l'effet pulse à une couleur selon le temps
+    ///
+    /// Avec `whole_window`, la couleur n'est pas modifiée : la pulsation porte
+    /// sur l'opacité de la fenêtre (voir [`PulseEffect::window_opacity`]).
     pub fn apply(&self, color: u32, time: f32) -> u32 {
-        if !self.enabled {
+        if !self.enabled || self.whole_window {
             return color;
         }
 
-        // Calcul du facteur de pulsation (oscillation sinusoïdale)
-        let pulse_factor = (time * self.speed * 2.0 * std::f32::consts::PI).sin();
-        let pulse_factor = (pulse_factor + 1.0) / 2.0; // Normaliser entre 0 et 1
-        
-        // Interpolation entre min_alpha et max_alpha
-        let alpha_multiplier = self.min_alpha + (self.max_alpha - self.min_alpha) * pulse_factor;
+        let alpha_multiplier = self.alpha_multiplier(time);
         
         // Extraire les composants RGBA
         let original_alpha = ((color >> 24) & 0xFF) as f32 / 255.0;
@@ -40,8 +58,38 @@ impl PulseEffect {
         let b = color & 0xFF;
         
         // Appliquer le multiplicateur d'alpha
-        let new_alpha = (original_alpha * alpha_multiplier * 255.0) as u32;
+        let new_alpha = ((original_alpha * alpha_multiplier).clamp(0.0, 1.0) * 255.0) as u32;
         
         (new_alpha << 24) | (r << 16) | (g << 8) | b
     }
+
+    /// Multiplicateur de l'opacité de toute la fenêtre (1.0 sans `whole_window`)
+    pub fn window_opacity(&self, time: f32) -> f32 {
+        if self.enabled && self.whole_window {
+            self.alpha_multiplier(time)
+        } else {
+            1.0
+        }
+    }
+
+    /// Presets pour différents styles d'usage
+    pub fn preset_breathing() -> Self {
+        Self {
+            enabled: true,
+            speed: 0.5,      // Respiration lente
+            min_alpha: 0.4,
+            max_alpha: 1.0,
+            whole_window: false,
+        }
+    }
+
+    pub fn preset_alert() -> Self {
+        Self {
+            enabled: true,
+            speed: 3.0,      // Clignotement rapide
+            min_alpha: 0.2,
+            max_alpha: 1.0,
+            whole_window: false,
+        }
+    }
 } 
\ No newline at end of file
@@ -1,10 +1,17 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct RainbowEffect {
+    #[schemars(description = "Enable the rainbow effect")]
     pub enabled: bool,
+    #[schemars(description = "Color cycles per second")]
     pub speed: f32,        // Vitesse de rotation des couleurs (cycles par seconde)
+    #[schemars(description = "Color saturation", range(min = 0.0, max = 1.0))]
     pub saturation: f32,   // Saturation des couleurs (0.0-1.0)
+    #[schemars(description = "Color brightness", range(min = 0.0, max = 1.0))]
     pub brightness: f32,   // Luminosité des couleurs (0.0-1.0)
 }
 
@@ -27,15 +34,17 @@ impl RainbowEffect {
         }
 
         // Calcul de la teinte basée sur le temps
-        let hue = (time * self.speed * 360.0) % 360.0;
+        let hue = (time * self.speed * 360.0).rem_euclid(360.0);
         
         // Conversion HSV vers RGB
         let (r, g, b) = self.hsv_to_rgb(hue, self.saturation, self.brightness);
         
         // Appliquer l'alpha
-        let alpha = (base_alpha * 255.0) as u32;
+        let alpha = (base_alpha.clamp(0.0, 1.0) * 255.0) as u32;
+        // Saturation ou luminosité hors de 0.0-1.0 : composantes ramenées dans 0-255
+        let channel = |value: f32| value.clamp(0.0, 255.0) as u32;
         
-        (alpha << 24) | ((r as u32) << 16) | ((g as u32) << 8) | (b as u32)
+        (alpha << 24) | (channel(r) << 16) | (channel(g) << 8) | channel(b)
     }
 
     /// Convertit HSV vers RGB
@@ -59,4 +68,23 @@ impl RainbowEffect {
             ((b_prime + m) * 255.0),
         )
     }
+
+    /// Presets pour différents styles d'usage
+    pub fn preset_neon() -> Self {
+        Self {
+            enabled: true,
+            speed: 0.5,        // Défilement lent
+            saturation: 1.0,
+            brightness: 1.0,
+        }
+    }
+
+    pub fn preset_fast() -> Self {
+        Self {
+            enabled: true,
+            speed: 2.0,        // 2 cycles par seconde
+            saturation: 1.0,
+            brightness: 1.0,
+        }
+    }
 } 
\ No newline at end of file
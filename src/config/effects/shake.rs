@@ -1,9 +1,15 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct ShakeEffect {
+    #[schemars(description = "Enable the shake effect")]
     pub enabled: bool,
+    #[schemars(description = "Shake amplitude in pixels")]
     pub intensity: f32,    // Intensité du tremblement (en pixels)
+    #[schemars(description = "Shakes per second")]
     pub speed: f32,        // Vitesse du tremblement (cycles par seconde)
 }
 
@@ -34,4 +40,21 @@ impl ShakeEffect {
         
         (shake_x, shake_y)
     }
+
+    /// Presets pour différents styles d'usage
+    pub fn preset_subtle() -> Self {
+        Self {
+            enabled: true,
+            intensity: 1.0,  // 1 pixel, à peine visible
+            speed: 8.0,
+        }
+    }
+
+    pub fn preset_strong() -> Self {
+        Self {
+            enabled: true,
+            intensity: 2.0,
+            speed: 15.0,     // Tremblement nerveux
+        }
+    }
 } 
\ No newline at end of file
@@ -0,0 +1,133 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Affichage selon l'application au premier plan (section `[focus]` de config.toml)
+///
+/// La première règle qui correspond à la fenêtre active décide ; sans règle
+/// correspondante, `unmatched` s'applique.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Show, hide or switch profile depending on the focused application")]
+pub struct FocusConfig {
+    #[schemars(description = "Watch the focused window (X11 _NET_ACTIVE_WINDOW, Windows foreground window)")]
+    pub enabled: bool,
+    #[schemars(description = "What to do when no rule matches the focused window")]
+    pub unmatched: FocusAction,
+    #[schemars(description = "Rules checked in order, the first matching rule applies ([[focus.rules]] tables)")]
+    pub rules: Vec<FocusRule>,
+}
+
+impl Default for FocusConfig {
+    fn default() -> Self {
+        Self { enabled: false, unmatched: FocusAction::Hide, rules: Vec::new() }
+    }
+}
+
+/// Règle : critères sur la fenêtre active et action à appliquer
+///
+/// Tous les critères renseignés doivent correspondre (sans tenir compte de la
+/// casse) ; une règle sans critère correspond à toutes les fenêtres.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+pub struct FocusRule {
+    #[schemars(description = "Executable name, with or without .exe (exact match)", example = &"cs2")]
+    pub process: Option<String>,
+    #[schemars(description = "Part of the window class (WM_CLASS on X11, window class name on Windows)", example = &"steam_app_730")]
+    pub class: Option<String>,
+    #[schemars(description = "Part of the window title", example = &"Counter-Strike 2")]
+    pub title: Option<String>,
+    #[schemars(description = "Show or hide the crosshair")]
+    pub action: FocusAction,
+    #[schemars(description = "Profile to switch to when the crosshair is shown (optional)", example = &"cs2")]
+    pub profile: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "lowercase")]
+pub enum FocusAction {
+    #[default]
+    Show,
+    Hide,
+}
+
+/// Fenêtre au premier plan, décrite par le backend de surveillance
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FocusedWindow {
+    pub process: String, // Nom de l'exécutable (vide si inconnu)
+    pub class: String,
+    pub title: String,
+}
+
+/// Effet de la fenêtre active sur l'overlay
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FocusDecision<'a> {
+    pub visible: bool,
+    pub profile: Option<&'a str>,
+}
+
+impl FocusRule {
+    /// La fenêtre remplit tous les critères de la règle
+    pub fn matches(&self, window: &FocusedWindow) -> bool {
+        let executable = |name: &str| {
+            let name = name.trim().to_lowercase();
+            name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+        };
+        let contains = |text: &str, part: &str| text.to_lowercase().contains(&part.to_lowercase());
+
+        self.process.as_deref().is_none_or(|process| executable(process) == executable(&window.process))
+            && self.class.as_deref().is_none_or(|class| contains(&window.class, class))
+            && self.title.as_deref().is_none_or(|title| contains(&window.title, title))
+    }
+}
+
+impl FocusConfig {
+    /// Visibilité et profil pour la fenêtre active
+    pub fn decide(&self, window: &FocusedWindow) -> FocusDecision<'_> {
+        match self.rules.iter().find(|rule| rule.matches(window)) {
+            Some(rule) => FocusDecision {
+                visible: rule.action == FocusAction::Show,
+                profile: rule.profile.as_deref().filter(|_| rule.action == FocusAction::Show),
+            },
+            None => FocusDecision { visible: self.unmatched == FocusAction::Show, profile: None },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(process: &str, class: &str, title: &str) -> FocusedWindow {
+        FocusedWindow { process: process.to_string(), class: class.to_string(), title: title.to_string() }
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let focus: FocusConfig = toml::from_str(
+            r#"
+            enabled = true
+            [[rules]]
+            title = "main menu"
+            action = "hide"
+            [[rules]]
+            process = "CS2.exe"
+            profile = "cs2"
+            [[rules]]
+            class = "steam_app"
+            "#,
+        )
+        .unwrap();
+
+        let show = |profile| FocusDecision { visible: true, profile };
+        let hide = FocusDecision { visible: false, profile: None };
+        assert_eq!(focus.decide(&window("cs2", "steam_app_730", "Counter-Strike 2")), show(Some("cs2")));
+        assert_eq!(focus.decide(&window("cs2.exe", "", "Counter-Strike 2 - Main Menu")), hide);
+        assert_eq!(focus.decide(&window("hl2_linux", "steam_app_440", "Team Fortress 2")), show(None));
+        // Pas de règle pour le navigateur : `unmatched` (masqué par défaut)
+        assert_eq!(focus.decide(&window("firefox", "firefox", "Mozilla Firefox")), hide);
+        assert!(!focus.rules[1].matches(&window("cs2_launcher", "", "")));
+    }
+}
@@ -0,0 +1,240 @@
+use std::fmt::Write as _;
+use std::path::Path;
+use toml::{Table, Value};
+
+use super::CrosshairConfig;
+use crate::tr;
+
+/// Format de fichier de configuration, déterminé par l'extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+    Yaml,
+    Ron,
+}
+
+impl ConfigFormat {
+    /// Extensions reconnues, dans l'ordre de recherche des fichiers
+    pub const SUPPORTED_EXTENSIONS: &'static [&'static str] = &["toml", "json", "yaml", "yml", "ron"];
+
+    /// Détermine le format d'après l'extension du fichier
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let extension = path
+            .extension()
+            .map(|ext| ext.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+
+        match extension.as_str() {
+            "toml" => Ok(Self::Toml),
+            "json" => Ok(Self::Json),
+            "yaml" | "yml" => Ok(Self::Yaml),
+            "ron" => Ok(Self::Ron),
+            _ => Err(tr!(
+                "format.unsupported",
+                path = path.display(),
+                extensions = Self::SUPPORTED_EXTENSIONS.join(", ")
+            )),
+        }
+    }
+
+    /// Comme `from_path`, mais considère les extensions inconnues comme du TOML
+    pub fn detect<P: AsRef<Path>>(path: P) -> Self {
+        Self::from_path(path).unwrap_or(Self::Toml)
+    }
+
+    /// Lit un document dans ce format sous forme de table générique
+    ///
+    /// Tous les formats passent par la même représentation afin que l'héritage,
+    /// la validation et les messages d'erreur soient identiques.
+    pub fn parse(&self, content: &str) -> Result<Table, Box<dyn std::error::Error + Send + Sync>> {
+        let value: serde_json::Value = match self {
+            Self::Toml => return Ok(content.parse::<Table>()?),
+            Self::Json => serde_json::from_str(content)?,
+            Self::Yaml => serde_yaml::from_str(content)?,
+            Self::Ron => ron::from_str(content)?,
+        };
+
+        match json_to_toml(value) {
+            Some(Value::Table(table)) => Ok(table),
+            None => Ok(Table::new()), // Document vide
+            Some(_) => Err(tr!("format.not_a_table").into()),
+        }
+    }
+
+    /// Sérialise la configuration dans ce format
+    pub fn serialize(&self, config: &CrosshairConfig) -> Result<String, Box<dyn std::error::Error>> {
+        match self {
+            Self::Toml => Ok(toml::to_string_pretty(config)?),
+            Self::Json => Ok(serde_json::to_string_pretty(config)? + "\n"),
+            Self::Yaml => Ok(serde_yaml::to_string(config)?),
+            Self::Ron => {
+                let mut output = String::new();
+                write_ron(&mut output, &serde_json::to_value(config)?, 0)?;
+                output.push('\n');
+                Ok(output)
+            }
+        }
+    }
+}
+
+/// Convertit une valeur générique en valeur TOML (les `null` sont ignorés)
+fn json_to_toml(value: serde_json::Value) -> Option<Value> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::Bool(b) => Some(Value::Boolean(b)),
+        serde_json::Value::Number(n) => Some(match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Float(n.as_f64().unwrap_or_default()),
+        }),
+        serde_json::Value::String(s) => Some(Value::String(s)),
+        serde_json::Value::Array(items) => Some(Value::Array(items.into_iter().filter_map(json_to_toml).collect())),
+        serde_json::Value::Object(map) => Some(Value::Table(
+            map.into_iter()
+                .filter_map(|(key, value)| json_to_toml(value).map(|value| (key, value)))
+                .collect(),
+        )),
+    }
+}
+
+/// Écrit une valeur en RON avec la syntaxe des structures : `(clé: valeur)`
+///
+/// Les variantes d'énumération sont écrites comme des chaînes (`style: "classic"`),
+/// ce qui permet de relire le fichier avec `extends` et `include` comme les autres formats.
+fn write_ron(output: &mut String, value: &serde_json::Value, indent: usize) -> std::fmt::Result {
+    let padding = "    ".repeat(indent + 1);
+    match value {
+        serde_json::Value::Null => output.write_str("None"),
+        serde_json::Value::Bool(b) => write!(output, "{}", b),
+        serde_json::Value::Number(n) if n.is_f64() => {
+            // Les champs sont des f32 : on les réécrit sans les décimales parasites du f64
+            let float = n.as_f64().unwrap_or_default();
+            if (float as f32) as f64 == float {
+                write!(output, "{:?}", float as f32)
+            } else {
+                write!(output, "{:?}", float)
+            }
+        }
+        serde_json::Value::Number(n) => write!(output, "{}", n),
+        serde_json::Value::String(s) => write!(output, "{:?}", s),
+        serde_json::Value::Array(items) => {
+            output.write_str("[\n")?;
+            for item in items {
+                output.write_str(&padding)?;
+                write_ron(output, item, indent + 1)?;
+                output.write_str(",\n")?;
+            }
+            write!(output, "{}]", "    ".repeat(indent))
+        }
+        serde_json::Value::Object(map) => {
+            output.write_str("(\n")?;
+            for (key, item) in map {
+                write!(output, "{}{}: ", padding, key)?;
+                write_ron(output, item, indent + 1)?;
+                output.write_str(",\n")?;
+            }
+            write!(output, "{})", "    ".repeat(indent))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::CrosshairConfig;
+    use proptest::prelude::*;
+
+    const FORMATS: [ConfigFormat; 4] = [ConfigFormat::Toml, ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Ron];
+
+    proptest! {
+        #[test]
+        fn parse_never_panics(content in any::<String>()) {
+            for format in FORMATS {
+                if let Ok(table) = format.parse(&content) {
+                    let _ = toml::Value::Table(table).try_into::<CrosshairConfig>();
+                }
+            }
+        }
+
+        #[test]
+        fn serialized_config_parses_back(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let config = CrosshairConfig::from_arbitrary_bytes(&bytes);
+            let expected = Table::try_from(&config).unwrap();
+            for format in FORMATS {
+                // NaN et infinis ne sont pas représentables dans tous les formats : seule l'absence de panique compte
+                let Ok(content) = format.serialize(&config) else { continue };
+                let parsed = format.parse(&content);
+                if !is_finite(&expected) {
+                    continue;
+                }
+                let parsed: CrosshairConfig = Value::Table(parsed.unwrap()).try_into().unwrap();
+                prop_assert_eq!(&Table::try_from(&parsed).unwrap(), &expected, "{:?}\n{}", format, content);
+            }
+        }
+    }
+
+    /// Vrai si la valeur ne contient ni NaN ni infini
+    fn is_finite(table: &Table) -> bool {
+        fn value_is_finite(value: &Value) -> bool {
+            match value {
+                Value::Float(float) => float.is_finite(),
+                Value::Array(items) => items.iter().all(value_is_finite),
+                Value::Table(table) => is_finite(table),
+                _ => true,
+            }
+        }
+        table.values().all(value_is_finite)
+    }
+
+    #[test]
+    fn default_config_parses_back_in_every_format() {
+        let config = CrosshairConfig::default();
+        for format in FORMATS {
+            let content = format.serialize(&config).unwrap();
+            let parsed: CrosshairConfig = Value::Table(format.parse(&content).unwrap()).try_into().unwrap();
+            assert_eq!(Table::try_from(&parsed).unwrap(), Table::try_from(&config).unwrap(), "{:?}", format);
+        }
+    }
+
+    #[test]
+    fn format_is_detected_from_the_extension() {
+        for (path, expected) in [
+            ("config.toml", Some(ConfigFormat::Toml)),
+            ("config.json", Some(ConfigFormat::Json)),
+            ("config.yaml", Some(ConfigFormat::Yaml)),
+            ("config.yml", Some(ConfigFormat::Yaml)),
+            ("config.ron", Some(ConfigFormat::Ron)),
+            ("profiles/CS2.JSON", Some(ConfigFormat::Json)),
+            ("archive.tar.Yml", Some(ConfigFormat::Yaml)),
+            ("config.txt", None),
+            ("config", None),
+            ("toml", None),
+            (".json", None),
+        ] {
+            assert_eq!(ConfigFormat::from_path(path).ok(), expected, "{}", path);
+            assert_eq!(ConfigFormat::detect(path), expected.unwrap_or(ConfigFormat::Toml), "{}", path);
+        }
+
+        // L'erreur liste les extensions reconnues
+        let error = ConfigFormat::from_path("config.ini").unwrap_err();
+        assert!(ConfigFormat::SUPPORTED_EXTENSIONS.iter().all(|extension| error.contains(extension)), "{}", error);
+    }
+
+    #[test]
+    fn every_format_reads_the_same_table() {
+        let expected: Table = toml::from_str("[crosshair]\nsize = 12\nalpha = 0.5\nstyle = \"dot\"\n").unwrap();
+        for (format, content) in [
+            (ConfigFormat::Json, r#"{ "crosshair": { "size": 12, "alpha": 0.5, "style": "dot", "gap": null } }"#),
+            (ConfigFormat::Yaml, "crosshair:\n  size: 12\n  alpha: 0.5\n  style: dot\n"),
+            (ConfigFormat::Ron, r#"(crosshair: (size: 12, alpha: 0.5, style: "dot"))"#),
+        ] {
+            assert_eq!(format.parse(content).unwrap(), expected, "{:?}", format);
+        }
+
+        // Document vide accepté, racine autre qu'une table refusée
+        assert_eq!(ConfigFormat::Yaml.parse("").unwrap(), Table::new());
+        assert!(ConfigFormat::Json.parse("[1, 2]").is_err());
+        assert!(ConfigFormat::Yaml.parse("- 1\n- 2\n").is_err());
+    }
+}
@@ -0,0 +1,376 @@
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::tr;
+
+/// Texte d'un raccourci désactivé
+const DISABLED: &str = "none";
+
+/// Raccourcis clavier globaux (section `[hotkeys]` de config.toml)
+///
+/// Ils fonctionnent même quand le jeu a le focus : l'overlay, qui laisse
+/// passer les clics, ne reçoit presque jamais les touches du clavier.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Global keyboard shortcuts")]
+pub struct HotkeysConfig {
+    #[schemars(description = "Register the global shortcuts below (they work while the game has focus)")]
+    pub enabled: bool,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Show or hide the crosshair")]
+    pub toggle_visibility: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Reload the configuration (like F5)")]
+    pub reload: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Switch to the next profile (like F6)")]
+    pub next_profile: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Switch to the previous profile (like F7)")]
+    pub previous_profile: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Switch to the next crosshair style until the next reload")]
+    pub cycle_style: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Increase the window opacity by 10 % (like + in the window)")]
+    pub opacity_up: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Decrease the window opacity by 10 % (like - in the window)")]
+    pub opacity_down: Option<Hotkey>,
+    #[serde(with = "binding")]
+    #[schemars(with = "Hotkey", description = "Quit the overlay")]
+    pub quit: Option<Hotkey>,
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        let ctrl_alt = |key| Some(Hotkey { modifiers: Modifiers { ctrl: true, alt: true, ..Modifiers::default() }, key });
+        Self {
+            enabled: true,
+            toggle_visibility: ctrl_alt(HotkeyKey::Char('H')),
+            reload: ctrl_alt(HotkeyKey::Char('R')),
+            next_profile: ctrl_alt(HotkeyKey::Named(NamedKey::PageDown)),
+            previous_profile: ctrl_alt(HotkeyKey::Named(NamedKey::PageUp)),
+            cycle_style: ctrl_alt(HotkeyKey::Char('S')),
+            opacity_up: ctrl_alt(HotkeyKey::Named(NamedKey::NumpadAdd)),
+            opacity_down: ctrl_alt(HotkeyKey::Named(NamedKey::NumpadSubtract)),
+            quit: ctrl_alt(HotkeyKey::Char('Q')),
+        }
+    }
+}
+
+impl HotkeysConfig {
+    /// Raccourcis à enregistrer (aucun si `enabled` est faux)
+    pub fn bindings(&self) -> Vec<(Hotkey, HotkeyAction)> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        [
+            (self.toggle_visibility, HotkeyAction::ToggleVisibility),
+            (self.reload, HotkeyAction::Reload),
+            (self.next_profile, HotkeyAction::NextProfile),
+            (self.previous_profile, HotkeyAction::PreviousProfile),
+            (self.cycle_style, HotkeyAction::CycleStyle),
+            (self.opacity_up, HotkeyAction::OpacityUp),
+            (self.opacity_down, HotkeyAction::OpacityDown),
+            (self.quit, HotkeyAction::Quit),
+        ]
+        .into_iter()
+        .filter_map(|(hotkey, action)| hotkey.map(|hotkey| (hotkey, action)))
+        .collect()
+    }
+
+    /// Raccourcis attribués à plusieurs actions
+    pub fn duplicates(&self) -> Vec<Hotkey> {
+        let bindings = self.bindings();
+        let mut duplicates: Vec<Hotkey> = Vec::new();
+        for (index, (hotkey, _)) in bindings.iter().enumerate() {
+            if bindings[..index].iter().any(|(other, _)| other == hotkey) && !duplicates.contains(hotkey) {
+                duplicates.push(*hotkey);
+            }
+        }
+        duplicates
+    }
+}
+
+/// Action déclenchée par un raccourci global
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyAction {
+    ToggleVisibility,
+    Reload,
+    NextProfile,
+    PreviousProfile,
+    CycleStyle,
+    OpacityUp,
+    OpacityDown,
+    Quit,
+}
+
+impl fmt::Display for HotkeyAction {
+    /// Nom du champ de `[hotkeys]`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::ToggleVisibility => "toggle_visibility",
+            Self::Reload => "reload",
+            Self::NextProfile => "next_profile",
+            Self::PreviousProfile => "previous_profile",
+            Self::CycleStyle => "cycle_style",
+            Self::OpacityUp => "opacity_up",
+            Self::OpacityDown => "opacity_down",
+            Self::Quit => "quit",
+        })
+    }
+}
+
+/// Combinaison de touches, écrite `"Ctrl+Alt+H"` dans la configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct Hotkey {
+    pub modifiers: Modifiers,
+    pub key: HotkeyKey,
+}
+
+/// Touches de modification à maintenir
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub super_key: bool, // Touche Windows / Super / Cmd
+}
+
+/// Touche principale d'un raccourci
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HotkeyKey {
+    Char(char),    // Lettre majuscule A-Z ou chiffre 0-9
+    Function(u8),  // F1 à F24
+    Named(NamedKey),
+}
+
+/// Touches spéciales, avec leur nom dans la configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub enum NamedKey {
+    Escape,
+    Space,
+    Tab,
+    Enter,
+    Backspace,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    Up,
+    Down,
+    Left,
+    Right,
+    Pause,
+    ScrollLock,
+    PrintScreen,
+    NumpadAdd,      // + du pavé numérique
+    NumpadSubtract, // - du pavé numérique
+}
+
+impl NamedKey {
+    /// Noms canoniques puis alias acceptés
+    const NAMES: [(&'static str, Self); 28] = [
+        ("Escape", Self::Escape),
+        ("Space", Self::Space),
+        ("Tab", Self::Tab),
+        ("Enter", Self::Enter),
+        ("Backspace", Self::Backspace),
+        ("Insert", Self::Insert),
+        ("Delete", Self::Delete),
+        ("Home", Self::Home),
+        ("End", Self::End),
+        ("PageUp", Self::PageUp),
+        ("PageDown", Self::PageDown),
+        ("Up", Self::Up),
+        ("Down", Self::Down),
+        ("Left", Self::Left),
+        ("Right", Self::Right),
+        ("Pause", Self::Pause),
+        ("ScrollLock", Self::ScrollLock),
+        ("PrintScreen", Self::PrintScreen),
+        ("NumpadAdd", Self::NumpadAdd),
+        ("NumpadSubtract", Self::NumpadSubtract),
+        ("Esc", Self::Escape),
+        ("Return", Self::Enter),
+        ("Ins", Self::Insert),
+        ("Del", Self::Delete),
+        ("PgUp", Self::PageUp),
+        ("PgDn", Self::PageDown),
+        ("NumpadPlus", Self::NumpadAdd),
+        ("NumpadMinus", Self::NumpadSubtract),
+    ];
+
+    fn name(self) -> &'static str {
+        Self::NAMES.iter().find(|(_, key)| *key == self).map_or("?", |(name, _)| name)
+    }
+}
+
+impl FromStr for HotkeyKey {
+    type Err = String;
+
+    /// Accepte une lettre, un chiffre, `F1` à `F24` ou un nom de touche (sans tenir compte de la casse)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.chars();
+        if let (Some(c), None) = (chars.next(), chars.next()) {
+            if c.is_ascii_alphanumeric() {
+                return Ok(Self::Char(c.to_ascii_uppercase()));
+            }
+        }
+        if let Some(Ok(number @ 1..=24)) = s.strip_prefix(['F', 'f']).map(str::parse::<u8>) {
+            return Ok(Self::Function(number));
+        }
+        NamedKey::NAMES
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(s))
+            .map(|(_, key)| Self::Named(*key))
+            .ok_or_else(|| tr!("hotkey.unknown_key", key = s))
+    }
+}
+
+impl fmt::Display for HotkeyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Char(c) => write!(f, "{}", c),
+            Self::Function(number) => write!(f, "F{}", number),
+            Self::Named(key) => f.write_str(key.name()),
+        }
+    }
+}
+
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for HotkeyKey {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        const CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Self::Char(char::from(*u.choose(CHARS)?)),
+            1 => Self::Function(u.int_in_range(1..=24)?),
+            _ => Self::Named(u.arbitrary()?),
+        })
+    }
+}
+
+impl FromStr for Hotkey {
+    type Err = String;
+
+    /// Accepte `Ctrl+Alt+H`, `shift + f9`, `Super+PageUp`... (modificateurs dans n'importe quel ordre)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |detail: String| tr!("hotkey.invalid", hotkey = s, detail = detail);
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key = parts.pop().filter(|key| !key.is_empty()).ok_or_else(|| invalid(tr!("hotkey.missing_key")))?;
+
+        let mut modifiers = Modifiers::default();
+        for part in parts {
+            let modifier = match part.to_lowercase().as_str() {
+                "ctrl" | "control" => &mut modifiers.ctrl,
+                "alt" => &mut modifiers.alt,
+                "shift" => &mut modifiers.shift,
+                "super" | "win" | "meta" | "cmd" => &mut modifiers.super_key,
+                _ => return Err(invalid(tr!("hotkey.unknown_modifier", modifier = part))),
+            };
+            *modifier = true;
+        }
+        Ok(Self { modifiers, key: key.parse().map_err(invalid)? })
+    }
+}
+
+impl fmt::Display for Hotkey {
+    /// Forme canonique : `Ctrl+Alt+Shift+Super+Touche`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = &self.modifiers;
+        for (held, name) in [(modifiers.ctrl, "Ctrl"), (modifiers.alt, "Alt"), (modifiers.shift, "Shift"), (modifiers.super_key, "Super")] {
+            if held {
+                write!(f, "{}+", name)?;
+            }
+        }
+        write!(f, "{}", self.key)
+    }
+}
+
+/// Dans le schéma JSON, un raccourci est un texte (la syntaxe n'est pas vérifiée par l'éditeur)
+impl JsonSchema for Hotkey {
+    fn schema_name() -> Cow<'static, str> {
+        "Hotkey".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "description": "Modifiers (Ctrl, Alt, Shift, Super) and a key (letter, digit, F1-F24, Escape, Space, PageUp...) joined with +, or \"none\" to disable",
+            "examples": ["Ctrl+Alt+H", "Shift+F9", "none"]
+        })
+    }
+}
+
+/// Raccourci optionnel, `"none"` (ou texte vide) quand il est désactivé
+mod binding {
+    use super::{Hotkey, DISABLED};
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(binding: &Option<Hotkey>, serializer: S) -> Result<S::Ok, S::Error> {
+        match binding {
+            Some(hotkey) => serializer.collect_str(hotkey),
+            None => serializer.serialize_str(DISABLED),
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Hotkey>, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let text = text.trim();
+        if text.is_empty() || text.eq_ignore_ascii_case(DISABLED) {
+            return Ok(None);
+        }
+        text.parse().map(Some).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hotkeys_accept_modifiers_and_key_names() {
+        let ctrl_alt = Modifiers { ctrl: true, alt: true, ..Modifiers::default() };
+        for (text, expected, canonical) in [
+            ("Ctrl+Alt+H", Hotkey { modifiers: ctrl_alt, key: HotkeyKey::Char('H') }, "Ctrl+Alt+H"),
+            ("alt + control + h", Hotkey { modifiers: ctrl_alt, key: HotkeyKey::Char('H') }, "Ctrl+Alt+H"),
+            ("shift+f9", Hotkey { modifiers: Modifiers { shift: true, ..Modifiers::default() }, key: HotkeyKey::Function(9) }, "Shift+F9"),
+            ("Win+PgDn", Hotkey { modifiers: Modifiers { super_key: true, ..Modifiers::default() }, key: HotkeyKey::Named(NamedKey::PageDown) }, "Super+PageDown"),
+            ("F", Hotkey { modifiers: Modifiers::default(), key: HotkeyKey::Char('F') }, "F"),
+            ("Ctrl+NumpadPlus", Hotkey { modifiers: Modifiers { ctrl: true, ..Modifiers::default() }, key: HotkeyKey::Named(NamedKey::NumpadAdd) }, "Ctrl+NumpadAdd"),
+        ] {
+            let hotkey: Hotkey = text.parse().unwrap();
+            assert_eq!(hotkey, expected, "{}", text);
+            assert_eq!(hotkey.to_string(), canonical);
+            assert_eq!(canonical.parse::<Hotkey>(), Ok(hotkey));
+        }
+        for invalid in ["", "Ctrl+", "Hyper+H", "Ctrl+F25", "Ctrl+é"] {
+            assert!(invalid.parse::<Hotkey>().is_err(), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn bindings_can_be_disabled_and_detect_duplicates() {
+        let hotkeys: HotkeysConfig = toml::from_str("quit = \"none\"\nreload = \"\"\ncycle_style = \"Ctrl+Alt+H\"").unwrap();
+        assert_eq!((hotkeys.quit, hotkeys.reload), (None, None));
+        assert_eq!(hotkeys.bindings().len(), 6);
+        assert_eq!(hotkeys.duplicates(), vec!["Ctrl+Alt+H".parse().unwrap()]);
+
+        let written = toml::to_string(&hotkeys).unwrap();
+        assert_eq!(toml::from_str::<HotkeysConfig>(&written).unwrap(), hotkeys);
+
+        let disabled = HotkeysConfig { enabled: false, ..HotkeysConfig::default() };
+        assert!(disabled.bindings().is_empty());
+        assert!(HotkeysConfig::default().duplicates().is_empty());
+    }
+}
@@ -0,0 +1,254 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+use super::format::ConfigFormat;
+use super::layers::{collect_leaves, Layer, Origins};
+use super::presets::PresetLibrary;
+use crate::tr;
+
+/// Clé désignant le fichier parent dont hérite la configuration
+pub const EXTENDS_KEY: &str = "extends";
+/// Clé listant les fragments à inclure (ex: des `[effects]` partagés)
+pub const INCLUDE_KEY: &str = "include";
+
+/// Erreur de résolution de l'héritage, avec le fichier qui l'a provoquée
+#[derive(Debug)]
+pub enum InheritanceError {
+    Io { path: PathBuf, source: std::io::Error },
+    Parse { path: PathBuf, source: Box<dyn std::error::Error + Send + Sync> },
+    InvalidDirective { path: PathBuf, key: &'static str, message: String },
+    Preset { path: PathBuf, message: String },
+    Cycle { chain: Vec<PathBuf> },
+}
+
+impl fmt::Display for InheritanceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io { path, source } => {
+                f.write_str(&tr!("inheritance.read_failed", path = path.display(), error = source))
+            }
+            Self::Parse { path, source } => {
+                write!(f, "{}: {}", path.display(), source)
+            }
+            Self::InvalidDirective { path, key, message } => {
+                f.write_str(&tr!("inheritance.invalid_directive", path = path.display(), key = key, error = message))
+            }
+            Self::Preset { path, message } => {
+                write!(f, "{}: {}", path.display(), message)
+            }
+            Self::Cycle { chain } => {
+                let chain: Vec<String> = chain.iter().map(|p| p.display().to_string()).collect();
+                f.write_str(&tr!("inheritance.cycle", chain = chain.join(" -> ")))
+            }
+        }
+    }
+}
+
+impl std::error::Error for InheritanceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } => Some(source),
+            Self::Parse { source, .. } => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Charge un fichier de configuration en résolvant `extends` et `include`
+///
+/// Ordre de fusion : le parent (`extends`), puis chaque fragment de `include`
+/// dans l'ordre, puis le fichier lui-même. Les tables sont fusionnées en
+/// profondeur, les autres valeurs (tableaux compris) sont remplacées.
+/// Les clés `preset` de chaque fichier sont résolues avant la fusion.
+///
+/// Retourne aussi l'origine de chaque valeur : le fichier qui l'a écrite
+/// (`Layer::File`) ou le preset qui l'a apportée (`Layer::Preset`).
+pub fn load_table<P: AsRef<Path>>(path: P, presets: &PresetLibrary) -> Result<(Table, Origins), InheritanceError> {
+    let mut stack = Vec::new();
+    load_recursive(path.as_ref(), presets, &mut stack)
+}
+
+fn load_recursive(
+    path: &Path,
+    presets: &PresetLibrary,
+    stack: &mut Vec<PathBuf>,
+) -> Result<(Table, Origins), InheritanceError> {
+    // Chemin canonique pour détecter les cycles même avec des chemins relatifs différents
+    let canonical = fs::canonicalize(path).map_err(|source| InheritanceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    if stack.contains(&canonical) {
+        let mut chain = stack.clone();
+        chain.push(canonical);
+        return Err(InheritanceError::Cycle { chain });
+    }
+
+    let content = fs::read_to_string(&canonical).map_err(|source| InheritanceError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    // Le format de chaque fichier dépend de son extension : un fichier TOML peut inclure du JSON
+    let format = ConfigFormat::detect(path);
+    let mut table = format.parse(&content).map_err(|source| InheritanceError::Parse {
+        path: path.to_path_buf(),
+        source,
+    })?;
+
+    let extends = take_paths(&mut table, EXTENDS_KEY, path)?;
+    let includes = take_paths(&mut table, INCLUDE_KEY, path)?;
+
+    let preset_origins = presets.resolve(&mut table).map_err(|message| InheritanceError::Preset {
+        path: path.to_path_buf(),
+        message,
+    })?;
+
+    let base_dir = canonical.parent().map(Path::to_path_buf).unwrap_or_default();
+    stack.push(canonical);
+
+    let mut merged = Table::new();
+    let mut origins = Origins::new();
+    for relative in extends.iter().chain(includes.iter()) {
+        let (parent, parent_origins) = load_recursive(&base_dir.join(relative), presets, stack)?;
+        merge_tables(&mut merged, parent);
+        origins.extend(parent_origins);
+    }
+
+    // Valeurs du fichier lui-même, sauf celles apportées par ses presets
+    let mut leaves = Vec::new();
+    collect_leaves(&table, "", &mut leaves);
+    for (key, _) in leaves {
+        let origin = preset_origins.get(&key).cloned().unwrap_or_else(|| Layer::File(path.to_path_buf()));
+        origins.insert(key, origin);
+    }
+    merge_tables(&mut merged, table);
+
+    stack.pop();
+    Ok((merged, origins))
+}
+
+/// Retire une directive de la table et retourne les chemins qu'elle référence
+fn take_paths(table: &mut Table, key: &'static str, path: &Path) -> Result<Vec<PathBuf>, InheritanceError> {
+    let invalid = |message: String| InheritanceError::InvalidDirective {
+        path: path.to_path_buf(),
+        key,
+        message,
+    };
+
+    match table.remove(key) {
+        None => Ok(Vec::new()),
+        Some(Value::String(s)) => Ok(vec![PathBuf::from(s)]),
+        Some(Value::Array(values)) if key == INCLUDE_KEY => values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(s) => Ok(PathBuf::from(s)),
+                _ => Err(invalid(tr!("inheritance.path_items"))),
+            })
+            .collect(),
+        Some(_) if key == INCLUDE_KEY => Err(invalid(tr!("inheritance.paths_expected"))),
+        Some(_) => Err(invalid(tr!("inheritance.path_expected"))),
+    }
+}
+
+/// Fusionne `overlay` dans `base` en profondeur
+pub fn merge_tables(base: &mut Table, overlay: Table) {
+    for (key, value) in overlay {
+        match (base.get_mut(&key), value) {
+            (Some(Value::Table(base_table)), Value::Table(overlay_table)) => {
+                merge_tables(base_table, overlay_table);
+            }
+            (_, value) => {
+                base.insert(key, value);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Écrit des fichiers dans un dossier temporaire
+    fn files(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (name, content) in files {
+            let path = dir.path().join(name);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn load(dir: &tempfile::TempDir, name: &str) -> Result<Table, InheritanceError> {
+        load_table(dir.path().join(name), &PresetLibrary::builtin()).map(|(table, _)| table)
+    }
+
+    #[test]
+    fn parent_then_includes_then_own_fields() {
+        let dir = files(&[
+            ("base.toml", "[crosshair]\nsize = 10\ngap = 1\ncolor = \"red\"\n"),
+            ("gap.toml", "[crosshair]\ngap = 2\nthickness = 3\n"),
+            ("thick.json", r#"{ "crosshair": { "thickness": 4 } }"#),
+            ("config.toml", "extends = \"base.toml\"\ninclude = [\"gap.toml\", \"thick.json\"]\n[crosshair]\nsize = 20\n"),
+        ]);
+        let table = load(&dir, "config.toml").unwrap();
+        let expected: Table = toml::from_str("[crosshair]\nsize = 20\ngap = 2\ncolor = \"red\"\nthickness = 4").unwrap();
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn paths_are_relative_to_the_file_that_references_them() {
+        let dir = files(&[
+            ("base.toml", "[crosshair]\nsize = 10\n"),
+            ("shared/effects.toml", "include = \"pulse.toml\"\n"),
+            ("shared/pulse.toml", "[effects.pulse]\nenabled = true\n"),
+            ("profiles/cs2.toml", "extends = \"../base.toml\"\ninclude = \"../shared/effects.toml\"\n"),
+        ]);
+        let table = load(&dir, "profiles/cs2.toml").unwrap();
+        let expected: Table = toml::from_str("[crosshair]\nsize = 10\n[effects.pulse]\nenabled = true").unwrap();
+        assert_eq!(table, expected);
+    }
+
+    #[test]
+    fn cycles_are_reported_with_their_chain() {
+        let dir = files(&[
+            ("a.toml", "extends = \"b.toml\"\n"),
+            ("b.toml", "include = [\"./a.toml\"]\n"),
+        ]);
+        match load(&dir, "a.toml") {
+            Err(InheritanceError::Cycle { chain }) => {
+                let names: Vec<_> = chain.iter().map(|path| path.file_name().unwrap().to_str().unwrap()).collect();
+                assert_eq!(names, ["a.toml", "b.toml", "a.toml"]);
+            }
+            other => panic!("cycle attendu : {:?}", other),
+        }
+        // Un même fragment inclus deux fois n'est pas un cycle
+        let dir = files(&[("fragment.toml", "gap = 1\n"), ("config.toml", "include = [\"fragment.toml\", \"fragment.toml\"]\n")]);
+        assert!(load(&dir, "config.toml").is_ok());
+    }
+
+    #[test]
+    fn missing_files_and_invalid_directives_name_the_file() {
+        let dir = files(&[
+            ("config.toml", "extends = \"missing.toml\"\n"),
+            ("two_parents.toml", "extends = [\"a.toml\", \"b.toml\"]\n"),
+            ("numbers.toml", "include = [1]\n"),
+        ]);
+        match load(&dir, "config.toml") {
+            Err(InheritanceError::Io { path, .. }) => assert_eq!(path, dir.path().join("missing.toml")),
+            other => panic!("erreur de lecture attendue : {:?}", other),
+        }
+        assert!(matches!(load(&dir, "absent.toml"), Err(InheritanceError::Io { .. })));
+        for (name, directive) in [("two_parents.toml", EXTENDS_KEY), ("numbers.toml", INCLUDE_KEY)] {
+            match load(&dir, name) {
+                Err(InheritanceError::InvalidDirective { path, key, .. }) => {
+                    assert_eq!((path, key), (dir.path().join(name), directive));
+                }
+                other => panic!("directive invalide attendue pour {} : {:?}", name, other),
+            }
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::i18n::Language;
+
+/// Réglages de l'interface (section `[interface]` de config.toml)
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Console messages")]
+pub struct InterfaceConfig {
+    #[schemars(description = "Language of the messages (detected from LC_ALL, LC_MESSAGES, LANG or the Windows user language when missing)", example = &"en")]
+    pub language: Option<Language>, // Langue des messages (aucune = détection depuis l'environnement)
+}
@@ -0,0 +1,473 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+use super::inheritance::{self, merge_tables};
+use super::presets::{PresetKind, PresetLibrary};
+use super::{CrosshairConfig, ProfilesConfig};
+use crate::tr;
+
+/// Préfixe des variables d'environnement de configuration
+/// (ex: `CROSSHAIR__CROSSHAIR__COLOR=#FF00FF`, `CROSSHAIR__EFFECTS__PULSE__ENABLED=true`)
+pub const ENV_PREFIX: &str = "CROSSHAIR__";
+/// Séparateur des sections dans le nom des variables
+pub const ENV_SEPARATOR: &str = "__";
+
+/// Couche d'origine de chaque valeur, par clé pointée (ex: "crosshair.size")
+pub type Origins = BTreeMap<String, Layer>;
+
+/// Couche ayant fourni une valeur, de la moins prioritaire à la plus prioritaire
+#[derive(Debug, Clone, PartialEq)]
+pub enum Layer {
+    Defaults,
+    File(PathBuf),
+    Preset(PresetKind, String),
+    Profile(String),
+    Environment(String),
+    CommandLine,
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Defaults => f.write_str(&tr!("layer.defaults")),
+            Self::File(path) => f.write_str(&tr!("layer.file", path = path.display())),
+            Self::Preset(kind, name) => f.write_str(&tr!("layer.preset", kind = kind, name = name)),
+            Self::Profile(name) => f.write_str(&tr!("layer.profile", name = name)),
+            Self::Environment(variable) => f.write_str(&tr!("layer.environment", variable = variable)),
+            Self::CommandLine => f.write_str(&tr!("layer.command_line")),
+        }
+    }
+}
+
+/// Configuration effective et couche d'origine de chaque valeur
+#[derive(Debug, Clone)]
+pub struct LayeredConfig {
+    pub config: CrosshairConfig,
+    origins: Origins,
+}
+
+impl LayeredConfig {
+    /// Toutes les valeurs effectives, triées par clé, avec leur couche d'origine
+    pub fn values(&self) -> Vec<(String, Value, &Layer)> {
+        let mut leaves = Vec::new();
+        if let Ok(table) = Table::try_from(&self.config) {
+            collect_leaves(&table, "", &mut leaves);
+        }
+        leaves
+            .into_iter()
+            .map(|(key, value)| {
+                let layer = self.origins.get(&key).unwrap_or(&Layer::Defaults);
+                (key, value, layer)
+            })
+            .collect()
+    }
+}
+
+/// Empile les couches de configuration : défauts, fichier, profil, environnement, ligne de commande
+///
+/// Chaque couche est fusionnée en profondeur sur les précédentes. Une couche
+/// qui rendrait la configuration invalide est refusée et les couches déjà
+/// ajoutées restent en place.
+#[derive(Debug, Clone)]
+pub struct ConfigLayers {
+    merged: Table,
+    config: CrosshairConfig,
+    origins: Origins,
+}
+
+impl Default for ConfigLayers {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigLayers {
+    /// Commence avec les valeurs par défaut
+    pub fn new() -> Self {
+        let config = CrosshairConfig::default();
+        let merged = Table::try_from(&config).unwrap_or_default();
+        Self {
+            merged,
+            config,
+            origins: Origins::new(),
+        }
+    }
+
+    /// Ajoute une couche, après avoir vérifié que la configuration résultante reste lisible
+    pub fn push(&mut self, layer: Layer, table: Table) -> Result<(), String> {
+        self.push_with_origins(layer, table, Origins::new())
+    }
+
+    /// Comme `push`, mais les valeurs présentes dans `origins` sont attribuées
+    /// à la couche indiquée (fichier parent, preset) plutôt qu'à `layer`
+    fn push_with_origins(&mut self, layer: Layer, table: Table, mut origins: Origins) -> Result<(), String> {
+        let mut merged = self.merged.clone();
+        merge_tables(&mut merged, table.clone());
+        let config: CrosshairConfig = Value::Table(merged.clone())
+            .try_into()
+            .map_err(|e: toml::de::Error| e.to_string().trim_end().to_string())?;
+
+        let mut leaves = Vec::new();
+        collect_leaves(&table, "", &mut leaves);
+
+        // Une variable mal orthographiée serait ignorée sans bruit par serde
+        if matches!(layer, Layer::Environment(_)) {
+            let known = Table::try_from(&config).unwrap_or_default();
+            if let Some((key, _)) = leaves.iter().find(|(key, _)| lookup(&known, key).is_none()) {
+                return Err(tr!("layer.unknown_field", key = key));
+            }
+        }
+
+        for (key, _) in leaves {
+            let origin = origins.remove(&key).unwrap_or_else(|| layer.clone());
+            self.origins.insert(key, origin);
+        }
+        self.merged = merged;
+        self.config = config;
+        Ok(())
+    }
+
+    /// Ajoute un fichier de configuration (avec `extends`, `include` et `preset`)
+    ///
+    /// Pour un profil, la section `[profiles]` du fichier est ignorée. Les
+    /// valeurs venant d'un parent, d'un fragment ou d'un preset gardent cette
+    /// origine ; celles écrites dans le fichier lui-même prennent `layer`.
+    pub fn push_file(
+        &mut self,
+        layer: Layer,
+        path: &Path,
+        presets: &PresetLibrary,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let (mut table, mut origins) = inheritance::load_table(path, presets)?;
+        if matches!(layer, Layer::Profile(_)) {
+            table.remove("profiles");
+        }
+        origins.retain(|_, origin| *origin != Layer::File(path.to_path_buf()));
+        self.push_with_origins(layer, table, origins)
+            .map_err(|message| format!("{}: {}", path.display(), message).into())
+    }
+
+    /// Ajoute les variables `CROSSHAIR__...` de l'environnement
+    ///
+    /// Retourne les erreurs des variables refusées, les autres sont appliquées.
+    pub fn push_environment(&mut self) -> Vec<String> {
+        self.push_variables(
+            std::env::vars_os().filter_map(|(name, value)| Some((name.into_string().ok()?, value.into_string().ok()?))),
+        )
+    }
+
+    /// Ajoute les variables `CROSSHAIR__...` parmi celles données, les autres sont ignorées
+    fn push_variables(&mut self, variables: impl IntoIterator<Item = (String, String)>) -> Vec<String> {
+        let mut variables: Vec<(String, String)> = variables
+            .into_iter()
+            .filter(|(name, _)| name.starts_with(ENV_PREFIX))
+            .collect();
+        // Ordre stable : l'environnement n'est pas trié
+        variables.sort();
+
+        let mut errors = Vec::new();
+        for (name, value) in variables {
+            let layer = Layer::Environment(name.clone());
+            let result = self.environment_table(&name, &value).and_then(|(table, text)| {
+                // Un champ optionnel absent des défauts (ex: profiles.active) peut attendre du texte
+                self.push(layer.clone(), table)
+                    .or_else(|e| text.map_or(Err(e.clone()), |text| self.push(layer, text).map_err(|_| e)))
+            });
+            if let Err(e) = result {
+                errors.push(format!("{}: {}", name, e));
+            }
+        }
+        errors
+    }
+
+    /// Traduit `CROSSHAIR__EFFECTS__PULSE__ENABLED=true` en `effects.pulse.enabled = true`
+    ///
+    /// Les champs texte gardent la valeur brute, les autres sont lus comme une
+    /// valeur TOML (booléen, nombre, tableau) avec repli sur du texte. Si la
+    /// valeur lue n'est pas du texte, la variante texte est aussi retournée.
+    fn environment_table(&self, name: &str, raw: &str) -> Result<(Table, Option<Table>), String> {
+        let path: Vec<String> = name[ENV_PREFIX.len()..]
+            .split(ENV_SEPARATOR)
+            .map(str::to_lowercase)
+            .collect();
+        if path.iter().any(String::is_empty) {
+            return Err(tr!("layer.invalid_variable"));
+        }
+
+        let text = Value::String(raw.to_string());
+        let value = match lookup(&self.merged, &path.join(".")) {
+            Some(Value::String(_)) => text.clone(),
+            _ => parse_scalar(raw),
+        };
+        let text = (value != text).then(|| nested_table(&path, text));
+        Ok((nested_table(&path, value), text))
+    }
+
+    /// Réglages des profils, variables d'environnement comprises (ex: CROSSHAIR__PROFILES__ACTIVE)
+    ///
+    /// Les variables refusées sont ignorées ici : elles sont signalées par `push_environment`.
+    pub fn profiles_settings(&self) -> ProfilesConfig {
+        let mut layers = self.clone();
+        let _ = layers.push_environment();
+        layers.config.profiles
+    }
+
+    /// Configuration effective avec l'origine de chaque valeur
+    pub fn build(self) -> LayeredConfig {
+        LayeredConfig {
+            config: self.config,
+            origins: self.origins,
+        }
+    }
+}
+
+/// Construit `a.b.c = value` à partir du chemin ["a", "b", "c"]
+fn nested_table(path: &[String], value: Value) -> Table {
+    path.iter().rev().fold(None, |inner: Option<Table>, key| {
+        let mut table = Table::new();
+        table.insert(key.clone(), inner.map_or_else(|| value.clone(), Value::Table));
+        Some(table)
+    })
+    .unwrap_or_default()
+}
+
+/// Lit une valeur TOML seule, ou la garde comme texte
+fn parse_scalar(raw: &str) -> Value {
+    format!("value = {}", raw)
+        .parse::<Table>()
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| Value::String(raw.to_string()))
+}
+
+/// Valeur d'une clé pointée (ex: "effects.pulse.enabled")
+fn lookup<'a>(table: &'a Table, key: &str) -> Option<&'a Value> {
+    let mut parts = key.split('.');
+    let mut value = table.get(parts.next()?)?;
+    for part in parts {
+        value = value.as_table()?.get(part)?;
+    }
+    Some(value)
+}
+
+/// Liste les valeurs finales (hors tables) avec leur clé pointée
+pub(super) fn collect_leaves(table: &Table, prefix: &str, leaves: &mut Vec<(String, Value)>) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        match value {
+            Value::Table(inner) => collect_leaves(inner, &key, leaves),
+            value => leaves.push((key, value.clone())),
+        }
+    }
+}
+
+/// Charge les couches de base de l'overlay : défauts puis fichier de configuration
+///
+/// En cas d'erreur, l'avertissement est affiché et seules les valeurs par
+/// défaut sont gardées. Si le fichier n'existe pas, il est créé.
+pub fn load_base(path: &Path) -> ConfigLayers {
+    let presets = PresetLibrary::load(path.parent().unwrap_or(Path::new("")));
+    let mut layers = ConfigLayers::new();
+
+    if let Err(e) = layers.push_file(Layer::File(path.to_path_buf()), path, &presets) {
+        eprintln!("{}", tr!("config.load_error", path = path.display(), error = e));
+        eprintln!("{}", tr!("config.using_defaults"));
+
+        // Créer le fichier de config par défaut s'il n'existe pas encore
+        if !path.exists() {
+            if let Err(save_error) = CrosshairConfig::default().save_to_file(path) {
+                eprintln!("{}", tr!("config.create_failed", path = path.display(), error = save_error));
+            } else {
+                println!("{}", tr!("config.created", path = path.display()));
+            }
+        }
+    }
+
+    layers
+}
+
+/// Ajoute aux couches de base le profil, l'environnement et la ligne de commande
+///
+/// Une couche en erreur est signalée puis ignorée.
+pub fn load_overlay(
+    base: &ConfigLayers,
+    config_path: &Path,
+    profile: Option<(&str, &Path)>,
+    command_line: Table,
+) -> LayeredConfig {
+    let mut layers = base.clone();
+
+    if let Some((name, profile_path)) = profile {
+        // Les profils utilisent les presets du dossier de config.toml, pas de leur propre dossier
+        let presets = PresetLibrary::load(config_path.parent().unwrap_or(Path::new("")));
+        if let Err(e) = layers.push_file(Layer::Profile(name.to_string()), profile_path, &presets) {
+            eprintln!("{}", tr!("config.profile_error", name = name, error = e));
+        }
+    }
+
+    for error in layers.push_environment() {
+        eprintln!("{}", tr!("config.variable_ignored", error = error));
+    }
+
+    if let Err(e) = layers.push(Layer::CommandLine, command_line) {
+        eprintln!("{}", tr!("config.command_line_ignored", error = e));
+    }
+
+    layers.build()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::crosshair::CrosshairStyle;
+    use std::fs;
+
+    fn variables(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    fn table(content: &str) -> Table {
+        content.parse().unwrap()
+    }
+
+    #[test]
+    fn variables_are_read_by_field_type() {
+        let mut layers = ConfigLayers::new();
+        let errors = layers.push_variables(variables(&[
+            ("CROSSHAIR__CROSSHAIR__SIZE", "25"),
+            ("CROSSHAIR__CROSSHAIR__STYLE", "dot"),
+            ("CROSSHAIR__CROSSHAIR__COLOR", "#FF00FF"),
+            ("CROSSHAIR__EFFECTS__PULSE__ENABLED", "true"),
+            ("CROSSHAIR__WINDOW__OPACITY", "0.5"),
+            // Champ optionnel absent des défauts : la valeur reste du texte
+            ("CROSSHAIR__PROFILES__ACTIVE", "1"),
+            ("HOME", "/root"),
+        ]));
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let config = layers.build().config;
+        assert_eq!(config.crosshair.size, 25);
+        assert_eq!(config.crosshair.style, CrosshairStyle::Dot);
+        assert_eq!(config.crosshair.color.to_string(), "#FF00FF");
+        assert!(config.effects.pulse.enabled);
+        assert_eq!(config.window.opacity, 0.5);
+        assert_eq!(config.profiles.active.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn invalid_variables_are_reported_and_skipped() {
+        let mut layers = ConfigLayers::new();
+        let errors = layers.push_variables(variables(&[
+            ("CROSSHAIR__CROSSHAIR__SIZ", "25"),
+            ("CROSSHAIR__CROSSHAIR____SIZE", "25"),
+            ("CROSSHAIR__", "25"),
+            ("CROSSHAIR__CROSSHAIR__SIZE", "large"),
+            ("CROSSHAIR__CROSSHAIR__THICKNESS", "4"),
+        ]));
+
+        let names: Vec<&str> = errors.iter().filter_map(|error| error.split(':').next()).collect();
+        assert_eq!(
+            names,
+            ["CROSSHAIR__", "CROSSHAIR__CROSSHAIR__SIZ", "CROSSHAIR__CROSSHAIR__SIZE", "CROSSHAIR__CROSSHAIR____SIZE"]
+        );
+        assert!(errors[1].ends_with(&tr!("layer.unknown_field", key = "crosshair.siz")), "{}", errors[1]);
+
+        // Les variables valides restent appliquées
+        let config = layers.build().config;
+        assert_eq!(config.crosshair.thickness, 4);
+        assert_eq!(config.crosshair.size, CrosshairConfig::default().crosshair.size);
+    }
+
+    #[test]
+    fn later_layers_take_precedence() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.toml");
+        fs::write(&path, "[crosshair]\nsize = 10\nthickness = 10\ngap = 10\nalpha = 0.1\n").unwrap();
+        let profile = dir.path().join("cs2.toml");
+        fs::write(&profile, "[crosshair]\nthickness = 20\ngap = 20\nalpha = 0.2\n[profiles]\nactive = \"other\"\n").unwrap();
+
+        let presets = PresetLibrary::builtin();
+        let mut layers = ConfigLayers::new();
+        layers.push_file(Layer::File(path.clone()), &path, &presets).unwrap();
+        layers.push_file(Layer::Profile("cs2".to_string()), &profile, &presets).unwrap();
+        let errors = layers.push_variables(variables(&[
+            ("CROSSHAIR__CROSSHAIR__GAP", "30"),
+            ("CROSSHAIR__CROSSHAIR__ALPHA", "0.3"),
+        ]));
+        assert!(errors.is_empty(), "{:?}", errors);
+        layers.push(Layer::CommandLine, table("crosshair.alpha = 0.4")).unwrap();
+
+        let layered = layers.build();
+        let crosshair = &layered.config.crosshair;
+        assert_eq!((crosshair.size, crosshair.thickness, crosshair.gap, crosshair.alpha), (10, 20, 30, 0.4));
+        // La section [profiles] d'un profil est ignorée
+        assert_eq!(layered.config.profiles.active, None);
+
+        let origin = |key: &str| {
+            let values = layered.values();
+            values.iter().find(|(name, _, _)| name == key).map(|(_, _, layer)| (*layer).clone())
+        };
+        assert_eq!(origin("crosshair.size"), Some(Layer::File(path)));
+        assert_eq!(origin("crosshair.thickness"), Some(Layer::Profile("cs2".to_string())));
+        assert_eq!(origin("crosshair.gap"), Some(Layer::Environment("CROSSHAIR__CROSSHAIR__GAP".to_string())));
+        assert_eq!(origin("crosshair.alpha"), Some(Layer::CommandLine));
+        assert_eq!(origin("crosshair.color"), Some(Layer::Defaults));
+    }
+
+    #[test]
+    fn invalid_layers_leave_the_previous_ones_in_place() {
+        let mut layers = ConfigLayers::new();
+        layers.push(Layer::CommandLine, table("crosshair.size = 12")).unwrap();
+        assert!(layers.push(Layer::CommandLine, table("crosshair.size = \"large\"")).is_err());
+        assert!(layers.push(Layer::CommandLine, table("crosshair.style = \"hexagon\"")).is_err());
+
+        let layered = layers.build();
+        assert_eq!(layered.config.crosshair.size, 12);
+        assert_eq!(layered.config.crosshair.style, CrosshairConfig::default().crosshair.style);
+    }
+
+    #[test]
+    fn values_keep_the_file_or_preset_they_come_from() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| {
+            let path = dir.path().join(name);
+            fs::write(&path, content).unwrap();
+            path
+        };
+        let base = write("base.toml", "[window]\nsize = 300\n[crosshair]\ngap = 7\n");
+        let hotkeys = write("hotkeys.toml", "[hotkeys]\nenabled = false\n");
+        let child = write(
+            "child.toml",
+            "extends = \"base.toml\"\ninclude = \"hotkeys.toml\"\npreset = \"sniper\"\n[crosshair]\npreset = \"large\"\nthickness = 1\n",
+        );
+        let profile = write("cs2.toml", "include = \"hotkeys.toml\"\n[window]\nopacity = 0.5\n");
+
+        let presets = PresetLibrary::builtin();
+        let mut layers = ConfigLayers::new();
+        layers.push_file(Layer::File(child.clone()), &child, &presets).unwrap();
+        layers.push_file(Layer::Profile("cs2".to_string()), &profile, &presets).unwrap();
+        let layered = layers.build();
+        let values = layered.values();
+        let origin = |key: &str| values.iter().find(|(name, _, _)| name == key).map(|(_, _, layer)| (*layer).clone());
+
+        let base = Layer::File(dir.path().canonicalize().unwrap().join(base.file_name().unwrap()));
+        let hotkeys = Layer::File(dir.path().canonicalize().unwrap().join(hotkeys.file_name().unwrap()));
+        assert_eq!(origin("window.size"), Some(base));
+        assert_eq!(origin("hotkeys.enabled"), Some(hotkeys));
+        assert_eq!(origin("window.opacity"), Some(Layer::Profile("cs2".to_string())));
+        assert_eq!(origin("crosshair.thickness"), Some(Layer::File(child)));
+        assert_eq!(origin("crosshair.size"), Some(Layer::Preset(PresetKind::Crosshair, "large".to_string())));
+        assert_eq!(
+            origin("effects.mouse.gap_effect.enabled"),
+            Some(Layer::Preset(PresetKind::Config, "sniper".to_string()))
+        );
+        // Le preset remplace la valeur du parent : elle ne lui est plus attribuée
+        assert_eq!(origin("crosshair.gap"), Some(Layer::Preset(PresetKind::Crosshair, "large".to_string())));
+    }
+}
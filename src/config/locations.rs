@@ -0,0 +1,192 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use super::format::ConfigFormat;
+use crate::tr;
+
+/// Variable d'environnement pour forcer le fichier de configuration
+pub const CONFIG_ENV_VAR: &str = "CROSSHAIR_CONFIG";
+/// Nom du fichier de configuration créé par défaut
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+/// Nom du fichier de configuration sans extension (config.toml, config.json...)
+const CONFIG_FILE_STEM: &str = "config";
+/// Nom du dossier de l'application dans les dossiers de configuration
+const APP_DIR_NAME: &str = "crosshair-overlay";
+
+/// Origine du fichier de configuration retenu
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    CommandLine,      // --config <chemin>
+    Environment,      // CROSSHAIR_CONFIG
+    UserDirectory,    // ~/.config/crosshair-overlay, %APPDATA%\crosshair-overlay...
+    WorkingDirectory, // config.toml du dossier courant (ancien emplacement)
+    SystemDirectory,  // XDG_CONFIG_DIRS (/etc/xdg/crosshair-overlay...)
+    NewFile,          // Aucun fichier trouvé : emplacement où il sera créé
+}
+
+impl fmt::Display for ConfigSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Self::CommandLine => tr!("location.command_line"),
+            Self::Environment => tr!("location.environment"),
+            Self::UserDirectory => tr!("location.user_directory"),
+            Self::WorkingDirectory => tr!("location.working_directory"),
+            Self::SystemDirectory => tr!("location.system_directory"),
+            Self::NewFile => tr!("location.new_file"),
+        };
+        f.write_str(&label)
+    }
+}
+
+/// Fichier de configuration à utiliser et son origine
+#[derive(Debug, Clone)]
+pub struct ConfigLocation {
+    pub path: PathBuf,
+    pub source: ConfigSource,
+}
+
+impl ConfigLocation {
+    /// Dossier contenant le fichier (base des chemins relatifs comme `profiles`)
+    pub fn directory(&self) -> PathBuf {
+        self.path
+            .parent()
+            .map(Path::to_path_buf)
+            .unwrap_or_default()
+    }
+}
+
+impl fmt::Display for ConfigLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.path.display(), self.source)
+    }
+}
+
+/// Détermine le fichier de configuration à utiliser
+///
+/// Ordre de recherche : `--config`, `CROSSHAIR_CONFIG`, dossier utilisateur,
+/// dossier courant, dossiers système. Dans chaque dossier, `config.toml` est
+/// prioritaire sur `config.json`, `config.yaml` et `config.ron`. Si aucun fichier
+/// n'existe, `config.toml` sera créé dans le dossier utilisateur.
+pub fn discover(cli_path: Option<PathBuf>) -> ConfigLocation {
+    if let Some(path) = cli_path {
+        return ConfigLocation { path, source: ConfigSource::CommandLine };
+    }
+
+    if let Some(path) = std::env::var_os(CONFIG_ENV_VAR).filter(|value| !value.is_empty()) {
+        return ConfigLocation { path: PathBuf::from(path), source: ConfigSource::Environment };
+    }
+
+    let user_dir = user_config_dir();
+    let directories = user_dir
+        .iter()
+        .cloned()
+        .map(|dir| (dir, ConfigSource::UserDirectory))
+        .chain(std::iter::once((PathBuf::new(), ConfigSource::WorkingDirectory)))
+        .chain(
+            system_config_dirs()
+                .into_iter()
+                .map(|dir| (dir, ConfigSource::SystemDirectory)),
+        );
+
+    search(directories).unwrap_or_else(|| ConfigLocation {
+        path: user_dir.unwrap_or_default().join(CONFIG_FILE_NAME),
+        source: ConfigSource::NewFile,
+    })
+}
+
+/// Premier dossier contenant un fichier de configuration, dans l'ordre donné
+fn search(directories: impl IntoIterator<Item = (PathBuf, ConfigSource)>) -> Option<ConfigLocation> {
+    directories.into_iter().find_map(|(directory, source)| {
+        find_config_file(&directory).map(|path| ConfigLocation { path, source })
+    })
+}
+
+/// Cherche `config.<extension>` dans un dossier, pour chaque format reconnu
+fn find_config_file(directory: &Path) -> Option<PathBuf> {
+    ConfigFormat::SUPPORTED_EXTENSIONS
+        .iter()
+        .map(|extension| directory.join(format!("{}.{}", CONFIG_FILE_STEM, extension)))
+        .find(|path| path.is_file())
+}
+
+/// Dossier de configuration de l'utilisateur pour l'application
+///
+/// `$XDG_CONFIG_HOME` (ou `~/.config`) sous Linux, `%APPDATA%` sous Windows,
+/// `~/Library/Application Support` sous macOS.
+pub fn user_config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(APP_DIR_NAME))
+}
+
+/// Dossiers de configuration système (`$XDG_CONFIG_DIRS`, `/etc/xdg` par défaut)
+#[cfg(all(unix, not(target_os = "macos")))]
+fn system_config_dirs() -> Vec<PathBuf> {
+    let dirs = std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|value| !value.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string());
+
+    dirs.split(':')
+        .filter(|dir| Path::new(dir).is_absolute())
+        .map(|dir| Path::new(dir).join(APP_DIR_NAME))
+        .collect()
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn system_config_dirs() -> Vec<PathBuf> {
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn command_line_path_wins() {
+        let location = discover(Some(PathBuf::from("custom/overlay.json")));
+        assert_eq!(location.source, ConfigSource::CommandLine);
+        assert_eq!(location.path, Path::new("custom/overlay.json"));
+        assert_eq!(location.directory(), Path::new("custom"));
+    }
+
+    #[test]
+    fn toml_is_preferred_over_other_formats() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(find_config_file(dir.path()), None);
+
+        for extension in ["ron", "yml", "yaml", "json", "toml"] {
+            fs::write(dir.path().join(format!("config.{}", extension)), "").unwrap();
+            assert_eq!(
+                find_config_file(dir.path()),
+                Some(dir.path().join(format!("config.{}", extension))),
+                "{}",
+                extension
+            );
+        }
+    }
+
+    #[test]
+    fn directories_are_searched_in_order() {
+        let user = tempfile::tempdir().unwrap();
+        let working = tempfile::tempdir().unwrap();
+        let directories = || {
+            [
+                (user.path().to_path_buf(), ConfigSource::UserDirectory),
+                (working.path().to_path_buf(), ConfigSource::WorkingDirectory),
+            ]
+        };
+        assert!(search(directories()).is_none());
+
+        // Un dossier nommé config.toml n'est pas un fichier de configuration
+        fs::create_dir(user.path().join("config.toml")).unwrap();
+        fs::write(working.path().join("config.yaml"), "").unwrap();
+        let location = search(directories()).unwrap();
+        assert_eq!(location.source, ConfigSource::WorkingDirectory);
+        assert_eq!(location.path, working.path().join("config.yaml"));
+
+        fs::write(user.path().join("config.json"), "").unwrap();
+        let location = search(directories()).unwrap();
+        assert_eq!(location.source, ConfigSource::UserDirectory);
+        assert_eq!(location.directory(), user.path());
+    }
+}
@@ -1,95 +1,152 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::Path;
 
+pub mod color;
 pub mod effects;
+pub mod focus;
+pub mod format;
+pub mod hotkeys;
+pub mod inheritance;
+pub mod interface;
+pub mod layers;
+pub mod locations;
+pub mod presets;
+pub mod profiles;
+pub mod schema;
+pub mod share;
 pub mod window;
 
 use crate::crosshair::Crosshair;
+use crate::tr;
 pub use effects::Effects;
+pub use focus::FocusConfig;
+pub use hotkeys::HotkeysConfig;
+pub use interface::InterfaceConfig;
+pub use locations::ConfigLocation;
+pub use profiles::{ProfileManager, ProfilesConfig};
 pub use window::WindowConfig;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(title = "Crosshair Overlay configuration")]
 pub struct CrosshairConfig {
+    #[schemars(description = "Crosshair shape and colors")]
     pub crosshair: Crosshair,
+    #[schemars(description = "Visual effects")]
     pub effects: Effects,
+    #[schemars(description = "Overlay window")]
     pub window: WindowConfig,
-}
-
-impl Default for CrosshairConfig {
-    fn default() -> Self {
-        Self {
-            crosshair: Crosshair::default(),
-            effects: Effects::default(),
-            window: WindowConfig::default(),
-        }
-    }
+    #[schemars(description = "Named profiles")]
+    pub profiles: ProfilesConfig,
+    #[schemars(description = "Global keyboard shortcuts")]
+    pub hotkeys: HotkeysConfig,
+    #[schemars(description = "Show, hide or switch profile depending on the focused application")]
+    pub focus: FocusConfig,
+    #[schemars(description = "Console messages")]
+    pub interface: InterfaceConfig,
 }
 
 impl CrosshairConfig {
-    /// Charge la configuration depuis config.toml ou crée une configuration par défaut
-    pub fn load_or_default() -> Self {
-        Self::load_from_file("config.toml").unwrap_or_else(|e| {
-            eprintln!("⚠️  Erreur lors du chargement de config.toml: {}", e);
-            eprintln!("📝 Utilisation de la configuration par défaut");
-            let default_config = Self::default();
-            
-            // Essayer de créer le fichier de config par défaut
-            if let Err(save_error) = default_config.save_to_file("config.toml") {
-                eprintln!("⚠️  Impossible de créer config.toml: {}", save_error);
-            } else {
-                println!("✅ Fichier config.toml créé avec la configuration par défaut");
-            }
-            
-            default_config
-        })
+    /// Charge la configuration depuis un fichier TOML, JSON, YAML ou RON (avec `extends` et `include`)
+    ///
+    /// Les presets utilisateur sont cherchés dans le dossier `presets` à côté du fichier.
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let config_dir = path.parent().unwrap_or(Path::new(""));
+        Self::load_with_presets(path, &presets::PresetLibrary::load(config_dir))
     }
 
-    /// Charge la configuration depuis un fichier TOML
-    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
-        let content = fs::read_to_string(path)?;
-        let config: Self = toml::from_str(&content)?;
-        Ok(config)
+    /// Charge la configuration en résolvant les clés `preset` avec la bibliothèque donnée
+    ///
+    /// Les champs absents du fichier gardent leur valeur par défaut.
+    pub fn load_with_presets<P: AsRef<Path>>(
+        path: P,
+        presets: &presets::PresetLibrary,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        let path = path.as_ref();
+        let mut layers = layers::ConfigLayers::new();
+        layers.push_file(layers::Layer::File(path.to_path_buf()), path, presets)?;
+        Ok(layers.build().config)
     }
 
-    /// Sauvegarde la configuration dans un fichier TOML
+    /// Sauvegarde la configuration dans le format correspondant à l'extension (TOML par défaut)
     pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Box<dyn std::error::Error>> {
-        let toml_string = toml::to_string_pretty(self)?;
-        fs::write(path, toml_string)?;
+        let path = path.as_ref();
+        let content = format::ConfigFormat::detect(path).serialize(self)?;
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, content)?;
         Ok(())
     }
 
+    /// Côté de la fenêtre en pixels physiques pour une mise à l'échelle d'écran
+    ///
+    /// Avec `window.auto_size`, le plus petit carré contenant le crosshair et ses
+    /// effets, qui sont dessinés en pixels physiques ; sinon `window.size` mis à
+    /// l'échelle.
+    pub fn window_side(&self, scale_factor: f64) -> u32 {
+        let side = if self.window.auto_size {
+            // Un pixel de marge de chaque côté du centre
+            2.0 * f64::from(self.crosshair.extent(&self.effects)).ceil() + 2.0
+        } else {
+            f64::from(self.window.size) * scale_factor
+        };
+        side.round().clamp(1.0, f64::from(u32::MAX)) as u32
+    }
+
     /// Valide la configuration et retourne les erreurs éventuelles
     pub fn validate(&self) -> Vec<String> {
         let mut errors = Vec::new();
 
         // Validation du crosshair
         if self.crosshair.size == 0 {
-            errors.push("La taille du crosshair ne peut pas être 0".to_string());
+            errors.push(tr!("validate.crosshair_size"));
         }
 
         if self.crosshair.thickness == 0 {
-            errors.push("L'épaisseur du crosshair ne peut pas être 0".to_string());
+            errors.push(tr!("validate.crosshair_thickness"));
         }
 
         if !(0.0..=1.0).contains(&self.crosshair.alpha) {
-            errors.push("L'alpha du crosshair doit être entre 0.0 et 1.0".to_string());
+            errors.push(tr!("validate.crosshair_alpha"));
         }
 
         // Validation des effets
         if self.effects.pulse.enabled && !(0.0..=1.0).contains(&self.effects.pulse.min_alpha) {
-            errors.push("L'alpha minimum du pulse doit être entre 0.0 et 1.0".to_string());
+            errors.push(tr!("validate.pulse_min_alpha"));
         }
 
         if self.effects.pulse.enabled && !(0.0..=1.0).contains(&self.effects.pulse.max_alpha) {
-            errors.push("L'alpha maximum du pulse doit être entre 0.0 et 1.0".to_string());
+            errors.push(tr!("validate.pulse_max_alpha"));
         }
 
         // Validation de la fenêtre
         if self.window.size == 0 {
-            errors.push("La taille de la fenêtre ne peut pas être 0".to_string());
+            errors.push(tr!("validate.window_size"));
+        }
+
+        if !(0.0..=1.0).contains(&self.window.opacity) {
+            errors.push(tr!("validate.window_opacity"));
+        }
+
+        // Validation des raccourcis
+        for hotkey in self.hotkeys.duplicates() {
+            errors.push(tr!("validate.hotkey_duplicate", hotkey = hotkey));
         }
 
         errors
     }
-} 
\ No newline at end of file
+} 
+
+#[cfg(test)]
+impl CrosshairConfig {
+    /// Configuration quelconque (NaN, tailles énormes, gap > size...) tirée des octets
+    pub(crate) fn from_arbitrary_bytes(bytes: &[u8]) -> Self {
+        use arbitrary::{Arbitrary, Unstructured};
+        Self::arbitrary(&mut Unstructured::new(bytes)).unwrap_or_default()
+    }
+}
@@ -0,0 +1,544 @@
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml::{Table, Value};
+
+use super::color::Color;
+use super::effects::mouse::{GapEffect, VisibilityEffect};
+use super::effects::{Effects, PulseEffect, RainbowEffect, ShakeEffect};
+use super::format::ConfigFormat;
+use super::inheritance::merge_tables;
+use super::layers::{collect_leaves, Layer, Origins};
+use super::CrosshairConfig;
+use crate::crosshair::{Crosshair, CrosshairStyle};
+use crate::tr;
+
+/// Clé désignant le preset sur lequel se base une section
+pub const PRESET_KEY: &str = "preset";
+/// Dossier des presets utilisateur, à côté du fichier de configuration
+pub const PRESETS_DIR_NAME: &str = "presets";
+/// Clé optionnelle de description dans un preset utilisateur
+const DESCRIPTION_KEY: &str = "description";
+/// Profondeur maximale de presets basés sur d'autres presets
+const MAX_DEPTH: usize = 8;
+/// Sections définies par les presets de configuration complète
+const CONFIG_PRESET_SECTIONS: [&str; 2] = ["crosshair", "effects"];
+
+/// Partie de la configuration à laquelle s'applique un preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresetKind {
+    Config,           // Configuration complète (clé `preset` à la racine)
+    Crosshair,        // [crosshair]
+    Pulse,            // [effects.pulse]
+    Shake,            // [effects.shake]
+    Rainbow,          // [effects.rainbow]
+    GapEffect,        // [effects.mouse.gap_effect]
+    VisibilityEffect, // [effects.mouse.visibility_effect]
+}
+
+impl PresetKind {
+    pub const ALL: [Self; 7] = [
+        Self::Config,
+        Self::Crosshair,
+        Self::Pulse,
+        Self::Shake,
+        Self::Rainbow,
+        Self::GapEffect,
+        Self::VisibilityEffect,
+    ];
+
+    /// Nom du type, utilisé aussi comme sous-dossier des presets utilisateur
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Config => "config",
+            Self::Crosshair => "crosshair",
+            Self::Pulse => "pulse",
+            Self::Shake => "shake",
+            Self::Rainbow => "rainbow",
+            Self::GapEffect => "gap_effect",
+            Self::VisibilityEffect => "visibility_effect",
+        }
+    }
+
+    /// Nom complet de la section (`effects.mouse.gap_effect`)
+    pub fn section(&self) -> String {
+        self.path().join(".")
+    }
+
+    /// Chemin de la section dans la configuration
+    fn path(&self) -> &'static [&'static str] {
+        match self {
+            Self::Config => &[],
+            Self::Crosshair => &["crosshair"],
+            Self::Pulse => &["effects", "pulse"],
+            Self::Shake => &["effects", "shake"],
+            Self::Rainbow => &["effects", "rainbow"],
+            Self::GapEffect => &["effects", "mouse", "gap_effect"],
+            Self::VisibilityEffect => &["effects", "mouse", "visibility_effect"],
+        }
+    }
+
+    /// Presets fournis avec l'application : (nom, description, réglages)
+    fn builtin(&self) -> Vec<(&'static str, String, Table)> {
+        match self {
+            Self::Config => vec![
+                ("sniper", tr!("preset.config.sniper"), config_table(&config_sniper())),
+                ("precision", tr!("preset.config.precision"), config_table(&config_precision())),
+                ("neon", tr!("preset.config.neon"), config_table(&config_neon())),
+                ("rainbow_pulse", tr!("preset.config.rainbow_pulse"), config_table(&config_rainbow_pulse())),
+            ],
+            Self::Crosshair => vec![
+                ("discrete", tr!("preset.crosshair.discrete"), to_table(&Crosshair::preset_discrete())),
+                ("triangle", tr!("preset.crosshair.triangle"), to_table(&Crosshair::preset_triangle())),
+                ("filled_circle", tr!("preset.crosshair.filled_circle"), to_table(&Crosshair::preset_filled_circle())),
+                ("sniper_dot", tr!("preset.crosshair.sniper_dot"), to_table(&Crosshair::preset_sniper_dot())),
+                ("rotating_square", tr!("preset.crosshair.rotating_square"), to_table(&Crosshair::preset_rotating_square())),
+                ("diamond", tr!("preset.crosshair.diamond"), to_table(&Crosshair::preset_diamond())),
+                ("large", tr!("preset.crosshair.large"), to_table(&Crosshair::preset_large())),
+            ],
+            Self::Pulse => vec![
+                ("breathing", tr!("preset.pulse.breathing"), to_table(&PulseEffect::preset_breathing())),
+                ("alert", tr!("preset.pulse.alert"), to_table(&PulseEffect::preset_alert())),
+            ],
+            Self::Shake => vec![
+                ("subtle", tr!("preset.shake.subtle"), to_table(&ShakeEffect::preset_subtle())),
+                ("strong", tr!("preset.shake.strong"), to_table(&ShakeEffect::preset_strong())),
+            ],
+            Self::Rainbow => vec![
+                ("neon", tr!("preset.rainbow.neon"), to_table(&RainbowEffect::preset_neon())),
+                ("fast", tr!("preset.rainbow.fast"), to_table(&RainbowEffect::preset_fast())),
+            ],
+            Self::GapEffect => vec![
+                ("sniper", tr!("preset.gap_effect.sniper"), to_table(&GapEffect::preset_sniper())),
+                ("precision", tr!("preset.gap_effect.precision"), to_table(&GapEffect::preset_precision())),
+            ],
+            Self::VisibilityEffect => vec![
+                ("sniper_clarity", tr!("preset.visibility_effect.sniper_clarity"), to_table(&VisibilityEffect::preset_sniper_clarity())),
+                ("peripheral_vision", tr!("preset.visibility_effect.peripheral_vision"), to_table(&VisibilityEffect::preset_peripheral_vision())),
+                ("minimal", tr!("preset.visibility_effect.minimal"), to_table(&VisibilityEffect::preset_minimal())),
+            ],
+        }
+    }
+}
+
+impl fmt::Display for PresetKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for PresetKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|kind| kind.name() == s)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Self::ALL.iter().map(|kind| kind.name()).collect();
+                tr!("presets.unknown_kind", kind = s, kinds = names.join(", "))
+            })
+    }
+}
+
+/// Provenance d'un preset
+#[derive(Debug, Clone)]
+pub enum PresetSource {
+    Builtin,
+    User(PathBuf),
+}
+
+/// Preset nommé, dont les réglages complètent les valeurs par défaut
+#[derive(Debug, Clone)]
+pub struct Preset {
+    pub kind: PresetKind,
+    pub name: String,
+    pub description: Option<String>,
+    pub source: PresetSource,
+    table: Table,
+}
+
+/// Presets intégrés et presets utilisateur (`presets/<type>/<nom>.toml`)
+///
+/// Un preset utilisateur de même nom qu'un preset intégré le remplace.
+#[derive(Debug, Clone)]
+pub struct PresetLibrary {
+    presets: Vec<Preset>,
+}
+
+impl PresetLibrary {
+    /// Presets fournis avec l'application uniquement
+    pub fn builtin() -> Self {
+        let presets = PresetKind::ALL
+            .into_iter()
+            .flat_map(|kind| {
+                kind.builtin().into_iter().map(move |(name, description, table)| Preset {
+                    kind,
+                    name: name.to_string(),
+                    description: Some(description.to_string()),
+                    source: PresetSource::Builtin,
+                    table,
+                })
+            })
+            .collect();
+        Self { presets }
+    }
+
+    /// Presets intégrés et presets utilisateur du dossier `presets` de `config_dir`
+    ///
+    /// Les fichiers illisibles sont signalés et ignorés.
+    pub fn load(config_dir: &Path) -> Self {
+        let mut library = Self::builtin();
+        let directory = config_dir.join(PRESETS_DIR_NAME);
+
+        for kind in PresetKind::ALL {
+            let Ok(entries) = fs::read_dir(directory.join(kind.name())) else {
+                continue;
+            };
+            let mut paths: Vec<PathBuf> = entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| path.is_file() && ConfigFormat::from_path(path).is_ok())
+                .collect();
+            paths.sort();
+
+            for path in paths {
+                match Self::load_user_preset(kind, &path) {
+                    Ok(preset) => library.insert(preset),
+                    Err(e) => eprintln!("{}", tr!("presets.ignored", path = path.display(), error = e)),
+                }
+            }
+        }
+        library
+    }
+
+    fn load_user_preset(kind: PresetKind, path: &Path) -> Result<Preset, String> {
+        let content = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let mut table = ConfigFormat::detect(path).parse(&content).map_err(|e| e.to_string())?;
+        let description = match table.remove(DESCRIPTION_KEY) {
+            None => None,
+            Some(Value::String(description)) => Some(description),
+            Some(_) => return Err(tr!("presets.description_string", key = DESCRIPTION_KEY)),
+        };
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default();
+
+        Ok(Preset {
+            kind,
+            name,
+            description,
+            source: PresetSource::User(path.to_path_buf()),
+            table,
+        })
+    }
+
+    /// Ajoute un preset, en remplaçant celui de même type et de même nom
+    fn insert(&mut self, preset: Preset) {
+        self.presets.retain(|p| !(p.kind == preset.kind && p.name == preset.name));
+        self.presets.push(preset);
+    }
+
+    /// Presets d'un type donné
+    pub fn presets(&self, kind: PresetKind) -> impl Iterator<Item = &Preset> {
+        self.presets.iter().filter(move |preset| preset.kind == kind)
+    }
+
+    fn find(&self, kind: PresetKind, name: &str) -> Option<&Preset> {
+        self.presets(kind).find(|preset| preset.name == name)
+    }
+
+    /// Remplace chaque clé `preset` de la configuration par les réglages du preset
+    ///
+    /// Résultat d'une section : le preset, puis les champs écrits à côté de
+    /// `preset` dans le fichier. Les champs que le preset ne définit pas restent
+    /// ceux des parents (`extends`, `include`) ou les valeurs par défaut. Un
+    /// preset de section l'emporte sur la même section d'un preset de
+    /// configuration complète.
+    ///
+    /// Retourne le preset d'origine des valeurs apportées par les presets.
+    pub fn resolve(&self, table: &mut Table) -> Result<Origins, String> {
+        let mut origins = Origins::new();
+        self.resolve_kind(PresetKind::Config, table, 0, &mut origins)?;
+        Ok(origins)
+    }
+
+    /// Résout les presets d'une table correspondant à la section `kind`
+    fn resolve_kind(&self, kind: PresetKind, table: &mut Table, depth: usize, origins: &mut Origins) -> Result<(), String> {
+        let base_path = kind.path();
+
+        // Sections imbriquées d'abord : elles sont plus spécifiques
+        for sub_kind in PresetKind::ALL {
+            let path = sub_kind.path();
+            if path.len() <= base_path.len() || !path.starts_with(base_path) {
+                continue;
+            }
+            if let Some(section) = table_at(table, &path[base_path.len()..]) {
+                if section.contains_key(PRESET_KEY) {
+                    self.expand(sub_kind, section, depth, origins)?;
+                }
+            }
+        }
+
+        if table.contains_key(PRESET_KEY) {
+            self.expand(kind, table, depth, origins)?;
+        }
+        Ok(())
+    }
+
+    fn expand(&self, kind: PresetKind, table: &mut Table, depth: usize, origins: &mut Origins) -> Result<(), String> {
+        let location = match kind {
+            PresetKind::Config => PRESET_KEY.to_string(),
+            _ => format!("{}.{}", kind.section(), PRESET_KEY),
+        };
+        let name = match table.remove(PRESET_KEY) {
+            Some(Value::String(name)) => name,
+            _ => return Err(tr!("presets.name_string", key = location)),
+        };
+        if depth >= MAX_DEPTH {
+            return Err(tr!("presets.too_deep", key = location));
+        }
+
+        let preset = self.find(kind, &name).ok_or_else(|| {
+            let available: Vec<&str> = self.presets(kind).map(|preset| preset.name.as_str()).collect();
+            tr!("presets.unknown", key = location, kind = kind, name = name, available = available.join(", "))
+        })?;
+
+        // Un preset utilisateur peut lui-même se baser sur un autre preset
+        let mut preset_table = preset.table.clone();
+        let mut preset_origins = Origins::new();
+        self.resolve_kind(kind, &mut preset_table, depth + 1, &mut preset_origins)?;
+
+        // Valeurs apportées par le preset, sauf celles écrites à côté de `preset`
+        let own_keys: Vec<String> = leaf_keys(kind, table);
+        for key in leaf_keys(kind, &preset_table) {
+            if !own_keys.contains(&key) {
+                let origin = preset_origins.remove(&key).unwrap_or_else(|| Layer::Preset(kind, name.clone()));
+                origins.insert(key, origin);
+            }
+        }
+
+        merge_tables(&mut preset_table, std::mem::take(table));
+        *table = preset_table;
+        Ok(())
+    }
+}
+
+/// Clés pointées complètes (ex: "effects.pulse.speed") des valeurs d'une section
+fn leaf_keys(kind: PresetKind, table: &Table) -> Vec<String> {
+    let mut leaves = Vec::new();
+    collect_leaves(table, &kind.section(), &mut leaves);
+    leaves.into_iter().map(|(key, _)| key).collect()
+}
+
+/// Sous-table au chemin indiqué, si elle existe
+fn table_at<'a>(table: &'a mut Table, path: &[&str]) -> Option<&'a mut Table> {
+    path.iter().try_fold(table, |table, key| match table.get_mut(*key) {
+        Some(Value::Table(sub)) => Some(sub),
+        _ => None,
+    })
+}
+
+fn to_table<T: Serialize>(value: &T) -> Table {
+    // Les structures de configuration se sérialisent toujours en table TOML
+    Table::try_from(value).unwrap_or_default()
+}
+
+/// Table d'un preset de configuration complète : seulement le crosshair et les effets
+///
+/// La fenêtre, les raccourcis, les profils... restent ceux du fichier et de ses parents.
+fn config_table(config: &CrosshairConfig) -> Table {
+    let mut table = to_table(config);
+    CONFIG_PRESET_SECTIONS
+        .iter()
+        .filter_map(|section| Some((section.to_string(), table.remove(*section)?)))
+        .collect()
+}
+
+/// Presets de configuration complète, composés à partir des presets de section
+fn config_with(crosshair: Crosshair, effects: Effects) -> CrosshairConfig {
+    CrosshairConfig {
+        crosshair,
+        effects,
+        ..CrosshairConfig::default()
+    }
+}
+
+fn config_sniper() -> CrosshairConfig {
+    let mut effects = Effects::default();
+    effects.mouse.enabled = true;
+    effects.mouse.gap_effect = GapEffect::preset_sniper();
+    effects.mouse.visibility_effect = VisibilityEffect::preset_sniper_clarity();
+    config_with(Crosshair::preset_sniper_dot(), effects)
+}
+
+fn config_precision() -> CrosshairConfig {
+    let mut effects = Effects::default();
+    effects.mouse.enabled = true;
+    effects.mouse.gap_effect = GapEffect::preset_precision();
+    config_with(Crosshair::preset_discrete(), effects)
+}
+
+fn config_neon() -> CrosshairConfig {
+    let crosshair = Crosshair {
+        size: 20,
+        color: Color::rgb(0, 255, 255),
+        style: CrosshairStyle::Circle,
+        ..Crosshair::default()
+    };
+    let effects = Effects {
+        rainbow: RainbowEffect::preset_neon(),
+        ..Effects::default()
+    };
+    config_with(crosshair, effects)
+}
+
+fn config_rainbow_pulse() -> CrosshairConfig {
+    let crosshair = Crosshair {
+        style: CrosshairStyle::Circle,
+        ..Crosshair::default()
+    };
+    let effects = Effects {
+        rainbow: RainbowEffect::preset_neon(),
+        pulse: PulseEffect {
+            speed: 1.5,
+            ..PulseEffect::preset_breathing()
+        },
+        ..Effects::default()
+    };
+    config_with(crosshair, effects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Résout les presets d'un document TOML et lit la configuration obtenue
+    fn resolve(library: &PresetLibrary, content: &str) -> Result<CrosshairConfig, String> {
+        let mut table: Table = content.parse().unwrap();
+        library.resolve(&mut table)?;
+        Value::Table(table).try_into().map_err(|e: toml::de::Error| e.to_string())
+    }
+
+    /// Dossier de configuration avec ces presets utilisateur (`presets/<type>/<fichier>`)
+    fn user_presets(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (file, content) in files {
+            let path = dir.path().join(PRESETS_DIR_NAME).join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn kinds_are_parsed_by_name() {
+        for kind in PresetKind::ALL {
+            assert_eq!(kind.name().parse::<PresetKind>(), Ok(kind));
+        }
+        assert!("effects.pulse".parse::<PresetKind>().is_err());
+        assert_eq!(PresetKind::GapEffect.section(), "effects.mouse.gap_effect");
+        assert_eq!(PresetKind::Config.section(), "");
+    }
+
+    #[test]
+    fn every_builtin_preset_resolves() {
+        let library = PresetLibrary::builtin();
+        for kind in PresetKind::ALL {
+            for preset in library.presets(kind) {
+                let content = match kind {
+                    PresetKind::Config => format!("preset = \"{}\"", preset.name),
+                    _ => format!("[{}]\npreset = \"{}\"", kind.section(), preset.name),
+                };
+                let config = resolve(&library, &content).unwrap_or_else(|e| panic!("{} {}: {}", kind, preset.name, e));
+                assert!(config.validate().is_empty(), "{} {}", kind, preset.name);
+            }
+        }
+    }
+
+    #[test]
+    fn fields_next_to_the_preset_win() {
+        let library = PresetLibrary::builtin();
+        let config = resolve(&library, "[crosshair]\npreset = \"large\"\ngap = 2\n").unwrap();
+        assert_eq!(config.crosshair.size, 40);
+        assert_eq!(config.crosshair.gap, 2);
+        assert_eq!(config.crosshair.alpha, Crosshair::default().alpha);
+
+        // Un preset de section l'emporte sur la même section d'un preset complet
+        let content = "preset = \"sniper\"\n[crosshair]\npreset = \"large\"\n";
+        let config = resolve(&library, content).unwrap();
+        assert_eq!(to_table(&config.crosshair), to_table(&Crosshair::preset_large()));
+        assert_eq!(to_table(&config.effects.mouse.gap_effect), to_table(&GapEffect::preset_sniper()));
+    }
+
+    #[test]
+    fn invalid_references_are_rejected() {
+        let library = PresetLibrary::builtin();
+        for content in [
+            "preset = \"missing\"",
+            "preset = 3",
+            "[effects.pulse]\npreset = \"neon\"",
+            "[crosshair]\npreset = [\"large\"]",
+        ] {
+            assert!(resolve(&library, content).is_err(), "{}", content);
+        }
+
+        let error = resolve(&library, "[effects.shake]\npreset = \"missing\"").unwrap_err();
+        assert!(error.contains("effects.shake.preset") && error.contains("subtle, strong"), "{}", error);
+    }
+
+    #[test]
+    fn user_presets_extend_and_replace_builtin_ones() {
+        let dir = user_presets(&[
+            ("crosshair/large.toml", "description = \"Plus grand\"\nsize = 60\n"),
+            ("crosshair/outline.json", r#"{ "preset": "large", "thickness": 1 }"#),
+            ("crosshair/loop.toml", "preset = \"loop\"\n"),
+            ("crosshair/broken.toml", "description = 3\n"),
+            ("crosshair/notes.txt", "size = 1\n"),
+            ("pulse/slow.yaml", "enabled: true\nspeed: 0.5\n"),
+        ]);
+        let library = PresetLibrary::load(dir.path());
+
+        let names: Vec<&str> = library.presets(PresetKind::Crosshair).map(|preset| preset.name.as_str()).collect();
+        assert!(names.contains(&"outline") && names.contains(&"loop"), "{:?}", names);
+        assert!(!names.contains(&"broken") && !names.contains(&"notes"), "{:?}", names);
+        assert_eq!(names.iter().filter(|name| **name == "large").count(), 1);
+
+        let large = library.find(PresetKind::Crosshair, "large").unwrap();
+        assert_eq!(large.description.as_deref(), Some("Plus grand"));
+        assert!(matches!(large.source, PresetSource::User(_)));
+
+        // Un preset utilisateur basé sur un preset remplacé utilise la version utilisateur
+        let config = resolve(&library, "[crosshair]\npreset = \"outline\"\n").unwrap();
+        assert_eq!((config.crosshair.size, config.crosshair.thickness), (60, 1));
+
+        let config = resolve(&library, "[effects.pulse]\npreset = \"slow\"\n").unwrap();
+        assert!(config.effects.pulse.enabled);
+        assert_eq!(config.effects.pulse.speed, 0.5);
+
+        let error = resolve(&library, "[crosshair]\npreset = \"loop\"\n").unwrap_err();
+        assert_eq!(error, tr!("presets.too_deep", key = "crosshair.preset"));
+    }
+
+    #[test]
+    fn presets_keep_the_inherited_settings() {
+        let dir = user_presets(&[("crosshair/thin.toml", "thickness = 1\n")]);
+        let write = |name: &str, content: &str| fs::write(dir.path().join(name), content).unwrap();
+        write("base.toml", "[window]\nsize = 300\nopacity = 0.5\n[hotkeys]\nenabled = false\n[crosshair]\ngap = 7\n");
+        write("child.toml", "extends = \"base.toml\"\npreset = \"sniper\"\n");
+        write("thin.toml", "extends = \"base.toml\"\n[crosshair]\npreset = \"thin\"\n");
+
+        // Un preset complet ne définit que le crosshair et les effets
+        let config = CrosshairConfig::load_from_file(dir.path().join("child.toml")).unwrap();
+        assert_eq!((config.window.size, config.window.opacity), (300, 0.5));
+        assert!(!config.hotkeys.enabled);
+        assert_eq!(to_table(&config.crosshair), to_table(&Crosshair::preset_sniper_dot()));
+
+        // Un preset partiel garde les autres champs du parent
+        let config = CrosshairConfig::load_from_file(dir.path().join("thin.toml")).unwrap();
+        assert_eq!((config.crosshair.thickness, config.crosshair.gap), (1, 7));
+        assert_eq!(config.window.size, 300);
+    }
+}
@@ -0,0 +1,261 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::format::ConfigFormat;
+use crate::tr;
+
+/// Réglages des profils nommés (section `[profiles]` de config.toml)
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Named profiles")]
+pub struct ProfilesConfig {
+    #[schemars(description = "Directory containing the profiles (<name>.toml, .json, .yaml, .ron), relative to this file")]
+    pub directory: String,        // Dossier contenant les profils (<nom>.toml, .json, .yaml, .ron)
+    #[schemars(description = "Profile loaded at startup (F6 / F7 switch profiles, 1-9 jump to one)", example = &"cs2")]
+    pub active: Option<String>,   // Profil actif au démarrage (aucun = config.toml seul)
+}
+
+impl Default for ProfilesConfig {
+    fn default() -> Self {
+        Self {
+            directory: "profiles".to_string(),
+            active: None,
+        }
+    }
+}
+
+/// Gère la liste des profils disponibles et le profil courant
+#[derive(Debug, Clone)]
+pub struct ProfileManager {
+    directory: PathBuf,
+    names: Vec<String>,
+    paths: Vec<PathBuf>,
+    current: Option<usize>,
+}
+
+impl ProfileManager {
+    /// Crée le gestionnaire à partir des réglages et sélectionne le profil actif
+    ///
+    /// Un dossier relatif est résolu depuis `base_dir` (dossier de config.toml).
+    pub fn new(settings: &ProfilesConfig, base_dir: &Path) -> Self {
+        let mut manager = Self {
+            directory: base_dir.join(&settings.directory),
+            names: Vec::new(),
+            paths: Vec::new(),
+            current: None,
+        };
+        manager.refresh();
+
+        if let Some(active) = &settings.active {
+            if let Err(e) = manager.select(active) {
+                eprintln!("⚠️  {}", e);
+            }
+        }
+
+        manager
+    }
+
+    /// Applique de nouveaux réglages en conservant le profil courant si possible
+    pub fn reload(&mut self, settings: &ProfilesConfig, base_dir: &Path) {
+        let current_name = self.current_name().map(str::to_string);
+        *self = Self::new(settings, base_dir);
+        if let Some(name) = current_name {
+            let _ = self.select(&name);
+        }
+    }
+
+    /// Relit le dossier des profils en conservant le profil courant si possible
+    pub fn refresh(&mut self) {
+        let current_name = self.current_name().map(str::to_string);
+        (self.names, self.paths) = Self::scan_directory(&self.directory).into_iter().unzip();
+        self.current = current_name.and_then(|name| self.index_of(&name));
+    }
+
+    /// Liste les fichiers de configuration du dossier, triés par nom
+    ///
+    /// Si plusieurs fichiers portent le même nom, l'ordre des formats reconnus
+    /// décide (`cs2.toml` avant `cs2.json`).
+    fn scan_directory(directory: &Path) -> Vec<(String, PathBuf)> {
+        let mut profiles: Vec<(String, PathBuf)> = fs::read_dir(directory)
+            .map(|entries| {
+                entries
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_file() && ConfigFormat::from_path(path).is_ok())
+                    .filter_map(|path| {
+                        let name = path.file_stem()?.to_string_lossy().into_owned();
+                        Some((name, path))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let format_rank = |path: &Path| {
+            let extension = path.extension().map(|ext| ext.to_string_lossy().to_lowercase());
+            ConfigFormat::SUPPORTED_EXTENSIONS
+                .iter()
+                .position(|supported| Some(supported.to_string()) == extension)
+        };
+        profiles.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| format_rank(&a.1).cmp(&format_rank(&b.1))));
+        profiles.dedup_by(|a, b| a.0 == b.0);
+        profiles
+    }
+
+    fn index_of(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    /// Noms des profils disponibles
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Nom du profil courant (None = configuration de base)
+    pub fn current_name(&self) -> Option<&str> {
+        self.current.map(|i| self.names[i].as_str())
+    }
+
+    /// Sélectionne un profil par son nom
+    pub fn select(&mut self, name: &str) -> Result<(), String> {
+        match self.index_of(name) {
+            Some(index) => {
+                self.current = Some(index);
+                Ok(())
+            }
+            None => Err(tr!("profiles.not_found", name = name, directory = self.directory.display())),
+        }
+    }
+
+    /// Sélectionne un profil par sa position (0 = premier profil)
+    pub fn select_index(&mut self, index: usize) -> Result<(), String> {
+        if index < self.names.len() {
+            self.current = Some(index);
+            Ok(())
+        } else {
+            Err(tr!("profiles.no_profile_at", position = index + 1))
+        }
+    }
+
+    /// Passe au profil suivant (revient au premier après le dernier)
+    pub fn next_profile(&mut self) -> Option<&str> {
+        if self.names.is_empty() {
+            return None;
+        }
+        self.current = Some(match self.current {
+            Some(i) => (i + 1) % self.names.len(),
+            None => 0,
+        });
+        self.current_name()
+    }
+
+    /// Passe au profil précédent (revient au dernier avant le premier)
+    pub fn previous_profile(&mut self) -> Option<&str> {
+        if self.names.is_empty() {
+            return None;
+        }
+        self.current = Some(match self.current {
+            Some(0) | None => self.names.len() - 1,
+            Some(i) => i - 1,
+        });
+        self.current_name()
+    }
+
+    /// Nom et fichier du profil courant (None = configuration de base)
+    pub fn current_profile(&self) -> Option<(&str, &Path)> {
+        self.current.map(|i| (self.names[i].as_str(), self.paths[i].as_path()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Dossier de configuration avec un dossier `profiles` contenant ces fichiers
+    fn profiles(files: &[&str]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let profiles = dir.path().join("profiles");
+        fs::create_dir(&profiles).unwrap();
+        for name in files {
+            fs::write(profiles.join(name), "").unwrap();
+        }
+        dir
+    }
+
+    fn open(dir: &tempfile::TempDir, active: Option<&str>) -> ProfileManager {
+        let settings = ProfilesConfig { active: active.map(str::to_string), ..ProfilesConfig::default() };
+        ProfileManager::new(&settings, dir.path())
+    }
+
+    #[test]
+    fn profiles_are_sorted_by_name_and_format() {
+        let dir = profiles(&["valorant.yaml", "cs2.json", "cs2.toml", "apex.ron", "notes.txt"]);
+        fs::create_dir(dir.path().join("profiles/archive.toml")).unwrap();
+        let mut manager = open(&dir, None);
+        assert_eq!(manager.names(), ["apex", "cs2", "valorant"]);
+        assert_eq!(manager.current_name(), None);
+
+        manager.select("cs2").unwrap();
+        let (_, path) = manager.current_profile().unwrap();
+        assert_eq!(path.file_name().unwrap(), "cs2.toml");
+    }
+
+    #[test]
+    fn cycling_wraps_around_in_both_directions() {
+        let dir = profiles(&["a.toml", "b.toml", "c.toml"]);
+        let mut manager = open(&dir, None);
+
+        // Depuis la configuration de base : premier profil, ou dernier en arrière
+        assert_eq!(manager.next_profile(), Some("a"));
+        assert_eq!(manager.next_profile(), Some("b"));
+        assert_eq!(manager.next_profile(), Some("c"));
+        assert_eq!(manager.next_profile(), Some("a"));
+        assert_eq!(manager.previous_profile(), Some("c"));
+        assert_eq!(manager.previous_profile(), Some("b"));
+
+        let mut manager = open(&dir, None);
+        assert_eq!(manager.previous_profile(), Some("c"));
+
+        let mut empty = open(&profiles(&[]), None);
+        assert_eq!(empty.next_profile(), None);
+        assert_eq!(empty.previous_profile(), None);
+    }
+
+    #[test]
+    fn selection_by_name_and_position() {
+        let dir = profiles(&["a.toml", "b.toml"]);
+        let mut manager = open(&dir, Some("b"));
+        assert_eq!(manager.current_name(), Some("b"));
+
+        manager.select_index(0).unwrap();
+        assert_eq!(manager.current_name(), Some("a"));
+        assert!(manager.select_index(2).is_err());
+        assert!(manager.select("missing").is_err());
+        assert_eq!(manager.current_name(), Some("a"));
+
+        // Un profil actif inconnu laisse la configuration de base
+        assert_eq!(open(&dir, Some("missing")).current_name(), None);
+    }
+
+    #[test]
+    fn refresh_and_reload_keep_the_current_profile() {
+        let dir = profiles(&["b.toml", "c.toml"]);
+        let mut manager = open(&dir, Some("b"));
+
+        // Un nouveau profil avant le profil courant ne change pas la sélection
+        fs::write(dir.path().join("profiles/a.toml"), "").unwrap();
+        manager.refresh();
+        assert_eq!(manager.names(), ["a", "b", "c"]);
+        assert_eq!(manager.current_name(), Some("b"));
+
+        manager.reload(&ProfilesConfig::default(), dir.path());
+        assert_eq!(manager.current_name(), Some("b"));
+
+        // Profil supprimé : retour à la configuration de base
+        fs::remove_file(dir.path().join("profiles/b.toml")).unwrap();
+        manager.refresh();
+        assert_eq!(manager.current_name(), None);
+    }
+}
@@ -0,0 +1,230 @@
+use schemars::schema_for;
+use serde_json::{Map, Value};
+use std::fmt::Write;
+
+use super::presets::{PresetKind, PresetLibrary};
+use super::CrosshairConfig;
+
+/// En-tête du modèle : ce qui ne se déduit pas des types
+const TEMPLATE_HEADER: &str = r##"# ========================================
+# CROSSHAIR OVERLAY - CONFIGURATION TEMPLATE
+# ========================================
+# Generated from the configuration types: `crosshair-overlay print-default`
+# Press F5 in the application to reload configuration
+# Copy this file to config.toml and modify as needed
+# Location: ~/.config/crosshair-overlay/ (Linux), %APPDATA%\crosshair-overlay\ (Windows),
+# or any path passed with --config <path> / CROSSHAIR_CONFIG
+# JSON, YAML and RON are also accepted (config.json, config.yaml, config.ron)
+# JSON Schema for editors: `crosshair-overlay print-schema > config.schema.json`
+# Every field is optional: missing fields use the defaults below
+# Environment variables override this file: CROSSHAIR__CROSSHAIR__COLOR="#FF00FF"
+# (see where each value comes from with `crosshair-overlay explain`)
+#
+# Inheritance (paths relative to this file):
+# extends = "base.toml"              # Inherit every field from a parent file
+# include = ["shared/effects.toml"]  # Merge fragments on top of the parent
+#
+# Presets (list them with `crosshair-overlay presets`):
+# preset = "sniper"                  # Whole configuration, fields below override it
+# [crosshair] preset = "discrete"    # Or one section: only write the fields that differ
+"##;
+
+/// Schéma JSON de `CrosshairConfig` (valeurs par défaut, bornes, variantes des enums)
+pub fn json_schema() -> String {
+    let mut schema = schema_for!(CrosshairConfig).to_value();
+    narrow_floats(&mut schema);
+    let mut text = serde_json::to_string_pretty(&schema).expect("un schéma JSON se sérialise toujours");
+    text.push('\n');
+    text
+}
+
+/// Modèle config.toml commenté, généré à partir du schéma et des valeurs par défaut
+pub fn template() -> String {
+    let mut schema = schema_for!(CrosshairConfig).to_value();
+    narrow_floats(&mut schema);
+    let mut defaults = serde_json::to_value(CrosshairConfig::default()).expect("configuration sérialisable");
+    narrow_floats(&mut defaults);
+
+    let definitions = schema.get("$defs").and_then(Value::as_object).cloned().unwrap_or_default();
+    let mut out = String::from(TEMPLATE_HEADER);
+
+    for (name, property) in properties(&schema) {
+        let _ = write!(out, "\n# ========================================\n# {}\n# ========================================\n", name.to_uppercase());
+        write_section(&mut out, &definitions, name, property, &defaults[name]);
+    }
+
+    write_presets(&mut out);
+    out
+}
+
+/// Écrit une section `[chemin]` : d'abord ses valeurs, puis ses sous-sections
+fn write_section(out: &mut String, definitions: &Map<String, Value>, path: &str, property: &Value, defaults: &Value) {
+    let schema = resolve(definitions, property);
+    let (tables, leaves): (Vec<_>, Vec<_>) = properties(schema)
+        .into_iter()
+        .partition(|(_, field)| resolve(definitions, field).get("properties").is_some());
+
+    if !leaves.is_empty() {
+        out.push('\n');
+        if let Some(description) = property.get("description").and_then(Value::as_str) {
+            let _ = writeln!(out, "# {}", description);
+        }
+        let _ = writeln!(out, "[{}]", path);
+    }
+
+    for (name, field) in leaves {
+        out.push('\n');
+        if let Some(description) = field.get("description").and_then(Value::as_str) {
+            let _ = writeln!(out, "# {}", description);
+        }
+        if let Some(constraint) = constraint(definitions, field) {
+            let _ = writeln!(out, "# {}", constraint);
+        }
+        match &defaults[name] {
+            // Champ optionnel sans valeur par défaut : exemple commenté
+            Value::Null => {
+                let example = field["examples"].get(0).map(toml_literal).unwrap_or_default();
+                let _ = writeln!(out, "# {} = {}", name, example);
+            }
+            value => {
+                let _ = writeln!(out, "{} = {}", name, toml_literal(value));
+            }
+        }
+    }
+
+    for (name, field) in tables {
+        write_section(out, definitions, &format!("{}.{}", path, name), field, &defaults[name]);
+    }
+}
+
+/// Liste des presets intégrés, par section
+fn write_presets(out: &mut String) {
+    let library = PresetLibrary::builtin();
+    out.push_str("\n# ========================================\n# BUILT-IN PRESETS\n# ========================================\n");
+    out.push_str("# Use them with `preset = \"<name>\"` in the matching section\n#\n");
+    for kind in PresetKind::ALL {
+        let names: Vec<&str> = library.presets(kind).map(|preset| preset.name.as_str()).collect();
+        if names.is_empty() {
+            continue;
+        }
+        let section = match kind.section() {
+            section if section.is_empty() => "top level".to_string(),
+            section => format!("[{}]", section),
+        };
+        let _ = writeln!(out, "# {}: {}", section, names.join(", "));
+    }
+}
+
+/// Propriétés d'un objet du schéma, dans l'ordre des champs
+fn properties(schema: &Value) -> Vec<(&str, &Value)> {
+    schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .map(|properties| properties.iter().map(|(name, value)| (name.as_str(), value)).collect())
+        .unwrap_or_default()
+}
+
+/// Suit `$ref` (et `anyOf` des options) jusqu'à la définition
+fn resolve<'a>(definitions: &'a Map<String, Value>, schema: &'a Value) -> &'a Value {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = reference.rsplit('/').next().unwrap_or_default();
+        return definitions.get(name).map_or(schema, |definition| resolve(definitions, definition));
+    }
+    if let Some(variants) = schema.get("anyOf").and_then(Value::as_array) {
+        if let Some(variant) = variants.iter().find(|variant| variant.get("type") != Some(&Value::from("null"))) {
+            return resolve(definitions, variant);
+        }
+    }
+    schema
+}
+
+/// Valeurs possibles ou bornes d'un champ, pour le commentaire
+fn constraint(definitions: &Map<String, Value>, field: &Value) -> Option<String> {
+    let schema = resolve(definitions, field);
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        let values: Vec<String> = values.iter().map(toml_literal).collect();
+        return Some(format!("Values: {}", values.join(", ")));
+    }
+    match (field.get("minimum"), field.get("maximum")) {
+        (Some(min), Some(max)) => Some(format!("Range: {} to {}", min, max)),
+        // Le minimum 0 des entiers non signés n'apporte rien
+        (Some(min), None) if min.as_f64() == Some(0.0) => None,
+        (Some(min), None) => Some(format!("Minimum: {}", min)),
+        (None, Some(max)) => Some(format!("Maximum: {}", max)),
+        (None, None) => None,
+    }
+}
+
+/// Écrit une valeur JSON en TOML (les nombres décimaux gardent leur point)
+fn toml_literal(value: &Value) -> String {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let text = number.to_string();
+            if text.contains(['.', 'e']) { text } else { format!("{}.0", text) }
+        }
+        Value::String(text) => toml::Value::String(text.clone()).to_string(),
+        value => value.to_string(),
+    }
+}
+
+/// Réécrit les nombres issus de f32 sous leur forme courte (0.8 au lieu de 0.800000011920929)
+fn narrow_floats(value: &mut Value) {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let narrowed = number.as_f64().map(|x| x as f32).filter(|x| f64::from(*x) == number.as_f64().unwrap_or_default());
+            if let Some(short) = narrowed.and_then(|x| x.to_string().parse::<f64>().ok()) {
+                if let Some(short) = serde_json::Number::from_f64(short) {
+                    *number = short;
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(narrow_floats),
+        Value::Object(map) => map.values_mut().for_each(narrow_floats),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Fichiers générés, à la racine du dépôt
+    const TEMPLATE_FILE_NAME: &str = "config.toml.template";
+    const SCHEMA_FILE_NAME: &str = "config.schema.json";
+
+    /// Compare un fichier généré au contenu attendu, ou le réécrit avec UPDATE_GENERATED=1
+    fn assert_up_to_date(file_name: &str, expected: &str) {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(file_name);
+        if std::env::var_os("UPDATE_GENERATED").is_some() {
+            std::fs::write(&path, expected).unwrap();
+            return;
+        }
+        let actual = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            actual == expected,
+            "{} n'est plus à jour : relancer avec UPDATE_GENERATED=1 cargo test",
+            file_name
+        );
+    }
+
+    #[test]
+    fn template_is_up_to_date() {
+        assert_up_to_date(TEMPLATE_FILE_NAME, &template());
+    }
+
+    #[test]
+    fn json_schema_is_up_to_date() {
+        assert_up_to_date(SCHEMA_FILE_NAME, &json_schema());
+    }
+
+    #[test]
+    fn template_parses_to_defaults() {
+        let table: toml::Table = template().parse().unwrap();
+        let config: CrosshairConfig = toml::Value::Table(table).try_into().unwrap();
+        assert_eq!(
+            serde_json::to_value(&config).unwrap(),
+            serde_json::to_value(CrosshairConfig::default()).unwrap()
+        );
+    }
+}
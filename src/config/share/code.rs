@@ -0,0 +1,170 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+
+use crate::config::effects::Effects;
+use crate::config::window::WindowConfig;
+use crate::config::CrosshairConfig;
+use crate::crosshair::Crosshair;
+use crate::tr;
+
+/// Préfixe des codes de partage de l'application
+pub const PREFIX: &str = "XH";
+/// Version de l'encodage binaire, à incrémenter à chaque changement des structures
+///
+/// Les champs sont écrits dans l'ordre, sans leur nom : ajouter, retirer ou
+/// déplacer un réglage du crosshair, des effets ou de la fenêtre change le format.
+pub const VERSION: u8 = 1;
+/// Taille de la somme de contrôle CRC32 en fin de code
+const CHECKSUM_LENGTH: usize = 4;
+/// Taille maximale de la configuration décompressée (protège contre les codes malveillants)
+const MAX_DECODED_LENGTH: usize = 64 * 1024;
+
+/// Partie de la configuration incluse dans un code
+///
+/// Les profils, raccourcis, règles de focus et messages restent propres à chaque machine.
+#[derive(Serialize)]
+struct SharedConfig<'a> {
+    crosshair: &'a Crosshair,
+    effects: &'a Effects,
+    window: &'a WindowConfig,
+}
+
+#[derive(Deserialize)]
+struct OwnedSharedConfig {
+    crosshair: Crosshair,
+    effects: Effects,
+    window: WindowConfig,
+}
+
+/// Encode la configuration en code texte `XH<version>-<base64url>` (ex: `XH1-...`)
+///
+/// Contenu binaire : version (1 octet), configuration sérialisée avec postcard
+/// (entiers compacts, sans noms de champs, couleurs en 4 octets) et compressée
+/// en deflate, puis CRC32 de l'ensemble. Le texte n'utilise que des caractères
+/// sûrs dans une URL.
+pub fn encode(config: &CrosshairConfig) -> Result<String, String> {
+    let shared = SharedConfig {
+        crosshair: &config.crosshair,
+        effects: &config.effects,
+        window: &config.window,
+    };
+    let serialized = postcard::to_allocvec(&shared).map_err(|e| tr!("code.encode_failed", error = e))?;
+
+    let mut bytes = vec![VERSION];
+    bytes.extend(miniz_oxide::deflate::compress_to_vec(&serialized, 10));
+    let checksum = crc32fast::hash(&bytes);
+    bytes.extend(checksum.to_be_bytes());
+
+    Ok(format!("{}{}-{}", PREFIX, VERSION, URL_SAFE_NO_PAD.encode(bytes)))
+}
+
+/// Décode un code produit par `encode`
+///
+/// La configuration obtenue n'est pas validée : voir `CrosshairConfig::validate`.
+pub fn decode(code: &str) -> Result<CrosshairConfig, String> {
+    let invalid = |detail: String| tr!("code.invalid", detail = detail);
+
+    let (header, payload) = code
+        .trim()
+        .split_once('-')
+        .filter(|(header, _)| header.starts_with(PREFIX))
+        .ok_or_else(|| invalid(tr!("code.prefix", prefix = PREFIX, version = VERSION)))?;
+    let bytes = URL_SAFE_NO_PAD
+        .decode(payload)
+        .map_err(|_| invalid(tr!("code.characters")))?;
+    if bytes.len() <= 1 + CHECKSUM_LENGTH {
+        return Err(invalid(tr!("code.too_short")));
+    }
+
+    let (content, checksum) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+    if crc32fast::hash(content).to_be_bytes() != checksum {
+        return Err(invalid(tr!("code.checksum")));
+    }
+
+    // La version de l'en-tête texte doit correspondre à celle du contenu
+    let version = content[0];
+    if header != format!("{}{}", PREFIX, version) {
+        return Err(invalid(tr!("code.header_mismatch")));
+    }
+    if version < VERSION {
+        return Err(tr!("code.version_too_old", version = version, current = VERSION));
+    }
+    if version > VERSION {
+        return Err(tr!("code.version_too_new", version = version, current = VERSION));
+    }
+
+    let serialized = miniz_oxide::inflate::decompress_to_vec_with_limit(&content[1..], MAX_DECODED_LENGTH)
+        .map_err(|_| invalid(tr!("code.compressed_content")))?;
+    let shared: OwnedSharedConfig = postcard::from_bytes(&serialized).map_err(|e| invalid(e.to_string()))?;
+    Ok(CrosshairConfig {
+        crosshair: shared.crosshair,
+        effects: shared.effects,
+        window: shared.window,
+        ..CrosshairConfig::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::color::Color;
+    use crate::config::window::{MonitorSelection, Offset};
+
+    /// Code exporté avec la version 1 : crosshair de taille 12, couleur
+    /// `#FF000080`, pulsation activée, opacité 0.5, écran `"HDMI-1"` et x à 5 %
+    const VERSION_1_CODE: &str = "XH1-AVWNsQ2AMAwE36CkYggWoGAAwEgUIMEWDMIaoWKgMFN4ElJgvWWf7ZerwgTgoAYwpCCFREIOt1-_Vk_n-nwHBeoRCXPGuQgomNfIqipGLHaUdp62pWnT-lL8I9r5zXfyAJIB8JA";
+
+    /// Code d'une version donnée, avec un contenu quelconque
+    fn code_with_version(version: u8) -> String {
+        let mut bytes = vec![version];
+        bytes.extend(miniz_oxide::deflate::compress_to_vec(b"", 10));
+        bytes.extend(crc32fast::hash(&bytes).to_be_bytes());
+        format!("{}{}-{}", PREFIX, version, URL_SAFE_NO_PAD.encode(bytes))
+    }
+
+    fn shared_config() -> CrosshairConfig {
+        let mut config = CrosshairConfig::default();
+        config.crosshair.size = 12;
+        config.crosshair.color = Color::rgba(255, 0, 0, 128);
+        config.effects.pulse.enabled = true;
+        config.window.opacity = 0.5;
+        config.window.monitor = MonitorSelection::Name("HDMI-1".to_string());
+        config.window.position.x = Some(Offset::Percent(5.0));
+        config
+    }
+
+    fn assert_shared_settings(decoded: &CrosshairConfig) {
+        assert_eq!(decoded.crosshair.size, 12);
+        assert_eq!(decoded.crosshair.color, Color::rgba(255, 0, 0, 128));
+        assert!(decoded.effects.pulse.enabled);
+        assert_eq!(decoded.window, shared_config().window);
+    }
+
+    #[test]
+    fn codes_share_the_crosshair_effects_and_window_but_not_the_profiles() {
+        let mut config = shared_config();
+        config.hotkeys.enabled = false;
+        config.profiles.active = Some("sniper".to_string());
+
+        let decoded = decode(&encode(&config).unwrap()).unwrap();
+        assert_shared_settings(&decoded);
+        assert!(decoded.hotkeys.enabled);
+        assert_eq!(decoded.profiles.active, None);
+    }
+
+    /// Échoue si les structures changent : incrémenter VERSION et remplacer le code
+    #[test]
+    fn version_1_codes_still_decode() {
+        let decoded = decode(VERSION_1_CODE).expect("structures modifiées : incrémenter code::VERSION");
+        assert_shared_settings(&decoded);
+    }
+
+    #[test]
+    fn other_versions_are_rejected_with_the_right_advice() {
+        let older = decode(&code_with_version(VERSION - 1)).unwrap_err();
+        assert_eq!(older, tr!("code.version_too_old", version = VERSION - 1, current = VERSION));
+        let newer = decode(&code_with_version(VERSION + 1)).unwrap_err();
+        assert_eq!(newer, tr!("code.version_too_new", version = VERSION + 1, current = VERSION));
+    }
+}
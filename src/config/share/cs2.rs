@@ -0,0 +1,510 @@
+use crate::config::color::Color;
+use crate::config::effects::mouse::gap_effect::GapMode;
+use crate::config::CrosshairConfig;
+use crate::crosshair::CrosshairStyle;
+use crate::tr;
+
+use super::Conversion;
+
+/// Préfixe des codes de partage Counter-Strike
+pub const PREFIX: &str = "CSGO";
+/// Alphabet des codes de partage (sans les caractères ambigus 0, 1, I, O, l, g)
+const DICTIONARY: &[u8] = b"ABCDEFGHJKLMNOPQRSTUVWXYZabcdefhijkmnopqrstuvwxyz23456789";
+/// Nombre de caractères d'un code (5 groupes de 5)
+const CODE_LENGTH: usize = 25;
+/// Nombre d'octets encodés dans un code
+const BYTE_LENGTH: usize = 18;
+
+// Conversion des unités CS2 en pixels, approximée pour un écran 1080p
+const PIXELS_PER_UNIT: f32 = 2.0;
+const GAP_OFFSET: f32 = 4.0; // cl_crosshairgap 0 laisse environ 4 pixels
+
+// Couleurs prédéfinies de cl_crosshaircolor 0 à 4 (5 = couleur personnalisée)
+const PRESET_COLORS: [Color; 5] = [
+    Color::rgb(250, 50, 50),  // Rouge
+    Color::rgb(50, 250, 50),  // Vert
+    Color::rgb(250, 250, 50), // Jaune
+    Color::rgb(50, 50, 250),  // Bleu
+    Color::rgb(50, 250, 250), // Cyan
+];
+const CUSTOM_COLOR: u8 = 5;
+
+// Valeurs de cl_crosshairstyle
+const STYLE_CLASSIC_DYNAMIC: u8 = 2;
+const STYLE_CLASSIC_STATIC: u8 = 4;
+
+/// Réglages d'un crosshair Counter-Strike 2, tels que stockés dans un code de partage
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cs2Crosshair {
+    pub style: u8,             // cl_crosshairstyle (0-5)
+    pub length: f32,           // cl_crosshairsize
+    pub thickness: f32,        // cl_crosshairthickness
+    pub gap: f32,              // cl_crosshairgap
+    pub outline_enabled: bool, // cl_crosshair_drawoutline
+    pub outline: f32,          // cl_crosshair_outlinethickness
+    pub color: u8,             // cl_crosshaircolor (0-5)
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    pub alpha_enabled: bool, // cl_crosshairusealpha
+    pub alpha: u8,
+    pub center_dot: bool,   // cl_crosshairdot
+    pub t_style: bool,      // cl_crosshair_t
+    pub split_distance: u8, // cl_crosshair_dynamic_splitdist
+    pub follow_recoil: bool,
+    pub fixed_gap: f32,
+    pub inner_split_alpha: f32,
+    pub outer_split_alpha: f32,
+    pub split_size_ratio: f32,
+    pub deployed_weapon_gap: bool,
+}
+
+impl Default for Cs2Crosshair {
+    /// Crosshair par défaut de CS2
+    fn default() -> Self {
+        Self {
+            style: STYLE_CLASSIC_STATIC,
+            length: 5.0,
+            thickness: 0.5,
+            gap: 1.0,
+            outline_enabled: true,
+            outline: 1.0,
+            color: 1,
+            red: 50,
+            green: 250,
+            blue: 50,
+            alpha_enabled: true,
+            alpha: 200,
+            center_dot: false,
+            t_style: false,
+            split_distance: 7,
+            follow_recoil: false,
+            fixed_gap: 3.0,
+            inner_split_alpha: 0.0,
+            outer_split_alpha: 1.0,
+            split_size_ratio: 1.0,
+            deployed_weapon_gap: false,
+        }
+    }
+}
+
+impl Cs2Crosshair {
+    /// Décode un code `CSGO-xxxxx-xxxxx-xxxxx-xxxxx-xxxxx`
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let bytes = code_to_bytes(code)?;
+
+        let checksum = bytes[1..].iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+        if checksum != bytes[0] {
+            return Err(tr!("cs2.checksum"));
+        }
+
+        Ok(Self {
+            gap: bytes[2] as i8 as f32 / 10.0,
+            outline: bytes[3] as f32 / 2.0,
+            red: bytes[4],
+            green: bytes[5],
+            blue: bytes[6],
+            alpha: bytes[7],
+            split_distance: bytes[8] & 0x7F,
+            follow_recoil: bytes[8] & 0x80 != 0,
+            fixed_gap: bytes[9] as i8 as f32 / 10.0,
+            color: bytes[10] & 0x07,
+            outline_enabled: bytes[10] & 0x08 != 0,
+            inner_split_alpha: (bytes[10] >> 4) as f32 / 10.0,
+            outer_split_alpha: (bytes[11] & 0x0F) as f32 / 10.0,
+            split_size_ratio: (bytes[11] >> 4) as f32 / 10.0,
+            thickness: bytes[12] as f32 / 10.0,
+            center_dot: bytes[13] & 0x10 != 0,
+            deployed_weapon_gap: bytes[13] & 0x20 != 0,
+            alpha_enabled: bytes[13] & 0x40 != 0,
+            t_style: bytes[13] & 0x80 != 0,
+            style: (bytes[13] & 0x0F) >> 1,
+            length: ((((bytes[15] & 0x1F) as u16) << 8) | bytes[14] as u16) as f32 / 10.0,
+        })
+    }
+
+    /// Encode les réglages en code de partage
+    pub fn encode(&self) -> String {
+        let tenths = |value: f32| (value * 10.0).round();
+        let signed_tenths = |value: f32| tenths(value).clamp(-128.0, 127.0) as i8 as u8;
+        let length = tenths(self.length).clamp(0.0, 0x1FFF as f32) as u16;
+
+        let mut bytes = [0u8; BYTE_LENGTH];
+        bytes[1] = 1;
+        bytes[2] = signed_tenths(self.gap);
+        bytes[3] = (self.outline * 2.0).round().clamp(0.0, 255.0) as u8;
+        bytes[4] = self.red;
+        bytes[5] = self.green;
+        bytes[6] = self.blue;
+        bytes[7] = self.alpha;
+        bytes[8] = (self.split_distance & 0x7F) | ((self.follow_recoil as u8) << 7);
+        bytes[9] = signed_tenths(self.fixed_gap);
+        bytes[10] = (self.color & 0x07)
+            | ((self.outline_enabled as u8) << 3)
+            | ((tenths(self.inner_split_alpha).clamp(0.0, 15.0) as u8) << 4);
+        bytes[11] = (tenths(self.outer_split_alpha).clamp(0.0, 15.0) as u8)
+            | ((tenths(self.split_size_ratio).clamp(0.0, 15.0) as u8) << 4);
+        bytes[12] = tenths(self.thickness).clamp(0.0, 255.0) as u8;
+        bytes[13] = ((self.style & 0x07) << 1)
+            | ((self.center_dot as u8) << 4)
+            | ((self.deployed_weapon_gap as u8) << 5)
+            | ((self.alpha_enabled as u8) << 6)
+            | ((self.t_style as u8) << 7);
+        bytes[14] = (length & 0xFF) as u8;
+        bytes[15] = (length >> 8) as u8;
+        bytes[0] = bytes[1..].iter().fold(0u8, |sum, byte| sum.wrapping_add(*byte));
+
+        bytes_to_code(bytes)
+    }
+
+    /// Style dynamique : le crosshair s'écarte en tirant et en se déplaçant
+    fn is_dynamic(&self) -> bool {
+        !matches!(self.style, 1 | STYLE_CLASSIC_STATIC)
+    }
+
+    /// Couleur effective (prédéfinie ou personnalisée)
+    fn effective_color(&self) -> Color {
+        PRESET_COLORS
+            .get(self.color as usize)
+            .copied()
+            .unwrap_or(Color::rgb(self.red, self.green, self.blue))
+    }
+
+    /// Applique le crosshair CS2 sur une configuration existante
+    ///
+    /// Les effets et la fenêtre de `base` sont conservés, à part l'effet de gap
+    /// de la souris utilisé pour approximer les styles dynamiques.
+    pub fn to_config(&self, base: &CrosshairConfig) -> Conversion<CrosshairConfig> {
+        let mut conversion = Conversion::new(base.clone());
+        let config = &mut conversion.value;
+        let crosshair = &mut config.crosshair;
+
+        let gap = ((self.gap + GAP_OFFSET).max(0.0)).round() as u32;
+        let thickness = ((self.thickness * PIXELS_PER_UNIT).round() as u32).max(1);
+        let length = (self.length * PIXELS_PER_UNIT).round() as u32;
+        let alpha = if self.alpha_enabled { self.alpha as f32 / 255.0 } else { 1.0 };
+
+        crosshair.style = if self.t_style { CrosshairStyle::T } else { CrosshairStyle::Classic };
+        crosshair.gap = gap;
+        crosshair.size = gap + length;
+        crosshair.thickness = thickness;
+        crosshair.color = self.effective_color();
+        crosshair.alpha = alpha;
+        crosshair.rotation = 0.0;
+        crosshair.triangle_bars = false;
+
+        crosshair.outline.enabled = self.outline_enabled;
+        crosshair.outline.thickness = (self.outline.round() as u32).max(1);
+        crosshair.outline.color = Color::BLACK;
+        crosshair.outline.alpha = alpha;
+
+        // Le point central de CS2 reprend la couleur et l'épaisseur des lignes
+        crosshair.center_dot.enabled = self.center_dot;
+        crosshair.center_dot.size = thickness;
+        crosshair.center_dot.color = crosshair.color;
+        crosshair.center_dot.alpha = alpha;
+
+        if length == 0 && self.center_dot {
+            crosshair.style = CrosshairStyle::Dot;
+        }
+
+        let gap_effect = &mut config.effects.mouse.gap_effect;
+        if self.is_dynamic() {
+            config.effects.mouse.enabled = true;
+            gap_effect.enabled = true;
+            gap_effect.mode = GapMode::Expand;
+            conversion.lossy(tr!("cs2.dynamic_style", style = self.style));
+        } else {
+            gap_effect.enabled = false;
+        }
+
+        if self.follow_recoil {
+            conversion.lossy(tr!("cs2.follow_recoil"));
+        }
+        if self.deployed_weapon_gap {
+            conversion.lossy(tr!("cs2.weapon_gap"));
+        }
+        conversion
+    }
+
+    /// Convertit une configuration en crosshair CS2, en signalant les pertes
+    pub fn from_config(config: &CrosshairConfig) -> Conversion<Self> {
+        let crosshair = &config.crosshair;
+        let effects = &config.effects;
+        let mut conversion = Conversion::new(Self::default());
+        let mut lossy = Vec::new();
+
+        let line_length = crosshair.size.saturating_sub(crosshair.gap);
+        let value = &mut conversion.value;
+        value.gap = crosshair.gap as f32 - GAP_OFFSET;
+        value.length = line_length as f32 / PIXELS_PER_UNIT;
+        value.thickness = crosshair.thickness as f32 / PIXELS_PER_UNIT;
+        value.t_style = false;
+        value.center_dot = crosshair.center_dot.enabled;
+
+        match crosshair.style {
+            CrosshairStyle::Classic => {}
+            CrosshairStyle::T => value.t_style = true,
+            CrosshairStyle::Dot => {
+                value.length = 0.0;
+                value.center_dot = true;
+            }
+            ref other => lossy.push(tr!("share.style_unavailable", style = format!("{:?}", other), game = "CS2")),
+        }
+        if value.gap < -12.8 || value.gap > 12.7 {
+            lossy.push(tr!("cs2.gap_out_of_range", gap = crosshair.gap));
+        }
+
+        // Couleur : l'alpha de la couleur est combiné avec crosshair.alpha
+        let color = crosshair.color;
+        let alpha = (color.a as f32 / 255.0) * crosshair.alpha.clamp(0.0, 1.0);
+        value.color = CUSTOM_COLOR;
+        value.red = color.r;
+        value.green = color.g;
+        value.blue = color.b;
+        value.alpha = (alpha * 255.0).round() as u8;
+        value.alpha_enabled = value.alpha < 255;
+
+        value.outline_enabled = crosshair.outline.enabled;
+        value.outline = crosshair.outline.thickness.min(3) as f32;
+        if crosshair.outline.enabled {
+            if crosshair.outline.color != Color::BLACK {
+                lossy.push(tr!("share.outline_color", color = crosshair.outline.color, game = "CS2"));
+            }
+            if crosshair.outline.thickness > 3 {
+                lossy.push(tr!("cs2.outline_thickness", thickness = crosshair.outline.thickness));
+            }
+        }
+
+        if value.center_dot {
+            let dot = &crosshair.center_dot;
+            if dot.color != crosshair.color || dot.alpha != crosshair.alpha {
+                lossy.push(tr!("share.center_dot_color", game = "CS2"));
+            }
+            if dot.size != crosshair.thickness {
+                lossy.push(tr!("cs2.center_dot_size"));
+            }
+        }
+
+        if crosshair.rotation != 0.0 {
+            lossy.push(tr!("share.rotation", rotation = crosshair.rotation));
+        }
+        if crosshair.triangle_bars {
+            lossy.push(tr!("share.triangle_bars"));
+        }
+
+        // Un effet de gap à l'appui se rapproche d'un style dynamique
+        value.style = if effects.mouse.enabled && effects.mouse.gap_effect.enabled {
+            lossy.push(tr!("cs2.gap_effect"));
+            STYLE_CLASSIC_DYNAMIC
+        } else {
+            STYLE_CLASSIC_STATIC
+        };
+        for (enabled, name) in [
+            (effects.pulse.enabled, "pulse"),
+            (effects.shake.enabled, "shake"),
+            (effects.rainbow.enabled, "rainbow"),
+            (effects.mouse.enabled && effects.mouse.visibility_effect.enabled, "visibility_effect"),
+        ] {
+            if enabled {
+                lossy.push(tr!("share.effect_ignored", effect = name));
+            }
+        }
+
+        conversion.lossy = lossy;
+        conversion
+    }
+}
+
+/// Convertit le texte du code en octets (grand entier en base 57, petit boutiste)
+fn code_to_bytes(code: &str) -> Result<[u8; BYTE_LENGTH], String> {
+    let code = code.trim();
+    let digits: String = code
+        .strip_prefix(PREFIX)
+        .ok_or_else(|| tr!("cs2.prefix", prefix = PREFIX))?
+        .chars()
+        .filter(|c| *c != '-')
+        .collect();
+    if digits.chars().count() != CODE_LENGTH {
+        return Err(tr!("cs2.length", length = CODE_LENGTH, prefix = PREFIX));
+    }
+
+    let mut bytes = [0u8; BYTE_LENGTH];
+    // Le premier caractère est le chiffre de poids faible
+    for c in digits.chars().rev() {
+        let digit = DICTIONARY
+            .iter()
+            .position(|d| *d as char == c)
+            .ok_or_else(|| tr!("cs2.character", character = c))?;
+
+        // bytes = bytes * 57 + digit
+        let mut carry = digit as u32;
+        for byte in bytes.iter_mut().rev() {
+            let value = *byte as u32 * DICTIONARY.len() as u32 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(tr!("cs2.too_large"));
+        }
+    }
+    Ok(bytes)
+}
+
+/// Convertit les octets en texte `CSGO-xxxxx-xxxxx-xxxxx-xxxxx-xxxxx`
+fn bytes_to_code(mut bytes: [u8; BYTE_LENGTH]) -> String {
+    let mut digits = String::with_capacity(CODE_LENGTH);
+    for _ in 0..CODE_LENGTH {
+        // bytes = bytes / 57, le reste donne le caractère suivant
+        let mut remainder = 0u32;
+        for byte in bytes.iter_mut() {
+            let value = (remainder << 8) | *byte as u32;
+            *byte = (value / DICTIONARY.len() as u32) as u8;
+            remainder = value % DICTIONARY.len() as u32;
+        }
+        digits.push(DICTIONARY[remainder as usize] as char);
+    }
+
+    let groups: Vec<&str> = (0..CODE_LENGTH).step_by(5).map(|i| &digits[i..i + 5]).collect();
+    format!("{}-{}", PREFIX, groups.join("-"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Codes produits par cet encodeur à partir des réglages ci-dessous, et non
+    // relevés dans le jeu : ils figent la disposition des octets et l'alphabet,
+    // qui suivent le format décrit par la communauté (somme de contrôle, gap
+    // signé en dixièmes, drapeaux du style dans l'octet 13, longueur sur 13 bits).
+    fn fixtures() -> [(&'static str, Cs2Crosshair); 3] {
+        [
+            ("CSGO-7mtKo-CpA3U-25fUy-Kk2pV-Q3QqJ", Cs2Crosshair::default()),
+            (
+                "CSGO-8Npm2-TPpYJ-kocPJ-AoMcT-mVopQ",
+                Cs2Crosshair {
+                    length: 2.5,
+                    thickness: 1.0,
+                    gap: -2.2,
+                    outline_enabled: false,
+                    color: CUSTOM_COLOR,
+                    red: 0,
+                    green: 255,
+                    blue: 255,
+                    alpha_enabled: false,
+                    alpha: 255,
+                    center_dot: true,
+                    ..Cs2Crosshair::default()
+                },
+            ),
+            (
+                "CSGO-vWQc8-QMq6C-DKdnm-oHGMe-ekAUM",
+                Cs2Crosshair {
+                    style: STYLE_CLASSIC_DYNAMIC,
+                    length: 10.0,
+                    thickness: 0.6,
+                    gap: 0.0,
+                    outline: 2.0,
+                    color: 0,
+                    red: 250,
+                    green: 50,
+                    blue: 50,
+                    alpha: 255,
+                    t_style: true,
+                    split_distance: 3,
+                    follow_recoil: true,
+                    fixed_gap: -1.5,
+                    inner_split_alpha: 0.5,
+                    outer_split_alpha: 0.8,
+                    split_size_ratio: 0.3,
+                    deployed_weapon_gap: true,
+                    ..Cs2Crosshair::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn fixture_codes_decode_to_known_settings() {
+        for (code, expected) in fixtures() {
+            assert_eq!(Cs2Crosshair::decode(code).unwrap(), expected, "{}", code);
+            assert_eq!(expected.encode(), code);
+            // Sans tirets ni espaces autour, le code reste lisible
+            assert_eq!(Cs2Crosshair::decode(&format!(" {} ", code.replace('-', ""))).unwrap(), expected);
+        }
+    }
+
+    /// Code exporté par le client, exemple de la bibliothèque csgo-sharecode
+    /// (akiver), avec les réglages décodés qu'elle publie
+    #[test]
+    fn client_code_decodes_to_published_settings() {
+        let code = "CSGO-O4Jsi-V36wY-rTMGK-9w7qF-jQ8WB";
+        let expected = Cs2Crosshair {
+            style: STYLE_CLASSIC_DYNAMIC,
+            length: 33.0,
+            thickness: 4.1,
+            gap: 1.0,
+            outline_enabled: false,
+            outline: 1.5,
+            color: CUSTOM_COLOR,
+            red: 50,
+            green: 250,
+            blue: 84,
+            alpha_enabled: false,
+            alpha: 200,
+            center_dot: true,
+            t_style: true,
+            split_distance: 127,
+            follow_recoil: false,
+            fixed_gap: -10.0,
+            inner_split_alpha: 0.6,
+            outer_split_alpha: 0.8,
+            split_size_ratio: 0.3,
+            deployed_weapon_gap: false,
+        };
+        assert_eq!(Cs2Crosshair::decode(code).unwrap(), expected);
+        assert_eq!(expected.encode(), code);
+    }
+
+    #[test]
+    fn fixture_codes_convert_to_pixels() {
+        let [_, (_, custom), (_, dynamic)] = fixtures();
+
+        let conversion = custom.to_config(&CrosshairConfig::default());
+        let crosshair = &conversion.value.crosshair;
+        assert_eq!(crosshair.style, CrosshairStyle::Classic);
+        assert_eq!((crosshair.gap, crosshair.size, crosshair.thickness), (2, 7, 2));
+        assert_eq!(crosshair.color, Color::rgb(0, 255, 255));
+        assert_eq!(crosshair.alpha, 1.0);
+        assert!(crosshair.center_dot.enabled && !crosshair.outline.enabled);
+        assert!(!conversion.value.effects.mouse.gap_effect.enabled);
+        assert!(conversion.lossy.is_empty(), "{:?}", conversion.lossy);
+
+        let conversion = dynamic.to_config(&CrosshairConfig::default());
+        let crosshair = &conversion.value.crosshair;
+        assert_eq!(crosshair.style, CrosshairStyle::T);
+        assert_eq!((crosshair.gap, crosshair.size, crosshair.thickness), (4, 24, 1));
+        assert_eq!(crosshair.color, PRESET_COLORS[0]);
+        assert_eq!(crosshair.outline.thickness, 2);
+        assert!(conversion.value.effects.mouse.enabled && conversion.value.effects.mouse.gap_effect.enabled);
+        // Style dynamique, suivi du recul et gap selon l'arme
+        assert_eq!(conversion.lossy.len(), 3, "{:?}", conversion.lossy);
+    }
+
+    #[test]
+    fn malformed_codes_are_rejected() {
+        for code in [
+            "",
+            "XH1-7mtKo-CpA3U-25fUy-Kk2pV-Q3QqJ",
+            "CSGO-7mtKo-CpA3U-25fUy-Kk2pV",
+            "CSGO-7mtKo-CpA3U-25fUy-Kk2pV-Q3QqJA",
+            "CSGO-7mtKo-CpA3U-25fUy-Kk2pV-Q3Qq0",
+            // Plus grand que 18 octets
+            "CSGO-99999-99999-99999-99999-99999",
+        ] {
+            assert!(Cs2Crosshair::decode(code).is_err(), "{}", code);
+        }
+        // Un caractère modifié
+        assert_eq!(Cs2Crosshair::decode("CSGO-8mtKo-CpA3U-25fUy-Kk2pV-Q3QqJ"), Err(tr!("cs2.checksum")));
+    }
+}
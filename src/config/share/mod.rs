@@ -0,0 +1,70 @@
+// Codes de partage de crosshair : code de l'application et import/export depuis d'autres jeux
+pub mod code;
+pub mod cs2;
+pub mod valorant;
+
+/// Résultat d'une conversion entre formats de crosshair
+///
+/// `lossy` liste les réglages qui n'ont pas pu être représentés exactement
+/// (ignorés ou approximés), pour que l'utilisateur sache quoi vérifier.
+#[derive(Debug, Clone)]
+pub struct Conversion<T> {
+    pub value: T,
+    pub lossy: Vec<String>,
+}
+
+impl<T> Conversion<T> {
+    pub fn new(value: T) -> Self {
+        Self { value, lossy: Vec::new() }
+    }
+
+    /// Signale un réglage perdu ou approximé
+    pub fn lossy(&mut self, message: impl Into<String>) {
+        self.lossy.push(message.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::color::Color;
+    use crate::config::CrosshairConfig;
+    use super::cs2::Cs2Crosshair;
+    use super::valorant::ValorantCrosshair;
+    use super::code;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn decoders_never_panic(text in any::<String>(), payload in "[A-Za-z0-9_-]{0,64}") {
+            for input in [text.clone(), format!("{}{}-{}", code::PREFIX, code::VERSION, payload), format!("CSGO-{}", payload), format!("0;P;{}", text)] {
+                let _ = code::decode(&input);
+                let _ = Cs2Crosshair::decode(&input);
+                let _ = ValorantCrosshair::parse(&input);
+                let _ = input.parse::<Color>();
+            }
+        }
+
+        #[test]
+        fn share_code_round_trip(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let config = CrosshairConfig::from_arbitrary_bytes(&bytes);
+            let encoded = code::encode(&config).unwrap();
+            let decoded = code::decode(&encoded).unwrap();
+            prop_assert_eq!(code::encode(&decoded).unwrap(), encoded);
+        }
+
+        #[test]
+        fn game_codes_never_panic(bytes in prop::collection::vec(any::<u8>(), 0..512)) {
+            let config = CrosshairConfig::from_arbitrary_bytes(&bytes);
+
+            let cs2 = Cs2Crosshair::from_config(&config).value;
+            let decoded = Cs2Crosshair::decode(&cs2.encode());
+            prop_assert!(decoded.is_ok());
+            let _ = decoded.unwrap().to_config(&config);
+
+            let valorant = ValorantCrosshair::from_config(&config).value;
+            let parsed = ValorantCrosshair::parse(&valorant.serialize());
+            prop_assert!(parsed.is_ok());
+            let _ = parsed.unwrap().to_config(&config);
+        }
+    }
+}
@@ -0,0 +1,588 @@
+use std::fmt::Write as _;
+
+use crate::config::color::Color;
+use crate::config::effects::mouse::gap_effect::GapMode;
+use crate::config::CrosshairConfig;
+use crate::crosshair::CrosshairStyle;
+use crate::tr;
+
+use super::Conversion;
+
+/// Version du format de profil en tête de code
+const VERSION: &str = "0";
+
+// Couleurs prédéfinies de Valorant (c;0 à c;7, c;8 = couleur personnalisée `u`)
+const PRESET_COLORS: [Color; 8] = [
+    Color::rgb(255, 255, 255), // Blanc
+    Color::rgb(0, 255, 0),     // Vert
+    Color::rgb(127, 255, 0),   // Vert jaune
+    Color::rgb(223, 255, 0),   // Jaune vert
+    Color::rgb(255, 255, 0),   // Jaune
+    Color::rgb(0, 255, 255),   // Cyan
+    Color::rgb(255, 0, 255),   // Rose
+    Color::rgb(255, 0, 0),     // Rouge
+];
+const CUSTOM_COLOR: u8 = 8;
+
+/// Lignes intérieures (`0x`) ou extérieures (`1x`) d'un crosshair Valorant
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValorantLines {
+    pub show: bool,                   // b
+    pub thickness: u32,               // t
+    pub length: u32,                  // l
+    pub vertical_length: Option<u32>, // v (si g;1)
+    pub offset: u32,                  // o
+    pub opacity: f32,                 // a
+    pub movement_error: bool,         // m
+    pub firing_error: bool,           // f
+}
+
+impl ValorantLines {
+    /// Lignes intérieures par défaut
+    fn inner() -> Self {
+        Self {
+            show: true,
+            thickness: 2,
+            length: 6,
+            vertical_length: None,
+            offset: 3,
+            opacity: 0.8,
+            movement_error: false,
+            firing_error: true,
+        }
+    }
+
+    /// Lignes extérieures par défaut
+    fn outer() -> Self {
+        Self {
+            show: true,
+            thickness: 2,
+            length: 2,
+            vertical_length: None,
+            offset: 10,
+            opacity: 0.35,
+            movement_error: true,
+            firing_error: true,
+        }
+    }
+
+    /// Applique un paramètre ; retourne `false` si la clé n'a pas d'équivalent
+    fn set(&mut self, key: &str, value: &str) -> Result<bool, String> {
+        match key {
+            "b" => self.show = parse_flag(value)?,
+            "t" => self.thickness = parse_number(value)?,
+            "l" => self.length = parse_number(value)?,
+            "g" => {
+                if parse_flag(value)? {
+                    self.vertical_length.get_or_insert(self.length);
+                } else {
+                    self.vertical_length = None;
+                }
+            }
+            "v" => self.vertical_length = Some(parse_number(value)?),
+            "o" => self.offset = parse_number(value)?,
+            "a" => self.opacity = parse_opacity(value)?,
+            "m" => self.movement_error = parse_flag(value)?,
+            "f" => self.firing_error = parse_flag(value)?,
+            // Multiplicateurs d'erreur (s, e) : sans équivalent
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    /// Écrit les paramètres différents des valeurs par défaut
+    fn write(&self, output: &mut String, prefix: char, default: &Self) {
+        let mut field = |key: &str, value: String| {
+            let _ = write!(output, ";{}{};{}", prefix, key, value);
+        };
+        if self.thickness != default.thickness {
+            field("t", self.thickness.to_string());
+        }
+        if self.length != default.length {
+            field("l", self.length.to_string());
+        }
+        if let Some(vertical) = self.vertical_length {
+            field("v", vertical.to_string());
+            field("g", "1".to_string());
+        }
+        if self.offset != default.offset {
+            field("o", self.offset.to_string());
+        }
+        if self.opacity != default.opacity {
+            field("a", format_opacity(self.opacity));
+        }
+        if self.movement_error != default.movement_error {
+            field("m", flag(self.movement_error));
+        }
+        if self.firing_error != default.firing_error {
+            field("f", flag(self.firing_error));
+        }
+        if self.show != default.show {
+            field("b", flag(self.show));
+        }
+    }
+}
+
+/// Réglages du crosshair principal (`P`) d'un profil Valorant
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValorantCrosshair {
+    pub color: Color,            // c / u
+    pub outlines: bool,          // h
+    pub outline_thickness: u32,  // t
+    pub outline_opacity: f32,    // o
+    pub center_dot: bool,        // d
+    pub center_dot_thickness: u32, // z
+    pub center_dot_opacity: f32, // a
+    pub inner: ValorantLines,    // 0x
+    pub outer: ValorantLines,    // 1x
+    pub ignored: Vec<String>,    // Paramètres et sections sans équivalent
+}
+
+impl Default for ValorantCrosshair {
+    /// Crosshair par défaut de Valorant
+    fn default() -> Self {
+        Self {
+            color: PRESET_COLORS[0],
+            outlines: true,
+            outline_thickness: 1,
+            outline_opacity: 0.5,
+            center_dot: false,
+            center_dot_thickness: 2,
+            center_dot_opacity: 1.0,
+            inner: ValorantLines::inner(),
+            outer: ValorantLines::outer(),
+            ignored: Vec::new(),
+        }
+    }
+}
+
+/// Section du code en cours de lecture
+#[derive(PartialEq)]
+enum Section {
+    General, // Paramètres communs (s, c...)
+    Primary, // P
+    Ads,     // A
+    Sniper,  // S
+}
+
+impl ValorantCrosshair {
+    /// Lit un code de profil (`0;P;c;5;o;1;d;1;z;3;0b;0;1b;0`)
+    ///
+    /// Seul le crosshair principal est importé ; les sections de visée (A) et
+    /// de lunette (S) sont ignorées et signalées.
+    pub fn parse(code: &str) -> Result<Self, String> {
+        let mut tokens = code.trim().split(';').map(str::trim);
+        if tokens.next() != Some(VERSION) {
+            return Err(tr!("valorant.prefix", version = VERSION));
+        }
+
+        let mut crosshair = Self::default();
+        let mut section = Section::General;
+        let mut custom_color = None;
+        let mut use_custom_color = false;
+
+        while let Some(key) = tokens.next() {
+            match key {
+                "" => continue,
+                "P" => {
+                    section = Section::Primary;
+                    continue;
+                }
+                "A" | "S" => {
+                    section = if key == "A" { Section::Ads } else { Section::Sniper };
+                    let name = if key == "A" { tr!("valorant.section_ads") } else { tr!("valorant.section_sniper") };
+                    crosshair.ignored.push(tr!("valorant.section_ignored", section = key, name = name));
+                    continue;
+                }
+                _ => {}
+            }
+
+            let value = tokens
+                .next()
+                .ok_or_else(|| tr!("valorant.missing_value", key = key))?;
+            if section != Section::Primary {
+                if section == Section::General {
+                    crosshair.ignored.push(tr!("valorant.general_ignored", key = key));
+                }
+                continue;
+            }
+
+            let invalid = |e: String| tr!("valorant.invalid", key = key, value = value, error = e);
+            match key {
+                "c" => {
+                    let index: u8 = parse_number(value).map_err(invalid)?;
+                    use_custom_color = index == CUSTOM_COLOR;
+                    if let Some(color) = PRESET_COLORS.get(index as usize) {
+                        crosshair.color = *color;
+                    } else if !use_custom_color {
+                        return Err(invalid(tr!("valorant.unknown_color")));
+                    }
+                }
+                "u" => custom_color = Some(format!("#{}", value).parse::<Color>().map_err(invalid)?),
+                "h" => crosshair.outlines = parse_flag(value).map_err(invalid)?,
+                "t" => crosshair.outline_thickness = parse_number(value).map_err(invalid)?,
+                "o" => crosshair.outline_opacity = parse_opacity(value).map_err(invalid)?,
+                "d" => crosshair.center_dot = parse_flag(value).map_err(invalid)?,
+                "z" => crosshair.center_dot_thickness = parse_number(value).map_err(invalid)?,
+                "a" => crosshair.center_dot_opacity = parse_opacity(value).map_err(invalid)?,
+                _ => {
+                    // Lignes intérieures (0t, 0l...) ou extérieures (1t, 1l...)
+                    let lines = match key.split_at_checked(1) {
+                        Some(("0", field)) => Some((&mut crosshair.inner, field)),
+                        Some(("1", field)) => Some((&mut crosshair.outer, field)),
+                        _ => None,
+                    };
+                    let known = match lines {
+                        Some((lines, field)) => lines.set(field, value).map_err(invalid)?,
+                        None => false,
+                    };
+                    if !known {
+                        crosshair.ignored.push(tr!("valorant.setting_ignored", key = key));
+                    }
+                }
+            }
+        }
+
+        if use_custom_color {
+            crosshair.color = custom_color.ok_or_else(|| tr!("valorant.custom_color_missing"))?;
+        }
+        Ok(crosshair)
+    }
+
+    /// Écrit le code de profil (seules les valeurs différentes du défaut sont écrites)
+    pub fn serialize(&self) -> String {
+        let default = Self::default();
+        let mut code = format!("{};P", VERSION);
+        let mut field = |key: &str, value: String| {
+            let _ = write!(code, ";{};{}", key, value);
+        };
+
+        match PRESET_COLORS.iter().position(|preset| *preset == self.color) {
+            Some(0) => {}
+            Some(index) => field("c", index.to_string()),
+            None => {
+                field("c", CUSTOM_COLOR.to_string());
+                let Color { r, g, b, a } = self.color;
+                field("u", format!("{:02X}{:02X}{:02X}{:02X}", r, g, b, a));
+            }
+        }
+        if self.outlines != default.outlines {
+            field("h", flag(self.outlines));
+        }
+        if self.outline_thickness != default.outline_thickness {
+            field("t", self.outline_thickness.to_string());
+        }
+        if self.outline_opacity != default.outline_opacity {
+            field("o", format_opacity(self.outline_opacity));
+        }
+        if self.center_dot != default.center_dot {
+            field("d", flag(self.center_dot));
+        }
+        if self.center_dot_thickness != default.center_dot_thickness {
+            field("z", self.center_dot_thickness.to_string());
+        }
+        if self.center_dot_opacity != default.center_dot_opacity {
+            field("a", format_opacity(self.center_dot_opacity));
+        }
+
+        self.inner.write(&mut code, '0', &default.inner);
+        self.outer.write(&mut code, '1', &default.outer);
+        code
+    }
+
+    /// Convertit le profil en configuration
+    ///
+    /// Notre crosshair n'a qu'un jeu de lignes : les lignes intérieures sont
+    /// dessinées si elles sont affichées, sinon les lignes extérieures. Les
+    /// lignes extérieures écartées sont signalées avec leurs réglages.
+    pub fn to_config(&self, base: &CrosshairConfig) -> Conversion<CrosshairConfig> {
+        let mut conversion = Conversion::new(base.clone());
+        conversion.lossy.extend(self.ignored.iter().cloned());
+        self.apply_common(&mut conversion.value);
+
+        match (self.inner.show, self.outer.show) {
+            (true, outer) => {
+                self.apply_lines(&mut conversion.value, &self.inner, "inner", &mut conversion.lossy);
+                if outer {
+                    let ValorantLines { offset, length, thickness, .. } = self.outer;
+                    conversion.lossy(tr!("valorant.outer_lines", offset = offset, length = length, thickness = thickness));
+                }
+            }
+            (false, true) => self.apply_lines(&mut conversion.value, &self.outer, "outer", &mut conversion.lossy),
+            // Point central seul (ou crosshair vide)
+            (false, false) => conversion.value.crosshair.style = CrosshairStyle::Dot,
+        }
+        conversion
+    }
+
+    /// Couleur, outline et point central
+    fn apply_common(&self, config: &mut CrosshairConfig) {
+        let crosshair = &mut config.crosshair;
+        crosshair.color = self.color;
+        crosshair.rotation = 0.0;
+        crosshair.triangle_bars = false;
+
+        crosshair.outline.enabled = self.outlines;
+        crosshair.outline.thickness = self.outline_thickness.max(1);
+        crosshair.outline.color = Color::BLACK;
+        crosshair.outline.alpha = self.outline_opacity;
+
+        crosshair.center_dot.enabled = self.center_dot;
+        crosshair.center_dot.size = self.center_dot_thickness.max(1);
+        crosshair.center_dot.color = self.color;
+        crosshair.center_dot.alpha = self.center_dot_opacity;
+
+        config.effects.mouse.gap_effect.enabled = false;
+    }
+
+    fn apply_lines(&self, config: &mut CrosshairConfig, lines: &ValorantLines, name: &str, lossy: &mut Vec<String>) {
+        let crosshair = &mut config.crosshair;
+        crosshair.style = CrosshairStyle::Classic;
+        crosshair.thickness = lines.thickness.max(1);
+        crosshair.gap = lines.offset;
+        crosshair.size = lines.offset + lines.length;
+        crosshair.alpha = lines.opacity;
+
+        if lines.vertical_length.is_some_and(|vertical| vertical != lines.length) {
+            lossy.push(tr!("valorant.vertical_length", lines = name));
+        }
+        if lines.movement_error {
+            lossy.push(tr!("valorant.movement_error", lines = name));
+        }
+        // L'erreur de tir écarte les lignes : approximée par l'effet de gap au clic
+        if lines.firing_error {
+            config.effects.mouse.enabled = true;
+            let gap_effect = &mut config.effects.mouse.gap_effect;
+            gap_effect.enabled = true;
+            gap_effect.mode = GapMode::Expand;
+            lossy.push(tr!("valorant.firing_error", lines = name));
+        }
+    }
+
+    /// Convertit une configuration en profil Valorant (lignes intérieures uniquement)
+    pub fn from_config(config: &CrosshairConfig) -> Conversion<Self> {
+        let crosshair = &config.crosshair;
+        let effects = &config.effects;
+        let mut lossy = Vec::new();
+
+        let mut value = Self::default();
+        let color = crosshair.color;
+        value.color = Color::rgb(color.r, color.g, color.b);
+        if color.a != 255 {
+            lossy.push(tr!("valorant.color_transparency"));
+        }
+
+        value.outlines = crosshair.outline.enabled;
+        value.outline_thickness = crosshair.outline.thickness;
+        value.outline_opacity = round_opacity(crosshair.outline.alpha);
+        if crosshair.outline.enabled && crosshair.outline.color != Color::BLACK {
+            lossy.push(tr!("share.outline_color", color = crosshair.outline.color, game = "Valorant"));
+        }
+
+        value.center_dot = crosshair.center_dot.enabled;
+        value.center_dot_thickness = crosshair.center_dot.size;
+        value.center_dot_opacity = round_opacity(crosshair.center_dot.alpha);
+        if crosshair.center_dot.enabled && crosshair.center_dot.color != crosshair.color {
+            lossy.push(tr!("share.center_dot_color", game = "Valorant"));
+        }
+
+        let gap_effect = effects.mouse.enabled && effects.mouse.gap_effect.enabled;
+        value.inner = ValorantLines {
+            show: true,
+            thickness: crosshair.thickness,
+            length: crosshair.size.saturating_sub(crosshair.gap),
+            vertical_length: None,
+            offset: crosshair.gap,
+            opacity: round_opacity(crosshair.alpha),
+            movement_error: false,
+            firing_error: gap_effect,
+        };
+        value.outer.show = false;
+
+        match crosshair.style {
+            CrosshairStyle::Classic => {}
+            CrosshairStyle::Dot => {
+                value.inner.show = false;
+                value.center_dot = true;
+            }
+            ref other => lossy.push(tr!("share.style_unavailable", style = format!("{:?}", other), game = "Valorant")),
+        }
+        if crosshair.rotation != 0.0 {
+            lossy.push(tr!("share.rotation", rotation = crosshair.rotation));
+        }
+        if crosshair.triangle_bars {
+            lossy.push(tr!("share.triangle_bars"));
+        }
+        if gap_effect {
+            lossy.push(tr!("valorant.gap_effect"));
+        }
+        for (enabled, name) in [
+            (effects.pulse.enabled, "pulse"),
+            (effects.shake.enabled, "shake"),
+            (effects.rainbow.enabled, "rainbow"),
+            (effects.mouse.enabled && effects.mouse.visibility_effect.enabled, "visibility_effect"),
+        ] {
+            if enabled {
+                lossy.push(tr!("share.effect_ignored", effect = name));
+            }
+        }
+
+        Conversion { value, lossy }
+    }
+}
+
+fn parse_flag(value: &str) -> Result<bool, String> {
+    match value {
+        "0" => Ok(false),
+        "1" => Ok(true),
+        _ => Err(tr!("valorant.flag_expected")),
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| tr!("valorant.integer_expected"))
+}
+
+fn parse_opacity(value: &str) -> Result<f32, String> {
+    value
+        .parse::<f32>()
+        .ok()
+        .filter(|opacity| (0.0..=1.0).contains(opacity))
+        .ok_or_else(|| tr!("valorant.opacity_expected"))
+}
+
+/// Valorant n'accepte que trois décimales
+fn round_opacity(opacity: f32) -> f32 {
+    // NaN n'a pas d'équivalent : opacité maximale
+    let opacity = if opacity.is_nan() { 1.0 } else { opacity.clamp(0.0, 1.0) };
+    (opacity * 1000.0).round() / 1000.0
+}
+
+fn format_opacity(opacity: f32) -> String {
+    let text = format!("{:.3}", opacity);
+    text.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn flag(value: bool) -> String {
+    (value as u8).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_codes_round_trip() {
+        let custom = ValorantCrosshair {
+            color: Color::rgba(255, 136, 0, 204),
+            outlines: false,
+            center_dot: true,
+            center_dot_thickness: 3,
+            inner: ValorantLines {
+                length: 4,
+                vertical_length: Some(8),
+                offset: 2,
+                opacity: 1.0,
+                firing_error: false,
+                ..ValorantLines::inner()
+            },
+            outer: ValorantLines { show: false, ..ValorantLines::outer() },
+            ..ValorantCrosshair::default()
+        };
+        let split = ValorantCrosshair {
+            color: PRESET_COLORS[5],
+            outline_thickness: 2,
+            outline_opacity: 1.0,
+            inner: ValorantLines { thickness: 1, length: 2, offset: 1, ..ValorantLines::inner() },
+            outer: ValorantLines {
+                thickness: 3,
+                length: 3,
+                offset: 6,
+                opacity: 0.5,
+                movement_error: false,
+                ..ValorantLines::outer()
+            },
+            ..ValorantCrosshair::default()
+        };
+
+        for (code, expected) in [
+            ("0;P", ValorantCrosshair::default()),
+            ("0;P;c;8;u;FF8800CC;h;0;d;1;z;3;0l;4;0v;8;0g;1;0o;2;0a;1;0f;0;1b;0", custom),
+            ("0;P;c;5;t;2;o;1;0t;1;0l;2;0o;1;1t;3;1l;3;1o;6;1a;0.5;1m;0", split),
+        ] {
+            let parsed = ValorantCrosshair::parse(code).unwrap();
+            assert_eq!(parsed, expected, "{}", code);
+            assert_eq!(parsed.serialize(), code);
+        }
+    }
+
+    #[test]
+    fn toggles_follow_the_last_value() {
+        // g;1 reprend la longueur horizontale si v est absent, g;0 annule v
+        let parsed = ValorantCrosshair::parse("0;P;0l;5;0g;1").unwrap();
+        assert_eq!(parsed.inner.vertical_length, Some(5));
+        let parsed = ValorantCrosshair::parse("0;P;0v;8;0g;0").unwrap();
+        assert_eq!(parsed.inner.vertical_length, None);
+        let parsed = ValorantCrosshair::parse("0;P;0b;0;0b;1;1b;1;1b;0").unwrap();
+        assert!(parsed.inner.show && !parsed.outer.show);
+    }
+
+    #[test]
+    fn game_codes_keep_only_the_primary_crosshair() {
+        let canonical = "0;P;c;5;h;0;0l;4;0o;2;0a;1;0f;0;1b;0";
+        for (code, ignored) in [
+            (
+                "0;P;c;5;h;0;f;0;0l;4;0o;2;0a;1;0f;0;1b;0",
+                vec![tr!("valorant.setting_ignored", key = "f")],
+            ),
+            (
+                "0;s;1;P;c;5;h;0;m;1;0l;4;0o;2;0a;1;0f;0;1b;0;S;c;4;o;1",
+                vec![
+                    tr!("valorant.general_ignored", key = "s"),
+                    tr!("valorant.setting_ignored", key = "m"),
+                    tr!("valorant.section_ignored", section = "S", name = tr!("valorant.section_sniper")),
+                ],
+            ),
+            (
+                "0;P;c;5;h;0;0l;4;0o;2;0a;1;0f;0;1b;0;A;c;1;0l;10",
+                vec![tr!("valorant.section_ignored", section = "A", name = tr!("valorant.section_ads"))],
+            ),
+        ] {
+            let parsed = ValorantCrosshair::parse(code).unwrap();
+            assert_eq!(parsed.ignored, ignored, "{}", code);
+            assert_eq!(parsed.color, PRESET_COLORS[5]);
+            assert_eq!(parsed.serialize(), canonical);
+        }
+    }
+
+    #[test]
+    fn inner_lines_are_drawn_and_outer_lines_reported() {
+        let parsed = ValorantCrosshair::parse("0;P;c;5;d;1;0t;1;0l;2;0o;1;0f;0;1t;3;1l;3;1o;6;1a;0.5;1m;0;1f;0").unwrap();
+        let conversion = parsed.to_config(&CrosshairConfig::default());
+        let crosshair = &conversion.value.crosshair;
+        assert_eq!(crosshair.style, CrosshairStyle::Classic);
+        assert_eq!((crosshair.gap, crosshair.size, crosshair.thickness, crosshair.alpha), (1, 3, 1, 0.8));
+        assert!(crosshair.center_dot.enabled);
+        assert_eq!(conversion.lossy, [tr!("valorant.outer_lines", offset = 6, length = 3, thickness = 3)]);
+
+        // Lignes extérieures seules : ce sont elles qui sont dessinées
+        let parsed = ValorantCrosshair::parse("0;P;c;5;0b;0;1t;3;1l;3;1o;6;1a;0.5;1m;0;1f;0").unwrap();
+        let conversion = parsed.to_config(&CrosshairConfig::default());
+        let crosshair = &conversion.value.crosshair;
+        assert_eq!((crosshair.gap, crosshair.size, crosshair.thickness, crosshair.alpha), (6, 9, 3, 0.5));
+        assert!(conversion.lossy.is_empty(), "{:?}", conversion.lossy);
+
+        // Sans lignes, seul le point central reste
+        let parsed = ValorantCrosshair::parse("0;P;d;1;0b;0;1b;0").unwrap();
+        let conversion = parsed.to_config(&CrosshairConfig::default());
+        assert_eq!(conversion.value.crosshair.style, CrosshairStyle::Dot);
+        assert!(conversion.value.crosshair.center_dot.enabled);
+    }
+
+    #[test]
+    fn invalid_codes_are_rejected() {
+        for code in ["", "1;P", "0;P;c;9", "0;P;c;8", "0;P;u;XYZ;c;8", "0;P;h;2", "0;P;0a;1.5", "0;P;0l", "0;P;0t;-1"] {
+            assert!(ValorantCrosshair::parse(code).is_err(), "{}", code);
+        }
+    }
+}
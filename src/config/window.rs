@@ -1,37 +1,104 @@
-use serde::{Deserialize, Serialize};
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::borrow::Cow;
+use std::fmt;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+use super::color::Color;
+use crate::tr;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct WindowConfig {
+    #[schemars(description = "Window size in pixels (the crosshair is drawn at its center)", range(min = 1))]
     pub size: u32,
+    #[schemars(description = "Size the window to fit the crosshair and its effects (size is then ignored)")]
+    pub auto_size: bool,        // Plus petit carré contenant le crosshair
+    #[schemars(description = "Monitor showing the overlay: \"primary\", \"cursor\" (monitor containing the mouse cursor), a number (1 = first) or a name")]
+    pub monitor: MonitorSelection,
+    #[schemars(description = "Window position on the monitor")]
     pub position: Position,
+    #[schemars(description = "Global window opacity", range(min = 0.0, max = 1.0))]
     pub opacity: f32,           // Opacité globale de la fenêtre 0.0-1.0
+    #[schemars(description = "Colored window background")]
     pub background: Background,
+    #[schemars(description = "Display backend: \"winit\" (regular window, default), \"auto\" (layer-shell on Wayland, winit elsewhere or when unavailable), \"layer-shell\" or \"x11\"")]
+    pub backend: Backend,       // Lu au démarrage seulement
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct Position {
+    #[schemars(description = "Place the aim point on the anchor (false: the window's top-left corner goes at x / y from the monitor's top-left corner)")]
     pub center_screen: bool,
-    pub x: Option<i32>,
-    pub y: Option<i32>,
+    #[schemars(description = "Point of the monitor the aim point is placed on")]
+    pub anchor: Anchor,         // Point de l'écran servant de référence
+    #[schemars(description = "Horizontal offset from the anchor: logical pixels or percent of the monitor width (\"2.5%\"), positive to the right", example = 100)]
+    pub x: Option<Offset>,
+    #[schemars(description = "Vertical offset from the anchor: logical pixels or percent of the monitor height (\"5%\"), positive downwards", example = &"5%")]
+    pub y: Option<Offset>,
+    #[schemars(description = "Horizontal position of the aim point inside the window, from its center: logical pixels or percent of the window size")]
+    pub aim_x: Offset,          // Point de la fenêtre placé sur l'ancre
+    #[schemars(description = "Vertical position of the aim point inside the window, from its center: logical pixels or percent of the window size")]
+    pub aim_y: Offset,
+    #[schemars(description = "Follow the mouse cursor (Windows only: it needs the global mouse capture)")]
     pub follow_cursor: bool,    // Suivre le curseur de la souris
+    #[schemars(description = "Horizontal offset from the cursor in logical pixels")]
     pub offset_x: i32,          // Décalage par rapport au curseur
+    #[schemars(description = "Vertical offset from the cursor in logical pixels")]
     pub offset_y: i32,          // Décalage par rapport au curseur
+    #[schemars(description = "Smoothing of the cursor following (0 = immediate, closer to 1 = slower)", range(min = 0.0, max = 0.99))]
+    pub smoothing: f32,         // Lissage du suivi du curseur 0.0-0.99
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct Background {
+    #[schemars(description = "Fill the window with a background color")]
     pub enabled: bool,
-    pub color: String,          // Couleur de fond
+    #[schemars(description = "Background color (same syntax as crosshair.color)")]
+    pub color: Color,           // Couleur de fond
+    #[schemars(description = "Background transparency", range(min = 0.0, max = 1.0))]
     pub alpha: f32,             // Transparence du fond 0.0-1.0
 }
 
+/// Ce qui change pour la fenêtre entre deux configurations
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WindowChanges {
+    pub geometry: bool,   // Taille, écran ou position : replacer la fenêtre
+    pub appearance: bool, // Opacité ou fond : redessiner
+}
+
+impl WindowChanges {
+    pub fn any(&self) -> bool {
+        self.geometry || self.appearance
+    }
+}
+
+impl WindowConfig {
+    /// Changements à appliquer à la fenêtre pour passer de `previous` à cette configuration
+    pub fn changes_from(&self, previous: &WindowConfig) -> WindowChanges {
+        WindowChanges {
+            geometry: self.size != previous.size
+                || self.auto_size != previous.auto_size
+                || self.monitor != previous.monitor || self.position != previous.position,
+            appearance: self.opacity != previous.opacity || self.background != previous.background,
+        }
+    }
+}
+
 impl Default for WindowConfig {
     fn default() -> Self {
         Self {
             size: 100,
+            auto_size: false,
+            monitor: MonitorSelection::default(),
             position: Position::default(),
             opacity: 1.0,
             background: Background::default(),
+            backend: Backend::default(),
         }
     }
 }
@@ -40,11 +107,15 @@ impl Default for Position {
     fn default() -> Self {
         Self {
             center_screen: true,
+            anchor: Anchor::Center,
             x: None,
             y: None,
+            aim_x: Offset::default(),
+            aim_y: Offset::default(),
             follow_cursor: false,
             offset_x: 0,
             offset_y: 0,
+            smoothing: 0.0,
         }
     }
 }
@@ -53,8 +124,365 @@ impl Default for Background {
     fn default() -> Self {
         Self {
             enabled: false,
-            color: "#000000".to_string(),  // Noir
+            color: Color::BLACK,
             alpha: 0.1,
         }
     }
-} 
\ No newline at end of file
+}
+
+/// Système d'affichage de l'overlay
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub enum Backend {
+    /// Layer-shell sous Wayland s'il est disponible, sinon winit
+    Auto,
+    /// Fenêtre winit (toutes plateformes)
+    ///
+    /// Par défaut : c'est le seul backend qui gère les touches de la fenêtre,
+    /// le suivi du curseur et les effets de souris.
+    #[default]
+    Winit,
+    /// Surface wlr-layer-shell (Wayland : Sway, KDE, Hyprland...)
+    LayerShell,
+    /// Fenêtre X11 override-redirect, hors du gestionnaire de fenêtres
+    X11,
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Auto => "auto",
+            Self::Winit => "winit",
+            Self::LayerShell => "layer-shell",
+            Self::X11 => "x11",
+        })
+    }
+}
+
+/// Point de l'écran servant de référence au placement
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "kebab-case")]
+pub enum Anchor {
+    #[default]
+    Center,
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+impl Anchor {
+    /// Position de l'ancre en fraction de la largeur et de la hauteur de l'écran
+    pub fn fractions(self) -> (f64, f64) {
+        match self {
+            Self::TopLeft => (0.0, 0.0),
+            Self::Top => (0.5, 0.0),
+            Self::TopRight => (1.0, 0.0),
+            Self::Left => (0.0, 0.5),
+            Self::Center => (0.5, 0.5),
+            Self::Right => (1.0, 0.5),
+            Self::BottomLeft => (0.0, 1.0),
+            Self::Bottom => (0.5, 1.0),
+            Self::BottomRight => (1.0, 1.0),
+        }
+    }
+}
+
+/// Décalage en pixels logiques (`25`) ou en pourcentage d'une longueur de référence (`"2.5%"`)
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub enum Offset {
+    Pixels(i32),
+    Percent(f32),
+}
+
+impl Default for Offset {
+    fn default() -> Self {
+        Self::Pixels(0)
+    }
+}
+
+impl Offset {
+    /// Décalage en pixels physiques, `reference` étant la longueur physique des pourcentages
+    pub fn to_physical(self, reference: f64, scale_factor: f64) -> f64 {
+        let pixels = match self {
+            Self::Pixels(pixels) => f64::from(pixels) * scale_factor,
+            Self::Percent(percent) => f64::from(percent) / 100.0 * reference,
+        };
+        if pixels.is_finite() { pixels } else { 0.0 }
+    }
+}
+
+impl std::str::FromStr for Offset {
+    type Err = String;
+
+    /// Accepte `25`, `25px` et `2.5%`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let text = s.trim();
+        let invalid = || tr!("window.invalid_offset", value = s);
+        if let Some(percent) = text.strip_suffix('%') {
+            percent.trim().parse().map(Self::Percent).map_err(|_| invalid())
+        } else {
+            text.strip_suffix("px").unwrap_or(text).trim().parse().map(Self::Pixels).map_err(|_| invalid())
+        }
+    }
+}
+
+impl fmt::Display for Offset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pixels(pixels) => write!(f, "{}", pixels),
+            Self::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+/// Forme binaire (codes de partage)
+#[derive(Serialize, Deserialize)]
+enum OffsetBinary {
+    Pixels(i32),
+    Percent(f32),
+}
+
+/// Forme texte : un nombre de pixels ou un texte (`"2.5%"`)
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum OffsetText {
+    Pixels(i32),
+    Text(String),
+}
+
+impl Serialize for Offset {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            match self {
+                Self::Pixels(pixels) => OffsetText::Pixels(*pixels),
+                percent => OffsetText::Text(percent.to_string()),
+            }
+            .serialize(serializer)
+        } else {
+            match *self {
+                Self::Pixels(pixels) => OffsetBinary::Pixels(pixels),
+                Self::Percent(percent) => OffsetBinary::Percent(percent),
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Offset {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            match OffsetText::deserialize(deserializer)? {
+                OffsetText::Pixels(pixels) => Ok(Self::Pixels(pixels)),
+                OffsetText::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            }
+        } else {
+            Ok(match OffsetBinary::deserialize(deserializer)? {
+                OffsetBinary::Pixels(pixels) => Self::Pixels(pixels),
+                OffsetBinary::Percent(percent) => Self::Percent(percent),
+            })
+        }
+    }
+}
+
+/// Dans le schéma JSON : des pixels (entier) ou un pourcentage (texte)
+impl JsonSchema for Offset {
+    fn schema_name() -> Cow<'static, str> {
+        "Offset".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "Logical pixels (25) or a percentage (\"2.5%\")",
+            "anyOf": [
+                { "type": "integer" },
+                { "type": "string", "pattern": "^\\s*-?[0-9]+(\\.[0-9]+)?\\s*(%|px)?\\s*$" }
+            ],
+            "examples": [25, "2.5%"]
+        })
+    }
+}
+
+/// Écran sur lequel placer l'overlay
+///
+/// Dans la configuration : `"primary"`, `"cursor"` (écran contenant le
+/// curseur), un numéro (`1` = premier écran) ou le nom de l'écran.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum MonitorSelection {
+    #[default]
+    Primary,
+    Cursor,
+    Index(u32),
+    Name(String),
+}
+
+impl MonitorSelection {
+    fn from_text(text: &str) -> Self {
+        match text.trim().to_lowercase().as_str() {
+            "primary" => Self::Primary,
+            "cursor" => Self::Cursor,
+            number => number.parse().map_or_else(|_| Self::Name(text.to_string()), Self::Index),
+        }
+    }
+}
+
+impl fmt::Display for MonitorSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Primary => write!(f, "primary"),
+            Self::Cursor => write!(f, "cursor"),
+            Self::Index(index) => write!(f, "{}", index),
+            Self::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// Un nom qui ressemble à un numéro ou à un mot-clé ne peut pas être écrit
+/// dans un fichier : les noms générés passent par la même lecture
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a> arbitrary::Arbitrary<'a> for MonitorSelection {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => Self::Primary,
+            1 => Self::Cursor,
+            2 => Self::Index(u.arbitrary()?),
+            _ => Self::from_text(u.arbitrary()?),
+        })
+    }
+}
+
+/// Forme binaire (codes de partage)
+#[derive(Serialize, Deserialize)]
+enum MonitorBinary {
+    Primary,
+    Cursor,
+    Index(u32),
+    Name(String),
+}
+
+/// Forme texte : un numéro ou un texte
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum MonitorText {
+    Index(u32),
+    Text(String),
+}
+
+impl Serialize for MonitorSelection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            match self {
+                Self::Index(index) => MonitorText::Index(*index),
+                other => MonitorText::Text(other.to_string()),
+            }
+            .serialize(serializer)
+        } else {
+            match self.clone() {
+                Self::Primary => MonitorBinary::Primary,
+                Self::Cursor => MonitorBinary::Cursor,
+                Self::Index(index) => MonitorBinary::Index(index),
+                Self::Name(name) => MonitorBinary::Name(name),
+            }
+            .serialize(serializer)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MonitorSelection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            Ok(match MonitorText::deserialize(deserializer)? {
+                MonitorText::Index(index) => Self::Index(index),
+                MonitorText::Text(text) => Self::from_text(&text),
+            })
+        } else {
+            Ok(match MonitorBinary::deserialize(deserializer)? {
+                MonitorBinary::Primary => Self::Primary,
+                MonitorBinary::Cursor => Self::Cursor,
+                MonitorBinary::Index(index) => Self::Index(index),
+                MonitorBinary::Name(name) => Self::Name(name),
+            })
+        }
+    }
+}
+
+/// Dans le schéma JSON : un numéro d'écran ou un texte
+impl JsonSchema for MonitorSelection {
+    fn schema_name() -> Cow<'static, str> {
+        "MonitorSelection".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "description": "\"primary\", \"cursor\" (monitor containing the mouse cursor), a monitor number (1 = first) or a monitor name",
+            "anyOf": [
+                { "type": "integer", "minimum": 1 },
+                { "type": "string" }
+            ],
+            "examples": ["primary", "cursor", 2, "DELL U2720Q"]
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn monitor_selection_accepts_keywords_numbers_and_names() {
+        for (text, expected) in [
+            ("monitor = \"primary\"", MonitorSelection::Primary),
+            ("monitor = \"Cursor\"", MonitorSelection::Cursor),
+            ("monitor = 2", MonitorSelection::Index(2)),
+            ("monitor = \"2\"", MonitorSelection::Index(2)),
+            ("monitor = \"DELL U2720Q\"", MonitorSelection::Name("DELL U2720Q".to_string())),
+        ] {
+            let window: WindowConfig = toml::from_str(text).unwrap();
+            assert_eq!(window.monitor, expected, "{}", text);
+            let written = toml::to_string(&window).unwrap();
+            assert_eq!(toml::from_str::<WindowConfig>(&written).unwrap().monitor, expected);
+        }
+    }
+
+    #[test]
+    fn offsets_accept_pixels_and_percentages() {
+        for (text, expected) in [
+            ("x = 25", Offset::Pixels(25)),
+            ("x = \"-25px\"", Offset::Pixels(-25)),
+            ("x = \"2.5%\"", Offset::Percent(2.5)),
+            ("x = \" -10 % \"", Offset::Percent(-10.0)),
+        ] {
+            let position: Position = toml::from_str(text).unwrap();
+            assert_eq!(position.x, Some(expected), "{}", text);
+            let written = toml::to_string(&position).unwrap();
+            assert_eq!(toml::from_str::<Position>(&written).unwrap().x, Some(expected));
+        }
+        assert!(toml::from_str::<Position>("x = \"half\"").is_err());
+        assert!(toml::from_str::<Position>("anchor = \"bottom-right\"").is_ok_and(|p| p.anchor == Anchor::BottomRight));
+    }
+
+    #[test]
+    fn window_changes_separate_geometry_from_appearance() {
+        let previous = WindowConfig::default();
+        assert!(!previous.changes_from(&previous).any());
+
+        let mut window = previous.clone();
+        window.size = 200;
+        assert_eq!(window.changes_from(&previous), WindowChanges { geometry: true, appearance: false });
+
+        let mut window = previous.clone();
+        window.position.y = Some(Offset::Percent(5.0));
+        assert!(window.changes_from(&previous).geometry);
+
+        let mut window = previous.clone();
+        window.background.enabled = true;
+        assert_eq!(window.changes_from(&previous), WindowChanges { geometry: false, appearance: true });
+    }
+}
@@ -0,0 +1,339 @@
+//! État de l'overlay indépendant du système d'affichage
+//!
+//! Configuration, profils, raccourcis globaux et règles `[focus]` : la fenêtre
+//! winit comme les backends natifs (layer-shell, X11) transmettent leurs
+//! événements au [`Controller`] et appliquent les [`Changes`] qu'il retourne.
+
+use std::sync::{Arc, Mutex};
+
+use crosshair_overlay::cli::Overrides;
+use crosshair_overlay::config::focus::FocusedWindow;
+use crosshair_overlay::config::hotkeys::HotkeyAction;
+use crosshair_overlay::config::layers::{self, ConfigLayers};
+use crosshair_overlay::config::{ConfigLocation, CrosshairConfig, ProfileManager};
+use crosshair_overlay::crosshair::CrosshairStyle;
+use crosshair_overlay::{i18n, tr};
+
+use crate::focus::{self, FocusManager};
+use crate::hotkeys::{self, HotkeyManager};
+
+/// Pas de réglage de l'opacité (touches + et -)
+pub const OPACITY_STEP: f32 = 0.1;
+
+/// Événements envoyés à la boucle d'affichage par les threads des raccourcis et de la surveillance du focus
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppEvent {
+    Hotkey(HotkeyAction),
+    Focus(FocusedWindow),
+}
+
+/// Transmet les événements à la boucle d'affichage active
+///
+/// Les raccourcis démarrent avant le choix du backend : les événements reçus
+/// d'ici là sont gardés jusqu'à [`EventRelay::connect`].
+#[derive(Clone, Default)]
+pub struct EventRelay {
+    inner: Arc<Mutex<Relay>>,
+}
+
+#[derive(Default)]
+struct Relay {
+    target: Option<Box<dyn Fn(AppEvent) + Send>>,
+    pending: Vec<AppEvent>,
+}
+
+impl EventRelay {
+    pub fn send(&self, event: AppEvent) {
+        let Ok(mut relay) = self.inner.lock() else {
+            return;
+        };
+        if let Some(target) = &relay.target {
+            target(event);
+        } else {
+            relay.pending.push(event);
+        }
+    }
+
+    /// Envoie désormais les événements à `target`, en commençant par ceux en attente
+    pub fn connect(&self, target: impl Fn(AppEvent) + Send + 'static) {
+        let Ok(mut relay) = self.inner.lock() else {
+            return;
+        };
+        for event in relay.pending.drain(..) {
+            target(event);
+        }
+        relay.target = Some(Box::new(target));
+    }
+}
+
+/// Ce que le système d'affichage doit refaire après un événement
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Changes {
+    pub redraw: bool,     // Nouvelle image (crosshair, effets, opacité)
+    pub placement: bool,  // Taille, écran ou position à réappliquer
+    pub visibility: bool, // Afficher ou masquer selon `Controller::is_visible`
+    pub quit: bool,
+}
+
+impl std::ops::BitOrAssign for Changes {
+    fn bitor_assign(&mut self, other: Self) {
+        self.redraw |= other.redraw;
+        self.placement |= other.placement;
+        self.visibility |= other.visibility;
+        self.quit |= other.quit;
+    }
+}
+
+/// Configuration active et réactions aux raccourcis, aux profils et au focus
+pub struct Controller {
+    pub config: CrosshairConfig,
+    pub config_location: ConfigLocation,
+    pub profiles: ProfileManager,
+    base_layers: ConfigLayers,
+    overrides: Overrides,
+    opacity: Option<f32>, // Opacité réglée au clavier, gardée entre les profils
+    style: Option<CrosshairStyle>, // Style choisi au raccourci, gardé entre les profils
+    hidden: bool, // Crosshair masqué par le raccourci
+    focus_hidden: bool, // Crosshair masqué par une règle [focus]
+    focused: Option<FocusedWindow>, // Fenêtre active, tant que la surveillance tourne
+    hotkeys: Option<HotkeyManager>,
+    focus: Option<FocusManager>,
+}
+
+impl Controller {
+    pub fn new(config_location: ConfigLocation, overrides: Overrides) -> Self {
+        let base_layers = layers::load_base(&config_location.path);
+        let profiles = ProfileManager::new(&base_layers.profiles_settings(), &config_location.directory());
+        let config = layers::load_overlay(
+            &base_layers,
+            &config_location.path,
+            profiles.current_profile(),
+            overrides.to_table(),
+        )
+        .config;
+        i18n::set_language(config.interface.language);
+
+        Self {
+            config,
+            config_location,
+            profiles,
+            base_layers,
+            overrides,
+            opacity: None,
+            style: None,
+            hidden: false,
+            focus_hidden: false,
+            focused: None,
+            hotkeys: None,
+            focus: None,
+        }
+    }
+
+    /// Enregistre les raccourcis globaux, dont les actions passent par `relay`
+    pub fn start_hotkeys(&mut self, relay: &EventRelay) {
+        let relay = relay.clone();
+        let on_action: hotkeys::ActionSender = Arc::new(move |action| relay.send(AppEvent::Hotkey(action)));
+        let mut manager = HotkeyManager::new(hotkeys::platform_backend(), on_action);
+        manager.apply(&self.config.hotkeys);
+        self.hotkeys = Some(manager);
+    }
+
+    /// Démarre la surveillance de la fenêtre active, dont les changements passent par `relay`
+    pub fn start_focus(&mut self, relay: &EventRelay) {
+        let relay = relay.clone();
+        let on_focus: focus::FocusSender = Arc::new(move |window| relay.send(AppEvent::Focus(window)));
+        let mut manager = FocusManager::new(focus::platform_watcher(), on_focus);
+        manager.apply(&self.config.focus);
+        self.focus = Some(manager);
+    }
+
+    /// Libère les raccourcis et arrête la surveillance du focus (fermeture de l'overlay)
+    pub fn shutdown(&mut self) {
+        if let Some(hotkeys) = &mut self.hotkeys {
+            hotkeys.shutdown();
+        }
+        if let Some(focus) = &mut self.focus {
+            focus.shutdown();
+        }
+    }
+
+    pub fn handle(&mut self, event: AppEvent) -> Changes {
+        match event {
+            AppEvent::Hotkey(action) => self.handle_action(action),
+            AppEvent::Focus(window) => {
+                self.focused = Some(window);
+                self.apply_focus()
+            }
+        }
+    }
+
+    /// Exécute l'action d'un raccourci global ou d'une touche de la fenêtre
+    pub fn handle_action(&mut self, action: HotkeyAction) -> Changes {
+        match action {
+            HotkeyAction::ToggleVisibility => {
+                self.hidden = !self.hidden;
+                println!("{}", if self.hidden { tr!("app.crosshair_hidden") } else { tr!("app.crosshair_shown") });
+                Changes { visibility: true, ..Changes::default() }
+            }
+            HotkeyAction::Reload => {
+                // L'opacité et le style reviennent à ceux du fichier
+                println!("{}", tr!("app.reloading"));
+                self.opacity = None;
+                self.style = None;
+                self.base_layers = layers::load_base(&self.config_location.path);
+                self.profiles.reload(&self.base_layers.profiles_settings(), &self.config_location.directory());
+                let mut changes = self.apply_profile();
+                changes |= self.apply_focus();
+                changes
+            }
+            HotkeyAction::NextProfile | HotkeyAction::PreviousProfile => {
+                self.profiles.refresh();
+                let switched = if action == HotkeyAction::NextProfile {
+                    self.profiles.next_profile().is_some()
+                } else {
+                    self.profiles.previous_profile().is_some()
+                };
+                if switched {
+                    self.apply_profile()
+                } else {
+                    println!("{}", tr!("app.no_profiles"));
+                    Changes::default()
+                }
+            }
+            HotkeyAction::CycleStyle => {
+                let previous_side = self.config.window_side(1.0);
+                let style = self.config.crosshair.style.next();
+                println!("{}", tr!("app.style", style = format!("{:?}", style)));
+                self.config.crosshair.style = style.clone();
+                self.style = Some(style);
+                // En taille automatique, la fenêtre suit l'étendue du nouveau style
+                Changes { redraw: true, placement: self.config.window_side(1.0) != previous_side, ..Changes::default() }
+            }
+            HotkeyAction::OpacityUp => self.adjust_opacity(OPACITY_STEP),
+            HotkeyAction::OpacityDown => self.adjust_opacity(-OPACITY_STEP),
+            HotkeyAction::Quit => Changes { quit: true, ..Changes::default() },
+        }
+    }
+
+    /// Passe au profil d'une position (touches 1 à 9)
+    pub fn select_profile(&mut self, index: usize) -> Changes {
+        self.profiles.refresh();
+        match self.profiles.select_index(index) {
+            Ok(()) => self.apply_profile(),
+            Err(e) => {
+                eprintln!("{}", tr!("app.profile_switch_failed", error = e));
+                Changes::default()
+            }
+        }
+    }
+
+    /// Change l'opacité globale de la fenêtre
+    pub fn adjust_opacity(&mut self, step: f32) -> Changes {
+        let current = self.config.window.opacity;
+        let current = if current.is_finite() { current.clamp(0.0, 1.0) } else { 1.0 };
+        // Arrondi au pas pour éviter les dérives (0.30000001)
+        let opacity = ((current + step) / OPACITY_STEP).round() * OPACITY_STEP;
+        let opacity = opacity.clamp(0.0, 1.0);
+        self.opacity = Some(opacity);
+        self.config.window.opacity = opacity;
+        println!("{}", tr!("app.opacity", percent = (opacity * 100.0).round()));
+        Changes { redraw: true, ..Changes::default() }
+    }
+
+    /// Visible sauf si le raccourci ou une règle [focus] masquent le crosshair
+    pub fn is_visible(&self) -> bool {
+        !self.hidden && !self.focus_hidden
+    }
+
+    /// Applique le profil courant (et les options de la ligne de commande)
+    ///
+    /// La fenêtre est à replacer si sa taille, son écran ou sa position ont changé.
+    fn apply_profile(&mut self) -> Changes {
+        let previous_side = self.config.window_side(1.0);
+        let previous = std::mem::take(&mut self.config.window);
+        self.config = layers::load_overlay(
+            &self.base_layers,
+            &self.config_location.path,
+            self.profiles.current_profile(),
+            self.overrides.to_table(),
+        )
+        .config;
+        i18n::set_language(self.config.interface.language);
+        if let Some(opacity) = self.opacity {
+            self.config.window.opacity = opacity;
+        }
+        if let Some(style) = &self.style {
+            self.config.crosshair.style = style.clone();
+        }
+        if let Some(hotkeys) = &mut self.hotkeys {
+            hotkeys.apply(&self.config.hotkeys);
+        }
+        if let Some(focus) = &mut self.focus {
+            if !focus.apply(&self.config.focus) {
+                self.focused = None;
+            }
+        }
+        // Les règles ont pu changer : réévaluer la visibilité pour la fenêtre active
+        self.focus_hidden = self.focused.as_ref().is_some_and(|window| !self.config.focus.decide(window).visible);
+
+        let name = self.profiles.current_name().map_or_else(|| tr!("app.base_configuration"), str::to_string);
+        println!("{}", tr!("app.active_profile", name = name));
+
+        // En taille automatique, le crosshair et les effets changent aussi la taille
+        let placement = self.config.window.changes_from(&previous).geometry || self.config.window_side(1.0) != previous_side;
+        Changes { redraw: true, placement, visibility: true, quit: false }
+    }
+
+    /// Applique les règles [focus] à la fenêtre active : visibilité et profil
+    fn apply_focus(&mut self) -> Changes {
+        let Some(window) = &self.focused else {
+            return Changes::default();
+        };
+        let decision = self.config.focus.decide(window);
+        let profile = decision.profile.filter(|name| self.profiles.current_name() != Some(*name)).map(str::to_string);
+        let application = window_label(window);
+        self.focus_hidden = !decision.visible;
+        let mut changes = Changes { visibility: true, ..Changes::default() };
+
+        if let Some(name) = profile {
+            self.profiles.refresh();
+            match self.profiles.select(&name) {
+                Ok(()) => {
+                    println!("{}", tr!("focus.profile", name = name, application = application));
+                    changes |= self.apply_profile();
+                }
+                Err(e) => eprintln!("{}", tr!("focus.profile_failed", error = e)),
+            }
+        }
+        changes
+    }
+}
+
+/// Application de la fenêtre active pour les messages : processus, sinon titre
+fn window_label(window: &FocusedWindow) -> String {
+    if window.process.is_empty() { window.title.clone() } else { window.process.clone() }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn relay_keeps_events_until_a_loop_connects() {
+        let relay = EventRelay::default();
+        relay.send(AppEvent::Hotkey(HotkeyAction::ToggleVisibility));
+        relay.send(AppEvent::Hotkey(HotkeyAction::Quit));
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        relay.connect(move |event| sink.lock().unwrap().push(event));
+        relay.send(AppEvent::Focus(FocusedWindow::default()));
+
+        let expected = [
+            AppEvent::Hotkey(HotkeyAction::ToggleVisibility),
+            AppEvent::Hotkey(HotkeyAction::Quit),
+            AppEvent::Focus(FocusedWindow::default()),
+        ];
+        assert_eq!(*received.lock().unwrap(), expected);
+    }
+}
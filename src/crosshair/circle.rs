@@ -20,21 +20,22 @@ impl CrosshairRenderer for CircleCrosshair {
 
         let center_x = width / 2;
         let center_y = height / 2;
-        let radius = crosshair.size as i32;
+        let radius = crosshair.size as f32;
         let gap = crosshair.gap as f32;
 
-        // Calculer l'offset de shake
+        // Calculer l'offset de shake (en pixels entiers, calculs en flottants pour éviter les débordements)
         let (shake_x, shake_y) = effects.shake.get_offset(time);
+        let (shake_x, shake_y) = (shake_x.trunc(), shake_y.trunc());
 
         for y in 0..height {
             for x in 0..width {
-                let dx = x as i32 - (center_x as i32 + shake_x as i32);
-                let dy = y as i32 - (center_y as i32 + shake_y as i32);
-                let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                let dx = x as f32 - (center_x as f32 + shake_x);
+                let dy = y as f32 - (center_y as f32 + shake_y);
+                let distance = (dx * dx + dy * dy).sqrt();
                 
                 if crosshair.filled_circle {
                     // Mode cercle plein avec gap au centre
-                    let outer_radius = radius as f32;
+                    let outer_radius = radius;
                     
                     if distance >= gap && distance <= outer_radius {
                         // Outline pour le cercle plein
@@ -54,8 +55,8 @@ impl CrosshairRenderer for CircleCrosshair {
                     }
                 } else {
                     // Mode cercle contour (creux) avec gap au centre
-                    let inner_radius = ((radius - crosshair.thickness as i32).max(gap as i32)).max(0) as f32;
-                    let outer_radius = radius as f32;
+                    let inner_radius = (radius - crosshair.thickness as f32).max(gap.trunc()).max(0.0);
+                    let outer_radius = radius;
                     
                     if distance >= inner_radius && distance <= outer_radius {
                         // Outline pour le cercle creux
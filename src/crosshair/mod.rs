@@ -1,6 +1,10 @@
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use crate::clock::Clock;
+use crate::config::color::Color;
 use crate::config::effects::Effects;
-use crate::config::effects::mouse::VisibilityMask;
+use crate::config::effects::mouse::{MouseState, VisibilityMask};
+use crate::tr;
 
 // Modules pour chaque style de crosshair
 pub mod dot;
@@ -20,9 +24,11 @@ pub use x_shape::XShapeCrosshair;
 pub use square::SquareCrosshair;
 pub use diamond::DiamondCrosshair;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
 #[serde(rename_all = "lowercase")]
 pub enum CrosshairStyle {
+    #[default]
     Classic,    // Lignes droites avec gap
     Dot,        // Juste un point central
     Circle,     // Cercle
@@ -32,17 +38,50 @@ pub enum CrosshairStyle {
     Diamond,    // Losange avec gap
 }
 
-impl Default for CrosshairStyle {
-    fn default() -> Self {
-        CrosshairStyle::Classic
+impl CrosshairStyle {
+    /// Style suivant, dans l'ordre de la déclaration (le dernier revient au premier)
+    pub fn next(&self) -> Self {
+        match self {
+            Self::Classic => Self::Dot,
+            Self::Dot => Self::Circle,
+            Self::Circle => Self::T,
+            Self::T => Self::X,
+            Self::X => Self::Square,
+            Self::Square => Self::Diamond,
+            Self::Diamond => Self::Classic,
+        }
+    }
+}
+
+impl std::str::FromStr for CrosshairStyle {
+    type Err = String;
+
+    /// Accepte les mêmes noms que config.toml ("classic", "dot", "t"...)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "classic" => Ok(Self::Classic),
+            "dot" => Ok(Self::Dot),
+            "circle" => Ok(Self::Circle),
+            "t" => Ok(Self::T),
+            "x" => Ok(Self::X),
+            "square" => Ok(Self::Square),
+            "diamond" => Ok(Self::Diamond),
+            _ => Err(tr!("crosshair.unknown_style", style = s)),
+        }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct CenterDot {
+    #[schemars(description = "Draw a dot at the center")]
     pub enabled: bool,
+    #[schemars(description = "Center dot size in pixels")]
     pub size: u32,
-    pub color: String,          // Format hex: "#FF0000"
+    #[schemars(description = "Center dot color (same syntax as crosshair.color)")]
+    pub color: Color,           // "#FF0000", "red", "rgb(255, 0, 0)"...
+    #[schemars(description = "Center dot transparency (0.0 = invisible, 1.0 = opaque)", range(min = 0.0, max = 1.0))]
     pub alpha: f32,             // Transparence 0.0-1.0
 }
 
@@ -51,17 +90,23 @@ impl Default for CenterDot {
         Self {
             enabled: true,
             size: 2,
-            color: "#FF0000".to_string(),  // Rouge
+            color: Color::RED,
             alpha: 1.0,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
 pub struct Outline {
+    #[schemars(description = "Draw a border around the crosshair")]
     pub enabled: bool,
+    #[schemars(description = "Outline thickness in pixels")]
     pub thickness: u32,
-    pub color: String,          // Format hex: "#000000"
+    #[schemars(description = "Outline color (same syntax as crosshair.color)")]
+    pub color: Color,           // "#000000", "black"...
+    #[schemars(description = "Outline transparency (0.0 = invisible, 1.0 = opaque)", range(min = 0.0, max = 1.0))]
     pub alpha: f32,             // Transparence 0.0-1.0
 }
 
@@ -70,25 +115,39 @@ impl Default for Outline {
         Self {
             enabled: false,
             thickness: 1,
-            color: "#000000".to_string(),  // Noir
+            color: Color::BLACK,
             alpha: 0.8,
         }
     }
 }
 
 /// Structure principale du crosshair avec toutes ses propriétés
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[serde(default)]
+#[schemars(description = "Crosshair shape and colors")]
 pub struct Crosshair {
+    #[schemars(description = "Crosshair size in pixels (distance from the center to the end of the lines)", range(min = 1))]
     pub size: u32,
+    #[schemars(description = "Line thickness in pixels", range(min = 1))]
     pub thickness: u32,
+    #[schemars(description = "Empty space at the center in pixels")]
     pub gap: u32,
-    pub color: String,          // Format hex: "#00FF00"
+    #[schemars(description = "Crosshair color: \"#0F0\", \"#00FF00\", \"#00FF0080\", \"rgb(0, 255, 0)\", \"hsl(120, 100%, 50%)\", \"lime\"...")]
+    pub color: Color,           // "#00FF00", "lime", "hsl(120, 100%, 50%)"...
+    #[schemars(description = "Crosshair transparency (0.0 = invisible, 1.0 = opaque)", range(min = 0.0, max = 1.0))]
     pub alpha: f32,             // Transparence 0.0-1.0
+    #[schemars(description = "Rotation in degrees")]
     pub rotation: f32,          // Rotation en degrés
+    #[schemars(description = "Dot drawn at the center of the crosshair")]
     pub center_dot: CenterDot,
+    #[schemars(description = "Crosshair shape")]
     pub style: CrosshairStyle,
+    #[schemars(description = "Border drawn around the crosshair")]
     pub outline: Outline,
+    #[schemars(description = "For the \"classic\" style: triangles instead of rectangles")]
     pub triangle_bars: bool,    // Pour style classic : triangles au lieu de rectangles
+    #[schemars(description = "For the \"circle\" style: filled disc instead of a ring")]
     pub filled_circle: bool,    // Pour style circle : plein ou juste contour
 }
 
@@ -98,7 +157,7 @@ impl Default for Crosshair {
             size: 25,
             thickness: 2,
             gap: 5,
-            color: "#00FF00".to_string(),  // Vert
+            color: Color::GREEN,
             alpha: 1.0,
             rotation: 0.0,
             center_dot: CenterDot::default(),
@@ -110,47 +169,147 @@ impl Default for Crosshair {
     }
 }
 
+/// Pixels d'une image width x height au début du buffer, None si le buffer est trop petit
+pub(crate) fn frame_pixels(buffer: &mut [u32], width: usize, height: usize) -> Option<&mut [u32]> {
+    let length = width.checked_mul(height)?;
+    buffer.get_mut(..length)
+}
+
+/// Coordonnée décalée par le shake, None si elle sort de l'image (négative ou non finie)
+pub(crate) fn shake_coordinate(coordinate: usize, shake: f32) -> Option<usize> {
+    let adjusted = coordinate as f32 - shake;
+    (adjusted >= 0.0 && adjusted.is_finite()).then_some(adjusted as usize)
+}
+
 /// Trait commun pour tous les styles de crosshair
 pub trait CrosshairRenderer {
     fn draw(&self, crosshair: &Crosshair, buffer: &mut [u32], width: usize, height: usize, effects: &Effects, time: f32);
 }
 
 impl Crosshair {
-    /// Convertit une couleur hex en format RGBA u32 avec alpha
-    pub fn parse_color_with_alpha(hex: &str, alpha: f32) -> u32 {
-        let hex = hex.trim_start_matches('#');
-        let alpha_u8 = (alpha.clamp(0.0, 1.0) * 255.0) as u32;
-        
-        if hex.len() == 6 {
-            if let Ok(rgb) = u32::from_str_radix(hex, 16) {
-                return (alpha_u8 << 24) | rgb;
-            }
-        }
-        (alpha_u8 << 24) | 0xFFFFFF // Blanc par défaut avec alpha
-    }
-
     /// Obtient la couleur du crosshair en format u32 avec alpha
     pub fn get_color(&self) -> u32 {
-        Self::parse_color_with_alpha(&self.color, self.alpha)
+        self.color.to_argb(self.alpha)
     }
 
     /// Obtient la couleur du point central en format u32 avec alpha
     pub fn get_center_dot_color(&self) -> u32 {
-        Self::parse_color_with_alpha(&self.center_dot.color, self.center_dot.alpha)
+        self.center_dot.color.to_argb(self.center_dot.alpha)
     }
 
     /// Obtient la couleur de l'outline en format u32 avec alpha
     pub fn get_outline_color(&self) -> u32 {
-        Self::parse_color_with_alpha(&self.outline.color, self.outline.alpha)
+        self.outline.color.to_argb(self.outline.alpha)
+    }
+
+    /// Presets pour différents styles d'usage
+    pub fn preset_discrete() -> Self {
+        Self {
+            size: 15,
+            thickness: 1,
+            gap: 3,
+            color: Color::rgb(255, 255, 255),
+            alpha: 0.8,
+            outline: Outline { enabled: true, ..Outline::default() },
+            ..Self::default()
+        }
+    }
+
+    pub fn preset_triangle() -> Self {
+        Self {
+            thickness: 8,
+            gap: 3,
+            triangle_bars: true,
+            outline: Outline { enabled: true, ..Outline::default() },
+            ..Self::default()
+        }
+    }
+
+    pub fn preset_filled_circle() -> Self {
+        Self {
+            size: 20,
+            gap: 5,
+            color: Color::rgb(255, 0, 255),
+            style: CrosshairStyle::Circle,
+            filled_circle: true,
+            ..Self::default()
+        }
+    }
+
+    pub fn preset_sniper_dot() -> Self {
+        Self {
+            size: 3,
+            color: Color::RED,
+            style: CrosshairStyle::Dot,
+            center_dot: CenterDot { enabled: false, ..CenterDot::default() },
+            ..Self::default()
+        }
+    }
+
+    pub fn preset_rotating_square() -> Self {
+        Self {
+            thickness: 3,
+            rotation: 45.0,
+            style: CrosshairStyle::Square,
+            outline: Outline { enabled: true, ..Outline::default() },
+            ..Self::default()
+        }
+    }
+
+    pub fn preset_diamond() -> Self {
+        Self {
+            gap: 8,
+            color: Color::rgb(255, 0, 255),
+            style: CrosshairStyle::Diamond,
+            outline: Outline { enabled: true, ..Outline::default() },
+            ..Self::default()
+        }
+    }
+
+    pub fn preset_large() -> Self {
+        Self {
+            size: 40,
+            thickness: 3,
+            gap: 8,
+            ..Self::default()
+        }
+    }
+
+    /// Distance maximale au centre d'un pixel dessiné, effets compris
+    ///
+    /// Tient compte de la forme, de la rotation, du point central, du shake et
+    /// de l'agrandissement par les effets de souris.
+    pub fn extent(&self, effects: &Effects) -> f32 {
+        let size = effects.mouse.max_size(self.gap, self.size) as f32;
+        let half_thickness = self.thickness as f32 / 2.0;
+        // Un quart de tour garde la boîte englobante, sinon on prend le rayon
+        let rotated = self.rotation.rem_euclid(90.0) != 0.0;
+
+        let shape = match self.style {
+            CrosshairStyle::Classic | CrosshairStyle::T if rotated => size.hypot(half_thickness),
+            CrosshairStyle::Classic | CrosshairStyle::T => size.max(half_thickness),
+            CrosshairStyle::X | CrosshairStyle::Square if rotated => size * std::f32::consts::SQRT_2,
+            _ => size,
+        };
+        let center_dot = if self.center_dot.enabled { self.center_dot.size as f32 } else { 0.0 };
+        let shake = if effects.shake.enabled && effects.shake.intensity.is_finite() {
+            effects.shake.intensity.abs()
+        } else {
+            0.0
+        };
+        shape.max(center_dot) + shake
     }
 
     /// Applique la rotation à un point
     pub fn rotate_point(&self, x: f32, y: f32, center_x: f32, center_y: f32) -> (f32, f32) {
-        if self.rotation == 0.0 {
+        // Ramener l'angle sur un tour, comme `extent` : un très grand multiple de
+        // 90° en f32 ne donnerait pas un quart de tour exact une fois en radians
+        let degrees = self.rotation.rem_euclid(360.0);
+        if degrees == 0.0 {
             return (x, y);
         }
         
-        let angle = self.rotation.to_radians();
+        let angle = degrees.to_radians();
         let cos_a = angle.cos();
         let sin_a = angle.sin();
         
@@ -163,19 +322,31 @@ impl Crosshair {
         (rotated_x, rotated_y)
     }
 
-    /// Dessine le crosshair selon son style
-    pub fn draw(&self, buffer: &mut [u32], width: usize, height: usize, effects: &Effects, time: f32) {
-        use crate::config::effects::mouse::{get_global_mouse_state, VisibilityMask};
-        
-        // Récupérer l'état de la souris
-        let mouse_state = get_global_mouse_state();
-        
+    /// Dessine le crosshair pour un état de souris donné
+    ///
+    /// Seuls les `width * height` premiers pixels du buffer sont modifiés. Un
+    /// buffer trop petit pour ces dimensions n'est pas modifié du tout.
+    pub fn draw(
+        &self,
+        buffer: &mut [u32],
+        width: usize,
+        height: usize,
+        effects: &Effects,
+        clock: &dyn Clock,
+        mouse_state: &MouseState,
+    ) {
+        let Some(buffer) = frame_pixels(buffer, width, height) else {
+            return;
+        };
+        let time = clock.now();
+
         // Appliquer les effets de souris
         let (modified_gap, modified_size, modified_alpha, visibility_mask) = effects.mouse.apply_effects(
             self.gap,
             self.size,
             self.alpha,
-            &mouse_state
+            mouse_state,
+            clock
         );
         
         // Créer une version modifiée du crosshair avec les effets appliqués
@@ -222,6 +393,7 @@ impl Crosshair {
     }
 
     /// Dessine avec le masque de visibilité appliqué
+    #[allow(clippy::too_many_arguments)]
     fn draw_with_visibility_mask<T: CrosshairRenderer>(
         &self,
         renderer: &T,
@@ -257,7 +429,7 @@ impl Crosshair {
                     if visibility_mask.should_show_point(dx, dy, center_x, center_y) {
                         // Appliquer le multiplicateur d'alpha
                         let alpha = ((pixel >> 24) & 0xFF) as f32 / 255.0;
-                        let modified_alpha = (alpha * visibility_mask.alpha_multiplier * 255.0) as u32;
+                        let modified_alpha = ((alpha * visibility_mask.alpha_multiplier).clamp(0.0, 1.0) * 255.0) as u32;
                         let modified_pixel = (modified_alpha << 24) | (pixel & 0x00FFFFFF);
                         buffer[y * width + x] = modified_pixel;
                     }
@@ -275,7 +447,7 @@ impl Crosshair {
         let mut dot_color = if effects.rainbow.enabled {
             // Version plus sombre du rainbow pour le centre
             let rainbow = effects.rainbow.get_color(time * 1.5, self.alpha);
-            (rainbow & 0x00FFFFFF) | ((self.center_dot.alpha * 255.0) as u32) << 24
+            (rainbow & 0x00FFFFFF) | ((self.center_dot.alpha.clamp(0.0, 1.0) * 255.0) as u32) << 24
         } else {
             self.get_center_dot_color()
         };
@@ -1,4 +1,4 @@
-use super::{Crosshair, CrosshairRenderer};
+use super::{shake_coordinate, Crosshair, CrosshairRenderer};
 use crate::config::effects::Effects;
 
 /// Renderer pour le style Square - carré simple avec gap au centre
@@ -25,9 +25,10 @@ impl CrosshairRenderer for SquareCrosshair {
         
         for y in 0..height {
             for x in 0..width {
-                // Appliquer le shake en décalant les coordonnées
-                let adjusted_x = (x as f32 - shake_x) as usize;
-                let adjusted_y = (y as f32 - shake_y) as usize;
+                // Appliquer le shake en décalant les coordonnées (hors écran = rien à dessiner)
+                let (Some(adjusted_x), Some(adjusted_y)) = (shake_coordinate(x, shake_x), shake_coordinate(y, shake_y)) else {
+                    continue;
+                };
                 
                 if self.is_on_square(crosshair, adjusted_x, adjusted_y, width, height) {
                     let mut final_color = color;
@@ -36,9 +37,9 @@ impl CrosshairRenderer for SquareCrosshair {
                     if crosshair.outline.enabled {
                         // Vérifier si c'est un pixel de bordure
                         let is_edge = !self.is_on_square(crosshair, adjusted_x.saturating_sub(1), adjusted_y, width, height) ||
-                                     !self.is_on_square(crosshair, adjusted_x + 1, adjusted_y, width, height) ||
+                                     !self.is_on_square(crosshair, adjusted_x.saturating_add(1), adjusted_y, width, height) ||
                                      !self.is_on_square(crosshair, adjusted_x, adjusted_y.saturating_sub(1), width, height) ||
-                                     !self.is_on_square(crosshair, adjusted_x, adjusted_y + 1, width, height);
+                                     !self.is_on_square(crosshair, adjusted_x, adjusted_y.saturating_add(1), width, height);
                         
                         if is_edge {
                             final_color = outline_color;
@@ -0,0 +1,143 @@
+//! Surveillance de la fenêtre au premier plan, pour les règles `[focus]`
+//!
+//! Chaque plateforme fournit un [`FocusWatcher`] : `_NET_ACTIVE_WINDOW` sur
+//! la fenêtre racine sous X11, fenêtre de premier plan sous Windows. La
+//! décision (afficher, masquer, changer de profil) est prise par
+//! [`FocusConfig::decide`].
+
+use std::sync::Arc;
+
+use crosshair_overlay::config::focus::{FocusConfig, FocusedWindow};
+use crosshair_overlay::tr;
+
+#[cfg(windows)]
+mod windows;
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+mod x11;
+
+/// Reçoit la fenêtre active à chaque changement, depuis le thread du backend
+pub type FocusSender = Arc<dyn Fn(FocusedWindow) + Send + Sync>;
+
+/// Source des changements de fenêtre active propre à une plateforme
+pub trait FocusWatcher {
+    /// Démarre la surveillance ; `on_focus` reçoit d'abord la fenêtre active actuelle
+    fn start(&mut self, on_focus: FocusSender) -> Result<(), String>;
+
+    /// Arrête la surveillance
+    fn stop(&mut self);
+}
+
+/// Backend de la plateforme, None si la fenêtre active n'y est pas observable
+pub fn platform_watcher() -> Option<Box<dyn FocusWatcher>> {
+    #[cfg(windows)]
+    return Some(Box::new(windows::WindowsFocus::new()));
+
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+    return std::env::var_os("DISPLAY")
+        .is_some_and(|display| !display.is_empty())
+        .then(|| Box::new(x11::X11Focus::new()) as Box<dyn FocusWatcher>);
+
+    #[allow(unreachable_code)]
+    None
+}
+
+/// Démarre ou arrête la surveillance selon `focus.enabled`
+pub struct FocusManager {
+    watcher: Option<Box<dyn FocusWatcher>>,
+    on_focus: FocusSender,
+    running: bool,
+}
+
+impl FocusManager {
+    pub fn new(watcher: Option<Box<dyn FocusWatcher>>, on_focus: FocusSender) -> Self {
+        Self { watcher, on_focus, running: false }
+    }
+
+    /// Suit la configuration ; retourne vrai si la surveillance est active
+    pub fn apply(&mut self, focus: &FocusConfig) -> bool {
+        if focus.enabled == self.running {
+            return self.running;
+        }
+        let Some(watcher) = &mut self.watcher else {
+            if focus.enabled {
+                eprintln!("{}", tr!("focus.unsupported"));
+            }
+            return false;
+        };
+        if focus.enabled {
+            match watcher.start(self.on_focus.clone()) {
+                Ok(()) => self.running = true,
+                Err(e) => eprintln!("{}", tr!("focus.failed", error = e)),
+            }
+        } else {
+            watcher.stop();
+            self.running = false;
+        }
+        self.running
+    }
+
+    /// Arrête la surveillance (fermeture de l'overlay)
+    pub fn shutdown(&mut self) {
+        if let Some(watcher) = &mut self.watcher {
+            watcher.stop();
+        }
+        self.running = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crosshair_overlay::config::focus::FocusDecision;
+    use std::sync::Mutex;
+
+    /// Backend qui simule les changements de fenêtre active
+    #[derive(Clone, Default)]
+    struct FakeWatcher {
+        on_focus: Arc<Mutex<Option<FocusSender>>>,
+        starts: Arc<Mutex<usize>>,
+    }
+
+    impl FakeWatcher {
+        fn focus(&self, process: &str, title: &str) {
+            if let Some(on_focus) = self.on_focus.lock().unwrap().clone() {
+                on_focus(FocusedWindow { process: process.to_string(), class: String::new(), title: title.to_string() });
+            }
+        }
+    }
+
+    impl FocusWatcher for FakeWatcher {
+        fn start(&mut self, on_focus: FocusSender) -> Result<(), String> {
+            *self.starts.lock().unwrap() += 1;
+            *self.on_focus.lock().unwrap() = Some(on_focus);
+            Ok(())
+        }
+
+        fn stop(&mut self) {
+            *self.on_focus.lock().unwrap() = None;
+        }
+    }
+
+    #[test]
+    fn focus_changes_drive_the_rules_while_enabled() {
+        let focus: FocusConfig = toml::from_str("enabled = true\n[[rules]]\nprocess = \"cs2\"\nprofile = \"cs2\"").unwrap();
+        let watcher = FakeWatcher::default();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let mut manager = FocusManager::new(Some(Box::new(watcher.clone())), Arc::new(move |window| sink.lock().unwrap().push(window)));
+
+        assert!(manager.apply(&focus));
+        assert!(manager.apply(&focus.clone()), "déjà démarrée");
+        assert_eq!(*watcher.starts.lock().unwrap(), 1);
+
+        watcher.focus("cs2", "Counter-Strike 2");
+        watcher.focus("firefox", "Mozilla Firefox");
+        let decisions: Vec<FocusDecision> = received.lock().unwrap().iter().map(|window| focus.decide(window)).collect();
+        assert_eq!(decisions, [FocusDecision { visible: true, profile: Some("cs2") }, FocusDecision { visible: false, profile: None }]);
+
+        // Désactivée au rechargement : plus aucun événement
+        assert!(!manager.apply(&FocusConfig::default()));
+        watcher.focus("cs2", "Counter-Strike 2");
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,106 @@
+//! Fenêtre active Windows : `GetForegroundWindow`
+//!
+//! Windows ne notifie pas un autre processus des changements de titre de la
+//! fenêtre de premier plan : un thread dédié l'interroge à intervalle régulier.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use windows::core::PWSTR;
+use windows::Win32::{
+    Foundation::{CloseHandle, HWND},
+    System::Threading::{OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32, PROCESS_QUERY_LIMITED_INFORMATION},
+    UI::WindowsAndMessaging::{GetClassNameW, GetForegroundWindow, GetWindowTextW, GetWindowThreadProcessId},
+};
+
+use crosshair_overlay::config::focus::FocusedWindow;
+
+use super::{FocusSender, FocusWatcher};
+
+/// Intervalle entre deux lectures de la fenêtre de premier plan
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Thread de surveillance et son drapeau d'arrêt
+struct Watch {
+    stop: Arc<AtomicBool>,
+    thread: JoinHandle<()>,
+}
+
+pub struct WindowsFocus {
+    watch: Option<Watch>,
+}
+
+impl WindowsFocus {
+    pub fn new() -> Self {
+        Self { watch: None }
+    }
+}
+
+impl FocusWatcher for WindowsFocus {
+    fn start(&mut self, on_focus: FocusSender) -> Result<(), String> {
+        self.stop();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = stop.clone();
+        let thread = std::thread::spawn(move || {
+            let mut last = None;
+            while !stopped.load(Ordering::Relaxed) {
+                let window = foreground_window();
+                if last.as_ref() != Some(&window) {
+                    on_focus(window.clone());
+                    last = Some(window);
+                }
+                std::thread::sleep(POLL_INTERVAL);
+            }
+        });
+        self.watch = Some(Watch { stop, thread });
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        if let Some(watch) = self.watch.take() {
+            watch.stop.store(true, Ordering::Relaxed);
+            let _ = watch.thread.join();
+        }
+    }
+}
+
+impl Drop for WindowsFocus {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Processus, classe et titre de la fenêtre de premier plan (vides sans fenêtre active)
+fn foreground_window() -> FocusedWindow {
+    unsafe {
+        let window = GetForegroundWindow();
+        if window == HWND::default() {
+            return FocusedWindow::default();
+        }
+        let mut buffer = [0u16; 512];
+        let length = GetWindowTextW(window, &mut buffer);
+        let title = String::from_utf16_lossy(&buffer[..length.max(0) as usize]);
+        let length = GetClassNameW(window, &mut buffer);
+        let class = String::from_utf16_lossy(&buffer[..length.max(0) as usize]);
+
+        let mut pid = 0;
+        GetWindowThreadProcessId(window, Some(&mut pid));
+        FocusedWindow { process: process_name(pid).unwrap_or_default(), class, title }
+    }
+}
+
+/// Nom de l'exécutable d'un processus (`cs2.exe`)
+fn process_name(pid: u32) -> Option<String> {
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid).ok()?;
+        let mut buffer = [0u16; 1024];
+        let mut length = buffer.len() as u32;
+        let result = QueryFullProcessImageNameW(process, PROCESS_NAME_WIN32, PWSTR(buffer.as_mut_ptr()), &mut length);
+        let _ = CloseHandle(process);
+        result.ok()?;
+        let path = String::from_utf16_lossy(&buffer[..length as usize]);
+        path.rsplit(['\\', '/']).next().map(str::to_string)
+    }
+}
@@ -0,0 +1,186 @@
+//! Fenêtre active X11 : propriété `_NET_ACTIVE_WINDOW` de la fenêtre racine
+//!
+//! Le gestionnaire de fenêtres (EWMH) met à jour cette propriété à chaque
+//! changement de focus. Le titre de la fenêtre active est suivi lui aussi :
+//! un jeu peut passer du menu à la partie sans changer de fenêtre.
+
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use x11rb::connection::Connection;
+use x11rb::protocol::xproto::{
+    Atom, AtomEnum, ChangeWindowAttributesAux, ClientMessageEvent, ConnectionExt as _, CreateWindowAux, EventMask,
+    Window, WindowClass,
+};
+use x11rb::protocol::Event;
+use x11rb::rust_connection::RustConnection;
+
+use crosshair_overlay::config::focus::FocusedWindow;
+
+use super::{FocusSender, FocusWatcher};
+
+x11rb::atom_manager! {
+    /// Atomes EWMH utilisés pour décrire la fenêtre active
+    Atoms: AtomsCookie {
+        _NET_ACTIVE_WINDOW,
+        _NET_WM_NAME,
+        _NET_WM_PID,
+        UTF8_STRING,
+    }
+}
+
+/// Connexion et thread de surveillance
+struct Watch {
+    connection: Arc<RustConnection>,
+    wakeup: Window, // Fenêtre invisible qui reçoit le message d'arrêt du thread
+    thread: JoinHandle<()>,
+}
+
+pub struct X11Focus {
+    watch: Option<Watch>,
+}
+
+impl X11Focus {
+    pub fn new() -> Self {
+        Self { watch: None }
+    }
+}
+
+impl FocusWatcher for X11Focus {
+    fn start(&mut self, on_focus: FocusSender) -> Result<(), String> {
+        self.stop();
+        let (connection, screen_num) = x11rb::connect(None).map_err(|e| e.to_string())?;
+        let root = connection.setup().roots[screen_num].root;
+        let atoms = Atoms::new(&connection).map_err(|e| e.to_string())?.reply().map_err(|e| e.to_string())?;
+        connection
+            .change_window_attributes(root, &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE))
+            .map_err(|e| e.to_string())?;
+
+        let wakeup = connection.generate_id().map_err(|e| e.to_string())?;
+        connection
+            .create_window(0, wakeup, root, 0, 0, 1, 1, 0, WindowClass::INPUT_ONLY, 0, &CreateWindowAux::new())
+            .map_err(|e| e.to_string())?;
+        connection.flush().map_err(|e| e.to_string())?;
+
+        let connection = Arc::new(connection);
+        let reader = connection.clone();
+        let thread = std::thread::spawn(move || watch_focus(&reader, root, wakeup, atoms, &on_focus));
+        self.watch = Some(Watch { connection, wakeup, thread });
+        Ok(())
+    }
+
+    fn stop(&mut self) {
+        let Some(watch) = self.watch.take() else {
+            return;
+        };
+        // Réveiller le thread bloqué dans wait_for_event, puis fermer la connexion
+        let event = ClientMessageEvent::new(32, watch.wakeup, AtomEnum::NONE, [0u32; 5]);
+        let sent = watch.connection.send_event(false, watch.wakeup, EventMask::NO_EVENT, event).is_ok();
+        if sent && watch.connection.flush().is_ok() {
+            let _ = watch.thread.join();
+        }
+    }
+}
+
+impl Drop for X11Focus {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Boucle du thread : signale la fenêtre active quand elle ou son titre change
+fn watch_focus(connection: &RustConnection, root: Window, wakeup: Window, atoms: Atoms, on_focus: &FocusSender) {
+    let mut active = None;
+    let mut last = None;
+    let mut check = true; // Fenêtre active au démarrage
+    loop {
+        if check {
+            check = false;
+            let window = active_window(connection, root, &atoms);
+            if window != active {
+                // Suivre aussi les changements de titre de la nouvelle fenêtre active
+                if let Some(window) = window {
+                    let _ = connection.change_window_attributes(window, &ChangeWindowAttributesAux::new().event_mask(EventMask::PROPERTY_CHANGE));
+                }
+                active = window;
+            }
+            let description = active.map(|window| describe(connection, window, &atoms)).unwrap_or_default();
+            if last.as_ref() != Some(&description) {
+                on_focus(description.clone());
+                last = Some(description);
+            }
+            let _ = connection.flush();
+        }
+
+        match connection.wait_for_event() {
+            Ok(Event::PropertyNotify(property)) => {
+                check = (property.window == root && property.atom == atoms._NET_ACTIVE_WINDOW)
+                    || (Some(property.window) == active
+                        && [atoms._NET_WM_NAME, Atom::from(AtomEnum::WM_NAME)].contains(&property.atom));
+            }
+            Ok(Event::ClientMessage(message)) if message.window == wakeup => break,
+            Ok(_) => {}
+            Err(_) => break,
+        }
+    }
+}
+
+fn active_window(connection: &RustConnection, root: Window, atoms: &Atoms) -> Option<Window> {
+    let reply = connection.get_property(false, root, atoms._NET_ACTIVE_WINDOW, AtomEnum::WINDOW, 0, 1).ok()?.reply().ok()?;
+    let window = reply.value32()?.next();
+    window.filter(|window| *window != x11rb::NONE)
+}
+
+/// Processus (via `_NET_WM_PID` et /proc), classe (`WM_CLASS`) et titre de la fenêtre
+fn describe(connection: &RustConnection, window: Window, atoms: &Atoms) -> FocusedWindow {
+    let property = |property: Atom, kind: Atom| {
+        connection
+            .get_property(false, window, property, kind, 0, 1024)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+    };
+
+    let title = property(atoms._NET_WM_NAME, atoms.UTF8_STRING)
+        .filter(|reply| !reply.value.is_empty())
+        .or_else(|| property(AtomEnum::WM_NAME.into(), AtomEnum::STRING.into()))
+        .map(|reply| String::from_utf8_lossy(&reply.value).into_owned())
+        .unwrap_or_default();
+    // WM_CLASS : nom d'instance puis nom de classe, séparés par des zéros
+    let class = property(AtomEnum::WM_CLASS.into(), AtomEnum::STRING.into())
+        .map(|reply| {
+            let names: Vec<String> = reply
+                .value
+                .split(|byte| *byte == 0)
+                .filter(|name| !name.is_empty())
+                .map(|name| String::from_utf8_lossy(name).into_owned())
+                .collect();
+            names.join(" ")
+        })
+        .unwrap_or_default();
+    let process = property(atoms._NET_WM_PID, AtomEnum::CARDINAL.into())
+        .and_then(|reply| reply.value32().and_then(|mut values| values.next()))
+        .map(process_name)
+        .unwrap_or_default();
+
+    FocusedWindow { process, class, title }
+}
+
+/// Nom de l'exécutable d'un processus (lien /proc/<pid>/exe, sinon /proc/<pid>/comm)
+fn process_name(pid: u32) -> String {
+    std::fs::read_link(format!("/proc/{}/exe", pid))
+        .ok()
+        .and_then(|path| path.file_name().map(|name| name.to_string_lossy().into_owned()))
+        .or_else(|| std::fs::read_to_string(format!("/proc/{}/comm", pid)).ok().map(|name| name.trim().to_string()))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn process_names_come_from_proc() {
+        assert_eq!(process_name(std::process::id()), std::env::current_exe().unwrap().file_name().unwrap().to_string_lossy());
+        assert_eq!(process_name(u32::MAX), "");
+    }
+}
@@ -0,0 +1,149 @@
+use crate::config::window::Position;
+
+/// Images par seconde de référence pour le lissage (`smoothing` = part gardée à chaque image)
+const SMOOTHING_FRAME_RATE: f32 = 60.0;
+/// Distance en pixels sous laquelle la fenêtre rejoint directement le curseur
+const SNAP_DISTANCE: f64 = 0.5;
+
+/// Place la fenêtre sur le curseur (mode `window.position.follow_cursor`)
+///
+/// Le centre de la fenêtre suit le curseur décalé de `offset_x` / `offset_y`
+/// (pixels logiques, mis à l'échelle de l'écran), avec un lissage exponentiel optionnel qui dépend du temps écoulé et non du
+/// nombre d'images.
+#[derive(Debug, Clone, Default)]
+pub struct CursorFollower {
+    center: Option<(f64, f64)>, // Centre actuel de la fenêtre (lissé)
+    target: Option<(f64, f64)>, // Centre visé : curseur + décalage
+    placed: Option<(i32, i32)>, // Dernière position donnée à la fenêtre
+    last_time: Option<f32>,
+}
+
+impl CursorFollower {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Oublie la position courante : la prochaine mise à jour place la fenêtre sans lissage
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Coin supérieur gauche de la fenêtre pour ce curseur, si la fenêtre doit bouger
+    ///
+    /// `cursor` et `window_size` sont en pixels physiques, `scale_factor` est celui de
+    /// l'écran de la fenêtre et `now` vient de l'horloge des effets.
+    pub fn update(&mut self, cursor: (i32, i32), position: &Position, window_size: (u32, u32), scale_factor: f64, now: f32) -> Option<(i32, i32)> {
+        let target = (
+            f64::from(cursor.0) + f64::from(position.offset_x) * scale_factor,
+            f64::from(cursor.1) + f64::from(position.offset_y) * scale_factor,
+        );
+        let elapsed = self.last_time.map_or(0.0, |last| now - last);
+        if now.is_finite() {
+            self.last_time = Some(now);
+        }
+        self.target = Some(target);
+
+        let center = match self.center {
+            Some(center) if position.smoothing > 0.0 => {
+                let smoothing = position.smoothing.clamp(0.0, 0.99);
+                // Temps négatif ou NaN : pas d'avancée
+                let frames = if elapsed > 0.0 { elapsed * SMOOTHING_FRAME_RATE } else { 0.0 };
+                let keep = f64::from(smoothing.powf(frames));
+                let smoothed = (
+                    target.0 + (center.0 - target.0) * keep,
+                    target.1 + (center.1 - target.1) * keep,
+                );
+                if (smoothed.0 - target.0).hypot(smoothed.1 - target.1) < SNAP_DISTANCE {
+                    target
+                } else {
+                    smoothed
+                }
+            }
+            _ => target,
+        };
+        self.center = Some(center);
+
+        let top_left = (
+            (center.0 - f64::from(window_size.0) / 2.0).round() as i32,
+            (center.1 - f64::from(window_size.1) / 2.0).round() as i32,
+        );
+        if self.placed == Some(top_left) {
+            return None;
+        }
+        self.placed = Some(top_left);
+        Some(top_left)
+    }
+
+    /// La fenêtre n'a pas encore été placée, ou le lissage n'a pas rejoint le curseur
+    pub fn is_settling(&self) -> bool {
+        self.center.is_none() || self.center != self.target
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(offset: (i32, i32), smoothing: f32) -> Position {
+        Position {
+            follow_cursor: true,
+            offset_x: offset.0,
+            offset_y: offset.1,
+            smoothing,
+            ..Position::default()
+        }
+    }
+
+    #[test]
+    fn window_is_centered_on_the_offset_cursor() {
+        let mut follower = CursorFollower::new();
+        let position = position((10, -20), 0.0);
+        assert_eq!(follower.update((500, 300), &position, (100, 100), 1.0, 0.0), Some((460, 230)));
+        assert!(!follower.is_settling());
+    }
+
+    #[test]
+    fn offset_is_scaled_with_the_monitor() {
+        let mut follower = CursorFollower::new();
+        let position = position((10, -20), 0.0);
+        assert_eq!(follower.update((500, 300), &position, (200, 200), 2.0, 0.0), Some((420, 160)));
+    }
+
+    #[test]
+    fn window_only_moves_when_the_cursor_moves() {
+        let mut follower = CursorFollower::new();
+        let position = position((0, 0), 0.0);
+        assert!(follower.update((500, 300), &position, (100, 100), 1.0, 0.0).is_some());
+        assert_eq!(follower.update((500, 300), &position, (100, 100), 1.0, 1.0), None);
+        assert_eq!(follower.update((501, 300), &position, (100, 100), 1.0, 2.0), Some((451, 250)));
+    }
+
+    #[test]
+    fn smoothing_converges_on_the_cursor() {
+        let mut follower = CursorFollower::new();
+        let position = position((0, 0), 0.5);
+        follower.update((0, 0), &position, (100, 100), 1.0, 0.0);
+
+        // Une image à 60 FPS : la moitié du chemin
+        assert_eq!(follower.update((200, 0), &position, (100, 100), 1.0, 1.0 / 60.0), Some((50, -50)));
+        assert!(follower.is_settling());
+
+        // Le lissage finit par rejoindre exactement le curseur
+        let mut time = 1.0 / 60.0;
+        while follower.is_settling() {
+            time += 1.0 / 60.0;
+            follower.update((200, 0), &position, (100, 100), 1.0, time);
+            assert!(time < 1.0, "le lissage doit rejoindre le curseur");
+        }
+        assert_eq!(follower.placed, Some((150, -50)));
+    }
+
+    #[test]
+    fn clock_going_backwards_does_not_move_the_window() {
+        let mut follower = CursorFollower::new();
+        let position = position((0, 0), 0.9);
+        follower.update((0, 0), &position, (100, 100), 1.0, 5.0);
+        assert_eq!(follower.update((300, 0), &position, (100, 100), 1.0, 1.0), None);
+        assert_eq!(follower.update((300, 0), &position, (100, 100), 1.0, f32::NAN), None);
+    }
+}
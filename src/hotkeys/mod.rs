@@ -0,0 +1,178 @@
+//! Raccourcis clavier globaux, reçus même quand le jeu a le focus
+//!
+//! Chaque plateforme fournit un [`HotkeyBackend`] : `RegisterHotKey` sous
+//! Windows, `XGrabKey` sur la fenêtre racine sous X11. Wayland n'a pas de
+//! protocole commun pour les raccourcis globaux.
+
+use std::sync::Arc;
+
+use crosshair_overlay::config::hotkeys::{Hotkey, HotkeyAction, HotkeysConfig};
+use crosshair_overlay::tr;
+
+#[cfg(windows)]
+mod windows;
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+mod x11;
+
+/// Reçoit les actions déclenchées, depuis le thread du backend
+pub type ActionSender = Arc<dyn Fn(HotkeyAction) + Send + Sync>;
+
+/// Source de raccourcis globaux propre à une plateforme
+pub trait HotkeyBackend {
+    /// Enregistre les raccourcis, à la place de ceux déjà enregistrés
+    ///
+    /// Retourne les raccourcis refusés (déjà pris par une autre application,
+    /// touche absente du clavier) avec la raison ; une erreur listant ces
+    /// raisons si aucun ne peut l'être.
+    fn register(&mut self, bindings: &[(Hotkey, HotkeyAction)], on_action: ActionSender) -> Result<Vec<(Hotkey, String)>, String>;
+
+    /// Libère tous les raccourcis
+    fn unregister(&mut self);
+}
+
+/// Backend de la plateforme, None si les raccourcis globaux n'y sont pas disponibles
+pub fn platform_backend() -> Option<Box<dyn HotkeyBackend>> {
+    #[cfg(windows)]
+    return Some(Box::new(windows::WindowsHotkeys::new()));
+
+    #[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+    return std::env::var_os("DISPLAY")
+        .is_some_and(|display| !display.is_empty())
+        .then(|| Box::new(x11::X11Hotkeys::new()) as Box<dyn HotkeyBackend>);
+
+    #[allow(unreachable_code)]
+    None
+}
+
+/// Garde les raccourcis enregistrés en accord avec la configuration
+pub struct HotkeyManager {
+    backend: Option<Box<dyn HotkeyBackend>>,
+    on_action: ActionSender,
+    registered: Vec<(Hotkey, HotkeyAction)>,
+}
+
+impl HotkeyManager {
+    pub fn new(backend: Option<Box<dyn HotkeyBackend>>, on_action: ActionSender) -> Self {
+        Self { backend, on_action, registered: Vec::new() }
+    }
+
+    /// Enregistre les raccourcis de la configuration s'ils ont changé
+    pub fn apply(&mut self, hotkeys: &HotkeysConfig) {
+        let bindings = hotkeys.bindings();
+        if bindings == self.registered {
+            return;
+        }
+        let Some(backend) = &mut self.backend else {
+            if !bindings.is_empty() {
+                eprintln!("{}", tr!("hotkeys.unsupported"));
+            }
+            self.registered = bindings;
+            return;
+        };
+
+        backend.unregister();
+        self.registered = bindings;
+        if self.registered.is_empty() {
+            return;
+        }
+        match backend.register(&self.registered, self.on_action.clone()) {
+            Ok(refused) => {
+                for (hotkey, reason) in refused {
+                    eprintln!("{}", tr!("hotkeys.refused", hotkey = hotkey, error = reason));
+                }
+            }
+            Err(e) => eprintln!("{}", tr!("hotkeys.failed", error = e)),
+        }
+    }
+
+    /// Libère les raccourcis (fermeture de l'overlay)
+    pub fn shutdown(&mut self) {
+        if let Some(backend) = &mut self.backend {
+            backend.unregister();
+        }
+        self.registered.clear();
+    }
+}
+
+/// Erreur d'un backend qui n'a enregistré aucun raccourci, avec la raison de chaque refus
+#[cfg_attr(any(target_os = "macos", target_os = "ios", target_os = "android"), allow(dead_code))]
+fn all_refused(refused: &[(Hotkey, String)]) -> String {
+    let reasons: Vec<String> = refused.iter().map(|(hotkey, reason)| format!("{}: {}", hotkey, reason)).collect();
+    tr!("hotkeys.all_refused", reasons = reasons.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// Backend qui note les appels et déclenche les actions à la demande
+    #[derive(Clone, Default)]
+    struct FakeBackend {
+        calls: Arc<Mutex<Vec<String>>>,
+        on_action: Arc<Mutex<Option<ActionSender>>>,
+    }
+
+    impl FakeBackend {
+        fn press(&self, action: HotkeyAction) {
+            let on_action = self.on_action.lock().unwrap().clone().expect("raccourcis enregistrés");
+            on_action(action);
+        }
+
+        fn calls(&self) -> Vec<String> {
+            std::mem::take(&mut self.calls.lock().unwrap())
+        }
+    }
+
+    impl HotkeyBackend for FakeBackend {
+        fn register(&mut self, bindings: &[(Hotkey, HotkeyAction)], on_action: ActionSender) -> Result<Vec<(Hotkey, String)>, String> {
+            let names: Vec<String> = bindings.iter().map(|(hotkey, _)| hotkey.to_string()).collect();
+            self.calls.lock().unwrap().push(format!("register {}", names.join(" ")));
+            *self.on_action.lock().unwrap() = Some(on_action);
+            Ok(Vec::new())
+        }
+
+        fn unregister(&mut self) {
+            self.calls.lock().unwrap().push("unregister".to_string());
+            *self.on_action.lock().unwrap() = None;
+        }
+    }
+
+    #[test]
+    fn bindings_are_registered_again_only_when_they_change() {
+        let backend = FakeBackend::default();
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let sink = received.clone();
+        let mut manager = HotkeyManager::new(Some(Box::new(backend.clone())), Arc::new(move |action| sink.lock().unwrap().push(action)));
+
+        let mut hotkeys = HotkeysConfig { enabled: true, reload: None, next_profile: None, previous_profile: None, cycle_style: None, opacity_up: None, opacity_down: None, ..HotkeysConfig::default() };
+        manager.apply(&hotkeys);
+        assert_eq!(backend.calls(), ["unregister", "register Ctrl+Alt+H Ctrl+Alt+Q"]);
+        backend.press(HotkeyAction::Quit);
+        assert_eq!(*received.lock().unwrap(), [HotkeyAction::Quit]);
+
+        // Rechargement sans changement : rien à refaire
+        manager.apply(&hotkeys.clone());
+        assert!(backend.calls().is_empty());
+
+        hotkeys.quit = "Shift+F12".parse().ok();
+        manager.apply(&hotkeys);
+        assert_eq!(backend.calls(), ["unregister", "register Ctrl+Alt+H Shift+F12"]);
+
+        hotkeys.enabled = false;
+        manager.apply(&hotkeys);
+        assert_eq!(backend.calls(), ["unregister"]);
+    }
+
+    #[test]
+    fn refusing_every_hotkey_lists_each_reason() {
+        let refused: Vec<(Hotkey, String)> = ["Ctrl+Alt+H", "Ctrl+Alt+Q"]
+            .iter()
+            .map(|text| (text.parse().unwrap(), tr!("hotkeys.already_grabbed")))
+            .collect();
+        let error = all_refused(&refused);
+        for (hotkey, reason) in &refused {
+            assert!(error.contains(&format!("{}: {}", hotkey, reason)), "{}", error);
+        }
+    }
+}
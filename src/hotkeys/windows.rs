@@ -0,0 +1,136 @@
+//! Raccourcis globaux Windows : `RegisterHotKey`
+//!
+//! Les raccourcis appartiennent au thread qui les enregistre : un thread
+//! dédié les enregistre puis lit ses messages `WM_HOTKEY` jusqu'à `WM_QUIT`.
+
+use std::sync::mpsc;
+use std::thread::JoinHandle;
+
+use windows::Win32::{
+    Foundation::{HWND, LPARAM, WPARAM},
+    System::Threading::GetCurrentThreadId,
+    UI::Input::KeyboardAndMouse::{
+        RegisterHotKey, UnregisterHotKey, HOT_KEY_MODIFIERS, MOD_ALT, MOD_CONTROL, MOD_NOREPEAT, MOD_SHIFT, MOD_WIN,
+        VIRTUAL_KEY, VK_ADD, VK_BACK, VK_DELETE, VK_DOWN, VK_END, VK_ESCAPE, VK_F1, VK_HOME, VK_INSERT, VK_LEFT, VK_NEXT,
+        VK_PAUSE, VK_PRIOR, VK_RETURN, VK_RIGHT, VK_SCROLL, VK_SNAPSHOT, VK_SPACE, VK_SUBTRACT, VK_TAB, VK_UP,
+    },
+    UI::WindowsAndMessaging::{GetMessageW, PostThreadMessageW, MSG, WM_HOTKEY, WM_QUIT},
+};
+
+use crosshair_overlay::config::hotkeys::{Hotkey, HotkeyAction, HotkeyKey, Modifiers, NamedKey};
+
+use super::{all_refused, ActionSender, HotkeyBackend};
+
+/// Thread qui possède les raccourcis enregistrés
+struct Registration {
+    thread_id: u32,
+    thread: JoinHandle<()>,
+}
+
+pub struct WindowsHotkeys {
+    registration: Option<Registration>,
+}
+
+impl WindowsHotkeys {
+    pub fn new() -> Self {
+        Self { registration: None }
+    }
+}
+
+impl HotkeyBackend for WindowsHotkeys {
+    fn register(&mut self, bindings: &[(Hotkey, HotkeyAction)], on_action: ActionSender) -> Result<Vec<(Hotkey, String)>, String> {
+        self.unregister();
+        let count = bindings.len();
+        let bindings = bindings.to_vec();
+        let (sender, receiver) = mpsc::channel();
+
+        let thread = std::thread::spawn(move || unsafe {
+            let mut refused = Vec::new();
+            let mut registered = Vec::new();
+            for (id, (hotkey, _)) in bindings.iter().enumerate() {
+                // MOD_NOREPEAT : pas de nouvel événement tant que la touche reste enfoncée
+                match RegisterHotKey(HWND::default(), id as i32, modifiers(hotkey.modifiers) | MOD_NOREPEAT, u32::from(virtual_key(hotkey.key).0)) {
+                    Ok(()) => registered.push(id),
+                    Err(e) => refused.push((*hotkey, e.message())),
+                }
+            }
+            let _ = sender.send((GetCurrentThreadId(), refused));
+
+            let mut message = MSG::default();
+            while GetMessageW(&mut message, HWND::default(), 0, 0).as_bool() {
+                if message.message == WM_HOTKEY {
+                    if let Some((_, action)) = bindings.get(message.wParam.0) {
+                        on_action(*action);
+                    }
+                }
+            }
+            for id in registered {
+                let _ = UnregisterHotKey(HWND::default(), id as i32);
+            }
+        });
+
+        let (thread_id, refused) = receiver.recv().map_err(|e| e.to_string())?;
+        self.registration = Some(Registration { thread_id, thread });
+        if refused.len() == count {
+            self.unregister();
+            return Err(all_refused(&refused));
+        }
+        Ok(refused)
+    }
+
+    fn unregister(&mut self) {
+        let Some(registration) = self.registration.take() else {
+            return;
+        };
+        // WM_QUIT termine la boucle de messages du thread, qui libère ses raccourcis
+        if unsafe { PostThreadMessageW(registration.thread_id, WM_QUIT, WPARAM(0), LPARAM(0)) }.is_ok() {
+            let _ = registration.thread.join();
+        }
+    }
+}
+
+impl Drop for WindowsHotkeys {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
+
+fn modifiers(modifiers: Modifiers) -> HOT_KEY_MODIFIERS {
+    let mut flags = HOT_KEY_MODIFIERS(0);
+    for (held, flag) in [(modifiers.ctrl, MOD_CONTROL), (modifiers.alt, MOD_ALT), (modifiers.shift, MOD_SHIFT), (modifiers.super_key, MOD_WIN)] {
+        if held {
+            flags |= flag;
+        }
+    }
+    flags
+}
+
+/// Code de touche virtuelle (les lettres et chiffres ont leur code ASCII majuscule)
+fn virtual_key(key: HotkeyKey) -> VIRTUAL_KEY {
+    match key {
+        HotkeyKey::Char(c) => VIRTUAL_KEY(c.to_ascii_uppercase() as u16),
+        HotkeyKey::Function(number) => VIRTUAL_KEY(VK_F1.0 + u16::from(number) - 1),
+        HotkeyKey::Named(key) => match key {
+            NamedKey::Escape => VK_ESCAPE,
+            NamedKey::Space => VK_SPACE,
+            NamedKey::Tab => VK_TAB,
+            NamedKey::Enter => VK_RETURN,
+            NamedKey::Backspace => VK_BACK,
+            NamedKey::Insert => VK_INSERT,
+            NamedKey::Delete => VK_DELETE,
+            NamedKey::Home => VK_HOME,
+            NamedKey::End => VK_END,
+            NamedKey::PageUp => VK_PRIOR,
+            NamedKey::PageDown => VK_NEXT,
+            NamedKey::Up => VK_UP,
+            NamedKey::Down => VK_DOWN,
+            NamedKey::Left => VK_LEFT,
+            NamedKey::Right => VK_RIGHT,
+            NamedKey::Pause => VK_PAUSE,
+            NamedKey::ScrollLock => VK_SCROLL,
+            NamedKey::PrintScreen => VK_SNAPSHOT,
+            NamedKey::NumpadAdd => VK_ADD,
+            NamedKey::NumpadSubtract => VK_SUBTRACT,
+        },
+    }
+}
@@ -0,0 +1,223 @@
+//! Raccourcis globaux X11 : `XGrabKey` sur la fenêtre racine
+//!
+//! Les touches sont saisies sur une connexion dédiée, lue par un thread. Le
+//! serveur libère les saisies à la fermeture de la connexion.
+
+use std::sync::Arc;
+use std::thread::JoinHandle;
+
+use x11rb::connection::Connection;
+use x11rb::errors::ReplyError;
+use x11rb::protocol::xproto::{
+    AtomEnum, ClientMessageEvent, ConnectionExt as _, CreateWindowAux, EventMask, GrabMode, Keycode, ModMask, Window,
+    WindowClass,
+};
+use x11rb::protocol::{ErrorKind, Event};
+use x11rb::rust_connection::RustConnection;
+
+use crosshair_overlay::config::hotkeys::{Hotkey, HotkeyAction, HotkeyKey, Modifiers, NamedKey};
+use crosshair_overlay::tr;
+
+use super::{all_refused, ActionSender, HotkeyBackend};
+
+/// Modificateurs comparés aux raccourcis ; les boutons de la souris (bits suivants) sont ignorés
+const MODIFIER_BITS: u16 = 0xFF;
+
+/// Connexion et thread de lecture des raccourcis enregistrés
+struct Grabs {
+    connection: Arc<RustConnection>,
+    wakeup: Window, // Fenêtre invisible qui reçoit le message d'arrêt du thread
+    thread: JoinHandle<()>,
+}
+
+pub struct X11Hotkeys {
+    grabs: Option<Grabs>,
+}
+
+impl X11Hotkeys {
+    pub fn new() -> Self {
+        Self { grabs: None }
+    }
+}
+
+impl HotkeyBackend for X11Hotkeys {
+    fn register(&mut self, bindings: &[(Hotkey, HotkeyAction)], on_action: ActionSender) -> Result<Vec<(Hotkey, String)>, String> {
+        self.unregister();
+        let (connection, screen_num) = x11rb::connect(None).map_err(|e| e.to_string())?;
+        let root = connection.setup().roots[screen_num].root;
+        let keycodes = keycode_table(&connection).map_err(|e| e.to_string())?;
+
+        let mut refused = Vec::new();
+        let mut grabbed: Vec<(Keycode, ModMask, HotkeyAction)> = Vec::new();
+        for (hotkey, action) in bindings {
+            let Some(keycode) = keycodes.find(keysym(hotkey.key)) else {
+                refused.push((*hotkey, tr!("hotkeys.key_missing")));
+                continue;
+            };
+            let modifiers = mod_mask(hotkey.modifiers);
+            // Une saisie par combinaison de verrous : le raccourci doit marcher avec Verr. Maj et Verr. Num
+            let locks = [ModMask::from(0u16), ModMask::LOCK, ModMask::M2, ModMask::LOCK | ModMask::M2];
+            let mut variants = Vec::new();
+            let result: Result<(), ReplyError> = locks.into_iter().try_for_each(|locks| {
+                connection
+                    .grab_key(true, root, modifiers | locks, keycode, GrabMode::ASYNC, GrabMode::ASYNC)?
+                    .check()?;
+                variants.push(modifiers | locks);
+                Ok(())
+            });
+            if result.is_err() {
+                // Raccourci refusé : libérer les combinaisons déjà saisies
+                for variant in variants {
+                    let _ = connection.ungrab_key(keycode, root, variant);
+                }
+            }
+            match result {
+                Ok(()) => grabbed.push((keycode, modifiers, *action)),
+                Err(ReplyError::X11Error(error)) if error.error_kind == ErrorKind::Access => {
+                    refused.push((*hotkey, tr!("hotkeys.already_grabbed")))
+                }
+                Err(e) => refused.push((*hotkey, e.to_string())),
+            }
+        }
+        if grabbed.is_empty() {
+            return Err(all_refused(&refused));
+        }
+
+        let wakeup = connection.generate_id().map_err(|e| e.to_string())?;
+        connection
+            .create_window(0, wakeup, root, 0, 0, 1, 1, 0, WindowClass::INPUT_ONLY, 0, &CreateWindowAux::new())
+            .map_err(|e| e.to_string())?;
+        connection.flush().map_err(|e| e.to_string())?;
+
+        let connection = Arc::new(connection);
+        let reader = connection.clone();
+        let thread = std::thread::spawn(move || read_events(&reader, wakeup, &grabbed, &on_action));
+        self.grabs = Some(Grabs { connection, wakeup, thread });
+        Ok(refused)
+    }
+
+    fn unregister(&mut self) {
+        let Some(grabs) = self.grabs.take() else {
+            return;
+        };
+        // Réveiller le thread bloqué dans wait_for_event, puis fermer la connexion
+        let event = ClientMessageEvent::new(32, grabs.wakeup, AtomEnum::NONE, [0u32; 5]);
+        let sent = grabs.connection.send_event(false, grabs.wakeup, EventMask::NO_EVENT, event).is_ok();
+        if sent && grabs.connection.flush().is_ok() {
+            let _ = grabs.thread.join();
+        }
+    }
+}
+
+impl Drop for X11Hotkeys {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
+
+/// Boucle du thread : une action par appui, sans les répétitions automatiques
+fn read_events(connection: &RustConnection, wakeup: Window, grabbed: &[(Keycode, ModMask, HotkeyAction)], on_action: &ActionSender) {
+    let locks = u16::from(ModMask::LOCK | ModMask::M2);
+    let mut last_release = None;
+    while let Ok(event) = connection.wait_for_event() {
+        match event {
+            // Une répétition automatique envoie un relâchement et un appui au même instant
+            Event::KeyPress(press) if last_release != Some((press.detail, press.time)) => {
+                let state = u16::from(press.state) & MODIFIER_BITS & !locks;
+                if let Some((_, _, action)) = grabbed.iter().find(|(keycode, modifiers, _)| *keycode == press.detail && u16::from(*modifiers) == state) {
+                    on_action(*action);
+                }
+            }
+            Event::KeyRelease(release) => last_release = Some((release.detail, release.time)),
+            Event::ClientMessage(message) if message.window == wakeup => break,
+            _ => {}
+        }
+    }
+}
+
+/// Keysyms de chaque keycode du clavier
+struct KeycodeTable {
+    min_keycode: Keycode,
+    keysyms_per_keycode: usize,
+    keysyms: Vec<u32>,
+}
+
+impl KeycodeTable {
+    fn find(&self, keysym: u32) -> Option<Keycode> {
+        let per_keycode = self.keysyms_per_keycode.max(1);
+        self.keysyms
+            .chunks(per_keycode)
+            .position(|keysyms| keysyms.contains(&keysym))
+            .and_then(|index| Keycode::try_from(usize::from(self.min_keycode) + index).ok())
+    }
+}
+
+fn keycode_table(connection: &RustConnection) -> Result<KeycodeTable, ReplyError> {
+    let setup = connection.setup();
+    let count = setup.max_keycode - setup.min_keycode + 1;
+    let mapping = connection.get_keyboard_mapping(setup.min_keycode, count)?.reply()?;
+    Ok(KeycodeTable {
+        min_keycode: setup.min_keycode,
+        keysyms_per_keycode: usize::from(mapping.keysyms_per_keycode),
+        keysyms: mapping.keysyms,
+    })
+}
+
+fn mod_mask(modifiers: Modifiers) -> ModMask {
+    let mut mask = ModMask::from(0u16);
+    for (held, modifier) in [(modifiers.ctrl, ModMask::CONTROL), (modifiers.alt, ModMask::M1), (modifiers.shift, ModMask::SHIFT), (modifiers.super_key, ModMask::M4)] {
+        if held {
+            mask |= modifier;
+        }
+    }
+    mask
+}
+
+/// Keysym X11 de la touche (minuscule pour les lettres, comme dans la table du clavier)
+fn keysym(key: HotkeyKey) -> u32 {
+    match key {
+        HotkeyKey::Char(c) => u32::from(c.to_ascii_lowercase()),
+        HotkeyKey::Function(number) => 0xFFBE + u32::from(number) - 1,
+        HotkeyKey::Named(key) => match key {
+            NamedKey::Escape => 0xFF1B,
+            NamedKey::Space => 0x0020,
+            NamedKey::Tab => 0xFF09,
+            NamedKey::Enter => 0xFF0D,
+            NamedKey::Backspace => 0xFF08,
+            NamedKey::Insert => 0xFF63,
+            NamedKey::Delete => 0xFFFF,
+            NamedKey::Home => 0xFF50,
+            NamedKey::End => 0xFF57,
+            NamedKey::PageUp => 0xFF55,
+            NamedKey::PageDown => 0xFF56,
+            NamedKey::Left => 0xFF51,
+            NamedKey::Up => 0xFF52,
+            NamedKey::Right => 0xFF53,
+            NamedKey::Down => 0xFF54,
+            NamedKey::Pause => 0xFF13,
+            NamedKey::ScrollLock => 0xFF14,
+            NamedKey::PrintScreen => 0xFF61,
+            NamedKey::NumpadAdd => 0xFFAB,
+            NamedKey::NumpadSubtract => 0xFFAD,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hotkeys_map_to_keysyms_and_modifiers() {
+        let hotkey: Hotkey = "Ctrl+Alt+Super+H".parse().unwrap();
+        assert_eq!(keysym(hotkey.key), 0x68);
+        assert_eq!(mod_mask(hotkey.modifiers), ModMask::CONTROL | ModMask::M1 | ModMask::M4);
+        assert_eq!(keysym("F12".parse().unwrap()), 0xFFC9);
+        assert_eq!(keysym("7".parse().unwrap()), 0x37);
+
+        let table = KeycodeTable { min_keycode: 8, keysyms_per_keycode: 2, keysyms: vec![0x61, 0x41, 0xFFBE, 0] };
+        assert_eq!(table.find(0x61), Some(8));
+        assert_eq!(table.find(0xFFBE), Some(9));
+        assert_eq!(table.find(0xFF1B), None);
+    }
+}
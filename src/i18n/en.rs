@@ -0,0 +1,280 @@
+//! Messages en anglais (langue de repli)
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Overlay
+    ("app.config_file", "📄 Configuration: {location}"),
+    ("app.click_through_unsupported", "⚠️  Click-through not supported on this platform: {error}"),
+    ("app.window_failed", "❌ Cannot create the overlay window: {error}"),
+    ("app.window_recreate_failed", "⚠️  Cannot recreate the window at its new size, keeping the previous one: {error}"),
+    ("app.mouse_capture_failed", "❌ Failed to start mouse capture: {error}"),
+    ("app.reloading", "🔄 Reloading configuration..."),
+    ("app.follow_cursor_unavailable", "⚠️  follow_cursor needs the global mouse capture: the window stays in place"),
+    ("app.monitor_not_found", "⚠️  Monitor '{monitor}' not found, using the primary monitor"),
+    ("app.opacity", "🔆 Opacity: {percent} %"),
+    ("app.layer_shell_active", "🪟 Wayland layer-shell overlay on output {output}"),
+    ("app.x11_active", "🪟 X11 override-redirect overlay on monitor {output}"),
+    ("app.native_fallback", "⚠️  {backend} overlay unavailable ({error}), using a winit window"),
+    ("app.native_failed", "❌ {backend} overlay error: {error}"),
+    ("app.native_controls", "⚠️  No window keys, cursor following or mouse effects with {backend}: use the global hotkeys"),
+    ("app.no_profiles", "⚠️  No profile available"),
+    ("app.profile_switch_failed", "⚠️  Cannot switch profile: {error}"),
+    ("app.active_profile", "🎯 Active profile: {name}"),
+    ("app.base_configuration", "base configuration"),
+    ("app.crosshair_hidden", "🙈 Crosshair hidden"),
+    ("app.crosshair_shown", "🎯 Crosshair shown"),
+    ("app.style", "🎨 Style: {style}"),
+    // Présentation au démarrage
+    ("banner.title", "🎯 Crosshair Overlay Pro - Maximum Performance Edition!"),
+    ("banner.features", "📋 Features:"),
+    ("banner.feature_config", "   ✅ Modular configuration via {path}"),
+    ("banner.feature_window", "   ✅ Configurable transparent window"),
+    ("banner.feature_style", "   ✅ Customizable crosshair (style: {style})"),
+    ("banner.feature_effects", "   ✅ Visual effects (pulse, shake, rainbow)"),
+    ("banner.feature_rotation", "   ✅ Configurable rotation and alpha"),
+    ("banner.feature_outline", "   ✅ Advanced outline and center dot"),
+    ("banner.feature_click_through", "   ✅ Click-through enabled"),
+    ("banner.feature_mouse", "   🖱️  Mouse capture (left, right and middle click)"),
+    ("banner.feature_performance", "   ⚡ Maximum performance - ultra low CPU usage"),
+    ("banner.styles", "🎨 Available styles: {styles}"),
+    ("banner.effects", "✨ Available effects:"),
+    ("banner.pulse", "   • Pulse: {enabled} (alpha {min}-{max})"),
+    ("banner.shake", "   • Shake: {enabled} (intensity: {intensity}, speed: {speed})"),
+    ("banner.rainbow", "   • Rainbow: {enabled} (saturation: {saturation}, brightness: {brightness})"),
+    ("banner.mouse_effects", "   • Mouse effects: {enabled}"),
+    ("banner.controls", "⌨️  Controls:"),
+    ("banner.control_hotkey", "   • {hotkey}: {action} (global)"),
+    ("banner.control_reload", "   • F5: Reload configuration"),
+    ("banner.control_profiles", "   • F6 / F7: Next / previous profile"),
+    ("banner.control_digits", "   • 1-9: Jump to a profile"),
+    ("banner.control_opacity", "   • + / -: Increase / decrease opacity"),
+    ("banner.profiles", "📁 Available profiles: {names}"),
+    ("banner.edit_hint", "💡 Edit {path} to explore every option!"),
+    ("banner.shake_hint", "💡 To enable shake: [effects.shake] enabled = true"),
+    // Emplacement de la configuration
+    ("location.command_line", "--config option"),
+    ("location.environment", "CROSSHAIR_CONFIG variable"),
+    ("location.user_directory", "user configuration directory"),
+    ("location.working_directory", "current directory"),
+    ("location.system_directory", "system configuration directory"),
+    ("location.new_file", "new file"),
+    // Chargement de la configuration
+    ("config.load_error", "⚠️  Error while loading {path}: {error}"),
+    ("config.using_defaults", "📝 Using the default configuration"),
+    ("config.create_failed", "⚠️  Cannot create {path}: {error}"),
+    ("config.created", "✅ File {path} created with the default configuration"),
+    ("config.profile_error", "⚠️  Error while loading profile '{name}': {error}"),
+    ("config.variable_ignored", "⚠️  Variable ignored: {error}"),
+    ("config.command_line_ignored", "⚠️  Command line options ignored: {error}"),
+    // Validation
+    ("validate.crosshair_size", "Crosshair size cannot be 0"),
+    ("validate.crosshair_thickness", "Crosshair thickness cannot be 0"),
+    ("validate.crosshair_alpha", "Crosshair alpha must be between 0.0 and 1.0"),
+    ("validate.pulse_min_alpha", "Pulse minimum alpha must be between 0.0 and 1.0"),
+    ("validate.pulse_max_alpha", "Pulse maximum alpha must be between 0.0 and 1.0"),
+    ("validate.window_size", "Window size cannot be 0"),
+    ("validate.window_opacity", "Window opacity must be between 0.0 and 1.0"),
+    ("validate.hotkey_duplicate", "Hotkey {hotkey} is bound to several actions"),
+    // Profils
+    ("profiles.not_found", "Profile '{name}' not found in {directory}"),
+    ("profiles.no_profile_at", "No profile at position {position}"),
+    // Overlay Wayland layer-shell
+    ("layer_shell.unsupported", "the compositor does not support wlr-layer-shell: {error}"),
+    ("layer_shell.unsupported_platform", "Wayland and X11 do not exist on this platform"),
+    ("layer_shell.draw_failed", "❌ Layer-shell rendering error: {error}"),
+    ("x11.no_argb_visual", "the X server has no 32-bit ARGB visual"),
+    ("x11.no_shape", "the X server does not support the SHAPE extension"),
+    ("x11.protocol_error", "⚠️  X11 error: {error}"),
+    ("focus.unsupported", "⚠️  Focus rules are not available here (Windows or an X11 session is required)"),
+    ("focus.failed", "❌ Focus watcher error: {error}"),
+    ("focus.profile", "🎯 {application} is in the foreground: profile {name}"),
+    ("focus.profile_failed", "⚠️  Focus rule: {error}"),
+    ("hotkeys.unsupported", "⚠️  Global hotkeys are not available here (Windows or an X11 session is required)"),
+    ("hotkeys.refused", "⚠️  Hotkey {hotkey} unavailable: {error}"),
+    ("hotkeys.failed", "❌ Global hotkeys error: {error}"),
+    ("hotkeys.all_refused", "no hotkey could be registered ({reasons})"),
+    ("hotkeys.key_missing", "the key is not on the keyboard"),
+    ("hotkeys.already_grabbed", "already used by another application"),
+    ("hotkey_action.toggle_visibility", "Show / hide the crosshair"),
+    ("hotkey_action.reload", "Reload configuration"),
+    ("hotkey_action.next_profile", "Next profile"),
+    ("hotkey_action.previous_profile", "Previous profile"),
+    ("hotkey_action.cycle_style", "Next crosshair style"),
+    ("hotkey_action.opacity_up", "Increase the opacity"),
+    ("hotkey_action.opacity_down", "Decrease the opacity"),
+    ("hotkey_action.quit", "Quit"),
+    // Capture de la souris
+    ("mouse.unsupported", "Global mouse capture is not supported on this platform"),
+    ("mouse.hook_failed", "Failed to install the mouse hook"),
+    // Ligne de commande
+    ("cli.profile_error", "❌ Profile '{name}': {error}"),
+    ("cli.image_size_invalid", "❌ The image size must be between 1 and {max} pixels (got {size})"),
+    ("cli.image_saved", "🖼️  {size}x{size} image saved: {path}"),
+    ("cli.write_failed", "❌ Cannot write {path}: {error}"),
+    ("cli.valid", "✅ Valid configuration: {path}"),
+    ("cli.invalid", "❌ {count} error(s) in {path}:"),
+    ("cli.converted", "✅ {input} converted to {output}"),
+    ("cli.preset_at_root", "at the root of the file"),
+    ("cli.preset_in_section", "in [{section}]"),
+    ("cli.preset_kind", "🎨 {kind} — {key} = \"...\" {location}"),
+    ("cli.user_preset", "{description} (user: {path})"),
+    ("cli.lossy_conversion", "⚠️  Approximate conversion:"),
+    ("cli.imported", "✅ Configuration imported: {path}"),
+    ("cli.invalid_code", "❌ The code contains an invalid configuration ({count} error(s)):"),
+    // Aide de la ligne de commande
+    ("help.about", "Transparent and customizable crosshair overlay"),
+    ("help.config", "Configuration file (otherwise CROSSHAIR_CONFIG or the standard directories)"),
+    ("help.run", "Run the overlay (default command)"),
+    ("help.render", "Render the crosshair to a PNG image without opening a window"),
+    ("help.validate", "Check a configuration and print the diagnostics"),
+    ("help.explain", "Print the effective values and where they come from (default, file, environment, options)"),
+    ("help.print_default", "Print the commented configuration template"),
+    ("help.print_schema", "Print the JSON schema of the configuration (completion in editors)"),
+    ("help.convert", "Convert a configuration to another format (from the extension)"),
+    ("help.presets", "List the built-in and user presets"),
+    ("help.export_code", "Print the share code of the configuration (crosshair, effects and window)"),
+    ("help.import_code", "Create a configuration from a share code (XH1-...)"),
+    ("help.import_cs2", "Create a configuration from a CS2 share code (CSGO-xxxxx-...)"),
+    ("help.export_cs2", "Print the CS2 share code of the configuration"),
+    ("help.import_valorant", "Create a configuration from a Valorant profile code (0;P;...)"),
+    ("help.export_valorant", "Print the Valorant profile code of the configuration"),
+    ("help.render_output", "PNG image to create"),
+    ("help.window_size", "Image size in pixels (default: the window size, window.size or auto_size)"),
+    ("help.time", "Animation time in seconds (pulse, shake, rainbow)"),
+    ("help.validate_path", "File to check alone, without profile or variables (default: the active configuration)"),
+    ("help.all", "Also print the default values"),
+    ("help.convert_input", "Source file"),
+    ("help.convert_output", "Destination file"),
+    ("help.preset_kind", "Preset type (config, crosshair, pulse, shake, rainbow, gap_effect, visibility_effect)"),
+    ("help.code", "Share code to import"),
+    ("help.import_output", "File to create (format from the extension), otherwise printed as TOML."),
+    ("help.style", "Crosshair style (classic, dot, circle, t, x, square, diamond)"),
+    ("help.color", "Crosshair color (e.g. \"#00FF00\", \"red\", \"rgb(0, 255, 0)\")"),
+    ("help.size", "Crosshair size in pixels"),
+    ("help.opacity", "Global window opacity (0.0 to 1.0)"),
+    ("help.value.path", "PATH"),
+    ("help.value.file", "FILE"),
+    ("help.value.seconds", "SECONDS"),
+    ("help.value.color", "COLOR"),
+    ("help.value.opacity", "OPACITY"),
+    // Rendu en image
+    ("render.image_size", "image size {size} out of range (1 to {max} pixels)"),
+    // Couleurs
+    ("color.components", "{function}() takes 3 components and an optional alpha"),
+    ("color.hue", "invalid hue '{value}'"),
+    ("color.percentage", "'{value}' must be a percentage"),
+    ("color.unknown_function", "unknown color function '{function}()'"),
+    ("color.percentage_range", "'{value}' must be between 0% and 100%"),
+    ("color.range", "'{value}' must be between 0 and {max}"),
+    ("color.invalid", "invalid color '{value}': {detail}"),
+    ("color.hex_length", "expected #RGB, #RGBA, #RRGGBB or #RRGGBBAA"),
+    ("color.closing_parenthesis", "missing closing parenthesis"),
+    ("color.expected", "expected a hex code (#00FF00), rgb(), rgba(), hsl(), hsla() or a CSS name"),
+    // Héritage (extends, include)
+    ("inheritance.read_failed", "{path}: cannot read: {error}"),
+    ("inheritance.invalid_directive", "{path}: invalid `{key}`: {error}"),
+    ("inheritance.cycle", "circular inheritance: {chain}"),
+    ("inheritance.path_items", "items must be paths (strings)"),
+    ("inheritance.paths_expected", "expected a path or a list of paths"),
+    ("inheritance.path_expected", "expected a path (string)"),
+    // Formats de fichier
+    ("format.unsupported", "{path}: unsupported format (known extensions: {extensions})"),
+    ("format.not_a_table", "the document must be a table of settings"),
+    // Couches de configuration
+    ("layer.defaults", "default"),
+    ("layer.file", "file {path}"),
+    ("layer.preset", "{kind} preset {name}"),
+    ("layer.profile", "profile {name}"),
+    ("layer.environment", "variable {variable}"),
+    ("layer.command_line", "command line"),
+    ("layer.unknown_field", "unknown field `{key}`"),
+    ("layer.invalid_variable", "invalid variable name (expected CROSSHAIR__SECTION__FIELD)"),
+    // Presets
+    ("presets.unknown_kind", "unknown preset type '{kind}' ({kinds})"),
+    ("presets.ignored", "⚠️  Preset ignored {path}: {error}"),
+    ("presets.description_string", "`{key}` must be a string"),
+    ("presets.name_string", "`{key}` must be a preset name (string)"),
+    ("presets.too_deep", "`{key}`: presets nested too deeply (cycle?)"),
+    ("presets.unknown", "`{key}`: unknown {kind} preset '{name}' (available: {available})"),
+    ("preset.config.sniper", "Red dot, wide gap and hidden center on click"),
+    ("preset.config.precision", "Discreet crosshair that tightens on click"),
+    ("preset.config.neon", "Cyan circle with a slow rainbow effect"),
+    ("preset.config.rainbow_pulse", "Pulsing rainbow circle"),
+    ("preset.crosshair.discrete", "Small white classic with an outline"),
+    ("preset.crosshair.triangle", "Classic with triangular bars"),
+    ("preset.crosshair.filled_circle", "Filled magenta circle"),
+    ("preset.crosshair.sniper_dot", "Red dot only"),
+    ("preset.crosshair.rotating_square", "Square rotated by 45°"),
+    ("preset.crosshair.diamond", "Magenta diamond with an outline"),
+    ("preset.crosshair.large", "Large, highly visible classic"),
+    ("preset.pulse.breathing", "Slow pulsing"),
+    ("preset.pulse.alert", "Fast blinking"),
+    ("preset.shake.subtle", "Barely visible shaking"),
+    ("preset.shake.strong", "Fast shaking"),
+    ("preset.rainbow.neon", "Slow color cycling"),
+    ("preset.rainbow.fast", "Fast color cycling"),
+    ("preset.gap_effect.sniper", "Widens the crosshair a lot on click"),
+    ("preset.gap_effect.precision", "Tightens the crosshair on click"),
+    ("preset.visibility_effect.sniper_clarity", "Hides the center on click"),
+    ("preset.visibility_effect.peripheral_vision", "Hides the sides on click"),
+    ("preset.visibility_effect.minimal", "Keeps only the corners on click"),
+    // Fenêtre
+    ("window.invalid_offset", "invalid offset '{value}' (expected pixels such as 25 or a percentage such as \"2.5%\")"),
+    // Raccourcis
+    ("hotkey.unknown_key", "unknown key '{key}' (letter, digit, F1-F24, Escape, Space, PageUp, Home...)"),
+    ("hotkey.invalid", "invalid hotkey '{hotkey}': {detail}"),
+    ("hotkey.missing_key", "missing key"),
+    ("hotkey.unknown_modifier", "unknown modifier '{modifier}' (Ctrl, Alt, Shift, Super)"),
+    // Styles
+    ("crosshair.unknown_style", "unknown style '{style}' (classic, dot, circle, t, x, square, diamond)"),
+    // Codes de partage
+    ("code.encode_failed", "cannot encode: {error}"),
+    ("code.invalid", "invalid code: {detail}"),
+    ("code.prefix", "a code starts with {prefix}{version}-"),
+    ("code.characters", "wrong characters (truncated code?)"),
+    ("code.too_short", "too short"),
+    ("code.checksum", "wrong checksum (truncated or modified code?)"),
+    ("code.header_mismatch", "header and content do not match"),
+    ("code.version_too_old", "version {version} code: this format is no longer read (current version: {current}), ask for a code exported with a recent version"),
+    ("code.version_too_new", "unsupported version {version} code (current version: {current}), update the application"),
+    ("code.compressed_content", "unreadable compressed content"),
+    // Conversions vers les jeux
+    ("share.style_unavailable", "style {style} not available in {game}, exported as classic"),
+    ("share.outline_color", "outline color {color} (always black in {game})"),
+    ("share.center_dot_color", "center dot color (same as the lines in {game})"),
+    ("share.rotation", "{rotation}° rotation"),
+    ("share.triangle_bars", "triangular bars"),
+    ("share.effect_ignored", "{effect} effect ignored"),
+    // Codes CS2
+    ("cs2.checksum", "invalid CS2 code: wrong checksum"),
+    ("cs2.dynamic_style", "dynamic style (cl_crosshairstyle {style}) approximated by effects.mouse.gap_effect"),
+    ("cs2.follow_recoil", "cl_crosshair_recoil (recoil following) ignored"),
+    ("cs2.weapon_gap", "cl_crosshairgap_useweaponvalue (per-weapon gap) ignored"),
+    ("cs2.gap_out_of_range", "{gap} pixel gap outside the CS2 limits"),
+    ("cs2.outline_thickness", "{thickness} pixel outline (3 at most in CS2)"),
+    ("cs2.center_dot_size", "center dot size (line thickness in CS2)"),
+    ("cs2.gap_effect", "mouse gap effect approximated by the dynamic classic style"),
+    ("cs2.prefix", "a CS2 code starts with {prefix}-"),
+    ("cs2.length", "a CS2 code has {length} characters after {prefix}-"),
+    ("cs2.character", "invalid character '{character}' in a CS2 code"),
+    ("cs2.too_large", "invalid CS2 code: value too large"),
+    // Codes Valorant
+    ("valorant.prefix", "a Valorant code starts with \"{version};\""),
+    ("valorant.section_ads", "aim down sights"),
+    ("valorant.section_sniper", "sniper scope"),
+    ("valorant.section_ignored", "section {section} ({name}) ignored"),
+    ("valorant.missing_value", "invalid Valorant code: missing value for '{key}'"),
+    ("valorant.general_ignored", "general setting '{key}' ignored"),
+    ("valorant.invalid", "invalid Valorant code: '{key};{value}' ({error})"),
+    ("valorant.unknown_color", "unknown color"),
+    ("valorant.setting_ignored", "setting '{key}' ignored"),
+    ("valorant.custom_color_missing", "invalid Valorant code: missing custom color 'u'"),
+    ("valorant.vertical_length", "separate vertical length of the {lines} lines ignored"),
+    ("valorant.movement_error", "movement error of the {lines} lines ignored"),
+    ("valorant.firing_error", "firing error of the {lines} lines approximated by effects.mouse.gap_effect"),
+    ("valorant.outer_lines", "outer lines ignored (offset {offset}, length {length}, thickness {thickness}): only one set of lines is drawn"),
+    ("valorant.color_transparency", "color transparency (use alpha)"),
+    ("valorant.gap_effect", "mouse gap effect approximated by the firing error"),
+    ("valorant.flag_expected", "expected 0 or 1"),
+    ("valorant.integer_expected", "integer expected"),
+    ("valorant.opacity_expected", "opacity between 0 and 1 expected"),
+];
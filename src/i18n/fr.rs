@@ -0,0 +1,280 @@
+//! Messages en français
+
+pub const MESSAGES: &[(&str, &str)] = &[
+    // Overlay
+    ("app.config_file", "📄 Configuration : {location}"),
+    ("app.click_through_unsupported", "⚠️  Click-through non supporté sur cette plateforme : {error}"),
+    ("app.window_failed", "❌ Impossible de créer la fenêtre de l'overlay : {error}"),
+    ("app.window_recreate_failed", "⚠️  Impossible de recréer la fenêtre à sa nouvelle taille, l'ancienne est gardée : {error}"),
+    ("app.mouse_capture_failed", "❌ Erreur lors du démarrage de la capture de souris: {error}"),
+    ("app.reloading", "🔄 Rechargement de la configuration..."),
+    ("app.follow_cursor_unavailable", "⚠️  follow_cursor nécessite la capture globale de la souris : la fenêtre reste en place"),
+    ("app.monitor_not_found", "⚠️  Écran '{monitor}' introuvable, utilisation de l'écran principal"),
+    ("app.opacity", "🔆 Opacité : {percent} %"),
+    ("app.layer_shell_active", "🪟 Overlay Wayland layer-shell sur la sortie {output}"),
+    ("app.x11_active", "🪟 Overlay X11 override-redirect sur l'écran {output}"),
+    ("app.native_fallback", "⚠️  Overlay {backend} indisponible ({error}), utilisation d'une fenêtre winit"),
+    ("app.native_failed", "❌ Erreur de l'overlay {backend} : {error}"),
+    ("app.native_controls", "⚠️  Pas de touches de fenêtre, de suivi du curseur ni d'effets de souris avec {backend} : utilisez les raccourcis globaux"),
+    ("app.no_profiles", "⚠️  Aucun profil disponible"),
+    ("app.profile_switch_failed", "⚠️  Changement de profil impossible : {error}"),
+    ("app.active_profile", "🎯 Profil actif : {name}"),
+    ("app.base_configuration", "configuration de base"),
+    ("app.crosshair_hidden", "🙈 Crosshair masqué"),
+    ("app.crosshair_shown", "🎯 Crosshair affiché"),
+    ("app.style", "🎨 Style : {style}"),
+    // Présentation au démarrage
+    ("banner.title", "🎯 Crosshair Overlay Pro - Version Performance Maximale !"),
+    ("banner.features", "📋 Fonctionnalités :"),
+    ("banner.feature_config", "   ✅ Configuration modulaire via {path}"),
+    ("banner.feature_window", "   ✅ Fenêtre transparente configurable"),
+    ("banner.feature_style", "   ✅ Crosshair personnalisable (style: {style})"),
+    ("banner.feature_effects", "   ✅ Effets visuels (pulse, shake, rainbow)"),
+    ("banner.feature_rotation", "   ✅ Rotation et alpha configurables"),
+    ("banner.feature_outline", "   ✅ Outline et centre dot avancés"),
+    ("banner.feature_click_through", "   ✅ Click-through activé"),
+    ("banner.feature_mouse", "   🖱️  Capture de souris (clic gauche, droit, molette)"),
+    ("banner.feature_performance", "   ⚡ Performance maximale - usage CPU ultra minimal"),
+    ("banner.styles", "🎨 Styles disponibles : {styles}"),
+    ("banner.effects", "✨ Effets disponibles :"),
+    ("banner.pulse", "   • Pulse: {enabled} (alpha {min}-{max})"),
+    ("banner.shake", "   • Shake: {enabled} (intensité: {intensity}, vitesse: {speed})"),
+    ("banner.rainbow", "   • Rainbow: {enabled} (saturation: {saturation}, luminosité: {brightness})"),
+    ("banner.mouse_effects", "   • Effets Souris: {enabled}"),
+    ("banner.controls", "⌨️  Contrôles :"),
+    ("banner.control_hotkey", "   • {hotkey} : {action} (global)"),
+    ("banner.control_reload", "   • F5 : Recharger la configuration"),
+    ("banner.control_profiles", "   • F6 / F7 : Profil suivant / précédent"),
+    ("banner.control_digits", "   • 1-9 : Aller directement à un profil"),
+    ("banner.control_opacity", "   • + / - : Augmenter / diminuer l'opacité"),
+    ("banner.profiles", "📁 Profils disponibles : {names}"),
+    ("banner.edit_hint", "💡 Modifiez {path} pour explorer toutes les options !"),
+    ("banner.shake_hint", "💡 Pour activer shake: [effects.shake] enabled = true"),
+    // Emplacement de la configuration
+    ("location.command_line", "option --config"),
+    ("location.environment", "variable CROSSHAIR_CONFIG"),
+    ("location.user_directory", "dossier de configuration utilisateur"),
+    ("location.working_directory", "dossier courant"),
+    ("location.system_directory", "dossier de configuration système"),
+    ("location.new_file", "nouveau fichier"),
+    // Chargement de la configuration
+    ("config.load_error", "⚠️  Erreur lors du chargement de {path}: {error}"),
+    ("config.using_defaults", "📝 Utilisation de la configuration par défaut"),
+    ("config.create_failed", "⚠️  Impossible de créer {path}: {error}"),
+    ("config.created", "✅ Fichier {path} créé avec la configuration par défaut"),
+    ("config.profile_error", "⚠️  Erreur lors du chargement du profil '{name}': {error}"),
+    ("config.variable_ignored", "⚠️  Variable ignorée : {error}"),
+    ("config.command_line_ignored", "⚠️  Options de la ligne de commande ignorées : {error}"),
+    // Validation
+    ("validate.crosshair_size", "La taille du crosshair ne peut pas être 0"),
+    ("validate.crosshair_thickness", "L'épaisseur du crosshair ne peut pas être 0"),
+    ("validate.crosshair_alpha", "L'alpha du crosshair doit être entre 0.0 et 1.0"),
+    ("validate.pulse_min_alpha", "L'alpha minimum du pulse doit être entre 0.0 et 1.0"),
+    ("validate.pulse_max_alpha", "L'alpha maximum du pulse doit être entre 0.0 et 1.0"),
+    ("validate.window_size", "La taille de la fenêtre ne peut pas être 0"),
+    ("validate.window_opacity", "L'opacité de la fenêtre doit être entre 0.0 et 1.0"),
+    ("validate.hotkey_duplicate", "Le raccourci {hotkey} est attribué à plusieurs actions"),
+    // Profils
+    ("profiles.not_found", "Profil '{name}' introuvable dans {directory}"),
+    ("profiles.no_profile_at", "Aucun profil à la position {position}"),
+    // Overlay Wayland layer-shell
+    ("layer_shell.unsupported", "le compositeur ne gère pas wlr-layer-shell : {error}"),
+    ("layer_shell.unsupported_platform", "Wayland et X11 n'existent pas sur cette plateforme"),
+    ("layer_shell.draw_failed", "❌ Erreur de rendu layer-shell : {error}"),
+    ("x11.no_argb_visual", "le serveur X n'a pas de visuel ARGB 32 bits"),
+    ("x11.no_shape", "le serveur X ne gère pas l'extension SHAPE"),
+    ("x11.protocol_error", "⚠️  Erreur X11 : {error}"),
+    ("focus.unsupported", "⚠️  Règles [focus] indisponibles ici (Windows ou une session X11 est nécessaire)"),
+    ("focus.failed", "❌ Erreur de la surveillance du focus : {error}"),
+    ("focus.profile", "🎯 {application} au premier plan : profil {name}"),
+    ("focus.profile_failed", "⚠️  Règle [focus] : {error}"),
+    ("hotkeys.unsupported", "⚠️  Raccourcis globaux indisponibles ici (Windows ou une session X11 est nécessaire)"),
+    ("hotkeys.refused", "⚠️  Raccourci {hotkey} indisponible : {error}"),
+    ("hotkeys.failed", "❌ Erreur des raccourcis globaux : {error}"),
+    ("hotkeys.all_refused", "aucun raccourci n'a pu être enregistré ({reasons})"),
+    ("hotkeys.key_missing", "la touche n'est pas sur le clavier"),
+    ("hotkeys.already_grabbed", "déjà utilisé par une autre application"),
+    ("hotkey_action.toggle_visibility", "Afficher / masquer le crosshair"),
+    ("hotkey_action.reload", "Recharger la configuration"),
+    ("hotkey_action.next_profile", "Profil suivant"),
+    ("hotkey_action.previous_profile", "Profil précédent"),
+    ("hotkey_action.cycle_style", "Style de crosshair suivant"),
+    ("hotkey_action.opacity_up", "Augmenter l'opacité"),
+    ("hotkey_action.opacity_down", "Diminuer l'opacité"),
+    ("hotkey_action.quit", "Quitter"),
+    // Capture de la souris
+    ("mouse.unsupported", "Capture globale de la souris non supportée sur cette plateforme"),
+    ("mouse.hook_failed", "Échec de l'installation du hook de souris"),
+    // Ligne de commande
+    ("cli.profile_error", "❌ Profil '{name}' : {error}"),
+    ("cli.image_size_invalid", "❌ La taille de l'image doit être comprise entre 1 et {max} pixels ({size} demandés)"),
+    ("cli.image_saved", "🖼️  Image {size}x{size} enregistrée : {path}"),
+    ("cli.write_failed", "❌ Impossible d'écrire {path} : {error}"),
+    ("cli.valid", "✅ Configuration valide : {path}"),
+    ("cli.invalid", "❌ {count} erreur(s) dans {path} :"),
+    ("cli.converted", "✅ {input} converti en {output}"),
+    ("cli.preset_at_root", "à la racine du fichier"),
+    ("cli.preset_in_section", "dans [{section}]"),
+    ("cli.preset_kind", "🎨 {kind} — {key} = \"...\" {location}"),
+    ("cli.user_preset", "{description} (utilisateur : {path})"),
+    ("cli.lossy_conversion", "⚠️  Conversion approximative :"),
+    ("cli.imported", "✅ Configuration importée : {path}"),
+    ("cli.invalid_code", "❌ Le code contient une configuration invalide ({count} erreur(s)) :"),
+    // Aide de la ligne de commande
+    ("help.about", "Crosshair overlay transparent et personnalisable"),
+    ("help.config", "Fichier de configuration (sinon CROSSHAIR_CONFIG ou les dossiers standards)"),
+    ("help.run", "Lance l'overlay (commande par défaut)"),
+    ("help.render", "Rend le crosshair dans une image PNG sans ouvrir de fenêtre"),
+    ("help.validate", "Vérifie une configuration et affiche les diagnostics"),
+    ("help.explain", "Affiche les valeurs effectives et leur origine (défaut, fichier, environnement, options)"),
+    ("help.print_default", "Affiche le modèle de configuration commenté"),
+    ("help.print_schema", "Affiche le schéma JSON de la configuration (autocomplétion dans les éditeurs)"),
+    ("help.convert", "Convertit une configuration vers un autre format (d'après l'extension)"),
+    ("help.presets", "Liste les presets intégrés et utilisateur"),
+    ("help.export_code", "Affiche le code de partage de la configuration (crosshair, effets et fenêtre)"),
+    ("help.import_code", "Crée une configuration à partir d'un code de partage (XH1-...)"),
+    ("help.import_cs2", "Crée une configuration à partir d'un code de partage CS2 (CSGO-xxxxx-...)"),
+    ("help.export_cs2", "Affiche le code de partage CS2 de la configuration"),
+    ("help.import_valorant", "Crée une configuration à partir d'un code de profil Valorant (0;P;...)"),
+    ("help.export_valorant", "Affiche le code de profil Valorant de la configuration"),
+    ("help.render_output", "Image PNG à créer"),
+    ("help.window_size", "Taille de l'image en pixels (par défaut : taille de la fenêtre, window.size ou auto_size)"),
+    ("help.time", "Instant des animations en secondes (pulse, shake, rainbow)"),
+    ("help.validate_path", "Fichier à vérifier seul, sans profil ni variables (par défaut : la configuration active)"),
+    ("help.all", "Affiche aussi les valeurs par défaut"),
+    ("help.convert_input", "Fichier source"),
+    ("help.convert_output", "Fichier destination"),
+    ("help.preset_kind", "Type de preset (config, crosshair, pulse, shake, rainbow, gap_effect, visibility_effect)"),
+    ("help.code", "Code de partage à importer"),
+    ("help.import_output", "Fichier à créer (format d'après l'extension), sinon affiché en TOML."),
+    ("help.style", "Style du crosshair (classic, dot, circle, t, x, square, diamond)"),
+    ("help.color", "Couleur du crosshair (ex: \"#00FF00\", \"red\", \"rgb(0, 255, 0)\")"),
+    ("help.size", "Taille du crosshair en pixels"),
+    ("help.opacity", "Opacité globale de la fenêtre (0.0 à 1.0)"),
+    ("help.value.path", "CHEMIN"),
+    ("help.value.file", "FICHIER"),
+    ("help.value.seconds", "SECONDES"),
+    ("help.value.color", "COULEUR"),
+    ("help.value.opacity", "OPACITE"),
+    // Rendu en image
+    ("render.image_size", "taille d'image {size} hors limites (1 à {max} pixels)"),
+    // Couleurs
+    ("color.components", "{function}() attend 3 composantes et un alpha optionnel"),
+    ("color.hue", "teinte invalide '{value}'"),
+    ("color.percentage", "'{value}' doit être un pourcentage"),
+    ("color.unknown_function", "fonction de couleur inconnue '{function}()'"),
+    ("color.percentage_range", "'{value}' doit être entre 0% et 100%"),
+    ("color.range", "'{value}' doit être entre 0 et {max}"),
+    ("color.invalid", "couleur invalide '{value}' : {detail}"),
+    ("color.hex_length", "attendu #RGB, #RGBA, #RRGGBB ou #RRGGBBAA"),
+    ("color.closing_parenthesis", "parenthèse fermante manquante"),
+    ("color.expected", "attendu un code hex (#00FF00), rgb(), rgba(), hsl(), hsla() ou un nom CSS"),
+    // Héritage (extends, include)
+    ("inheritance.read_failed", "{path} : lecture impossible : {error}"),
+    ("inheritance.invalid_directive", "{path} : `{key}` invalide : {error}"),
+    ("inheritance.cycle", "héritage circulaire : {chain}"),
+    ("inheritance.path_items", "les éléments doivent être des chemins (chaînes)"),
+    ("inheritance.paths_expected", "attendu un chemin ou une liste de chemins"),
+    ("inheritance.path_expected", "attendu un chemin (chaîne)"),
+    // Formats de fichier
+    ("format.unsupported", "{path} : format non supporté (extensions reconnues : {extensions})"),
+    ("format.not_a_table", "le document doit être une table de paramètres"),
+    // Couches de configuration
+    ("layer.defaults", "défaut"),
+    ("layer.file", "fichier {path}"),
+    ("layer.preset", "preset {kind} {name}"),
+    ("layer.profile", "profil {name}"),
+    ("layer.environment", "variable {variable}"),
+    ("layer.command_line", "ligne de commande"),
+    ("layer.unknown_field", "champ inconnu `{key}`"),
+    ("layer.invalid_variable", "nom de variable invalide (attendu CROSSHAIR__SECTION__CHAMP)"),
+    // Presets
+    ("presets.unknown_kind", "type de preset inconnu '{kind}' ({kinds})"),
+    ("presets.ignored", "⚠️  Preset ignoré {path} : {error}"),
+    ("presets.description_string", "`{key}` doit être une chaîne"),
+    ("presets.name_string", "`{key}` doit être un nom de preset (chaîne)"),
+    ("presets.too_deep", "`{key}` : presets imbriqués trop profondément (cycle ?)"),
+    ("presets.unknown", "`{key}` : preset {kind} inconnu '{name}' (disponibles : {available})"),
+    ("preset.config.sniper", "Point rouge, gap écarté et centre masqué au clic"),
+    ("preset.config.precision", "Crosshair discret qui se resserre au clic"),
+    ("preset.config.neon", "Cercle cyan avec effet rainbow lent"),
+    ("preset.config.rainbow_pulse", "Cercle rainbow qui pulse"),
+    ("preset.crosshair.discrete", "Petit classic blanc avec outline"),
+    ("preset.crosshair.triangle", "Classic à barres triangulaires"),
+    ("preset.crosshair.filled_circle", "Cercle plein magenta"),
+    ("preset.crosshair.sniper_dot", "Point rouge seul"),
+    ("preset.crosshair.rotating_square", "Carré tourné à 45°"),
+    ("preset.crosshair.diamond", "Losange magenta avec outline"),
+    ("preset.crosshair.large", "Grand classic très visible"),
+    ("preset.pulse.breathing", "Pulsation lente"),
+    ("preset.pulse.alert", "Clignotement rapide"),
+    ("preset.shake.subtle", "Tremblement à peine visible"),
+    ("preset.shake.strong", "Tremblement rapide"),
+    ("preset.rainbow.neon", "Défilement lent des couleurs"),
+    ("preset.rainbow.fast", "Défilement rapide des couleurs"),
+    ("preset.gap_effect.sniper", "Écarte fortement le crosshair au clic"),
+    ("preset.gap_effect.precision", "Resserre le crosshair au clic"),
+    ("preset.visibility_effect.sniper_clarity", "Masque le centre au clic"),
+    ("preset.visibility_effect.peripheral_vision", "Masque les côtés au clic"),
+    ("preset.visibility_effect.minimal", "Ne garde que les coins au clic"),
+    // Fenêtre
+    ("window.invalid_offset", "décalage invalide '{value}' (attendu des pixels comme 25 ou un pourcentage comme \"2.5%\")"),
+    // Raccourcis
+    ("hotkey.unknown_key", "touche inconnue '{key}' (lettre, chiffre, F1-F24, Escape, Space, PageUp, Home...)"),
+    ("hotkey.invalid", "raccourci invalide '{hotkey}' : {detail}"),
+    ("hotkey.missing_key", "touche manquante"),
+    ("hotkey.unknown_modifier", "modificateur inconnu '{modifier}' (Ctrl, Alt, Shift, Super)"),
+    // Styles
+    ("crosshair.unknown_style", "style inconnu '{style}' (classic, dot, circle, t, x, square, diamond)"),
+    // Codes de partage
+    ("code.encode_failed", "encodage impossible : {error}"),
+    ("code.invalid", "code invalide : {detail}"),
+    ("code.prefix", "un code commence par {prefix}{version}-"),
+    ("code.characters", "caractères incorrects (code tronqué ?)"),
+    ("code.too_short", "trop court"),
+    ("code.checksum", "somme de contrôle incorrecte (code tronqué ou modifié ?)"),
+    ("code.header_mismatch", "en-tête et contenu incohérents"),
+    ("code.version_too_old", "code de version {version} : ce format n'est plus lu (version actuelle : {current}), demandez un code exporté avec une version récente"),
+    ("code.version_too_new", "code de version {version} non supporté (version actuelle : {current}), mettez à jour l'application"),
+    ("code.compressed_content", "contenu compressé illisible"),
+    // Conversions vers les jeux
+    ("share.style_unavailable", "style {style} non disponible dans {game}, exporté en classic"),
+    ("share.outline_color", "couleur d'outline {color} (toujours noire dans {game})"),
+    ("share.center_dot_color", "couleur du point central (celle des lignes dans {game})"),
+    ("share.rotation", "rotation de {rotation}°"),
+    ("share.triangle_bars", "barres triangulaires"),
+    ("share.effect_ignored", "effet {effect} ignoré"),
+    // Codes CS2
+    ("cs2.checksum", "code CS2 invalide : somme de contrôle incorrecte"),
+    ("cs2.dynamic_style", "style dynamique (cl_crosshairstyle {style}) approximé par effects.mouse.gap_effect"),
+    ("cs2.follow_recoil", "cl_crosshair_recoil (suivi du recul) ignoré"),
+    ("cs2.weapon_gap", "cl_crosshairgap_useweaponvalue (gap selon l'arme) ignoré"),
+    ("cs2.gap_out_of_range", "gap de {gap} pixels hors des limites de CS2"),
+    ("cs2.outline_thickness", "outline de {thickness} pixels (3 maximum dans CS2)"),
+    ("cs2.center_dot_size", "taille du point central (épaisseur des lignes dans CS2)"),
+    ("cs2.gap_effect", "effet de gap de la souris approximé par le style classic dynamique"),
+    ("cs2.prefix", "un code CS2 commence par {prefix}-"),
+    ("cs2.length", "un code CS2 contient {length} caractères après {prefix}-"),
+    ("cs2.character", "caractère '{character}' invalide dans un code CS2"),
+    ("cs2.too_large", "code CS2 invalide : valeur trop grande"),
+    // Codes Valorant
+    ("valorant.prefix", "un code Valorant commence par \"{version};\""),
+    ("valorant.section_ads", "visée"),
+    ("valorant.section_sniper", "lunette"),
+    ("valorant.section_ignored", "section {section} ({name}) ignorée"),
+    ("valorant.missing_value", "code Valorant invalide : valeur manquante pour '{key}'"),
+    ("valorant.general_ignored", "paramètre général '{key}' ignoré"),
+    ("valorant.invalid", "code Valorant invalide : '{key};{value}' ({error})"),
+    ("valorant.unknown_color", "couleur inconnue"),
+    ("valorant.setting_ignored", "paramètre '{key}' ignoré"),
+    ("valorant.custom_color_missing", "code Valorant invalide : couleur personnalisée 'u' manquante"),
+    ("valorant.vertical_length", "longueur verticale distincte des lignes {lines} ignorée"),
+    ("valorant.movement_error", "erreur de déplacement des lignes {lines} ignorée"),
+    ("valorant.firing_error", "erreur de tir des lignes {lines} approximée par effects.mouse.gap_effect"),
+    ("valorant.outer_lines", "lignes extérieures ignorées (décalage {offset}, longueur {length}, épaisseur {thickness}) : un seul jeu de lignes est dessiné"),
+    ("valorant.color_transparency", "transparence de la couleur (utiliser alpha)"),
+    ("valorant.gap_effect", "effet de gap de la souris approximé par l'erreur de tir"),
+    ("valorant.flag_expected", "attendu 0 ou 1"),
+    ("valorant.integer_expected", "nombre entier attendu"),
+    ("valorant.opacity_expected", "opacité entre 0 et 1 attendue"),
+];
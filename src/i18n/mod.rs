@@ -0,0 +1,188 @@
+//! Catalogues des messages affichés à l'utilisateur (anglais, français)
+//!
+//! Les messages sont identifiés par une clé (`"app.reloading"`) et peuvent
+//! contenir des paramètres nommés (`{path}`), remplis par la macro [`tr!`].
+//! La langue est détectée depuis l'environnement (`LC_ALL`, `LC_MESSAGES`,
+//! `LANG`, puis la langue de l'utilisateur sous Windows) et peut être imposée
+//! par `interface.language` dans la configuration.
+
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+mod en;
+mod fr;
+
+/// Langue des messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+pub enum Language {
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "fr")]
+    French,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::French];
+
+    /// Code de la langue (`en`, `fr`)
+    pub fn code(self) -> &'static str {
+        match self {
+            Self::English => "en",
+            Self::French => "fr",
+        }
+    }
+
+    /// Langue d'une locale POSIX (`fr_FR.UTF-8`, `en_US`, `fr`) ; None si elle n'est pas traduite
+    pub fn from_locale(locale: &str) -> Option<Self> {
+        let code = locale.split(['_', '-', '.', '@']).next()?.to_lowercase();
+        Self::ALL.into_iter().find(|language| language.code() == code)
+    }
+
+    /// Langue de l'environnement : la première variable définie parmi LC_ALL, LC_MESSAGES et LANG,
+    /// sinon la langue de l'utilisateur sous Windows
+    ///
+    /// Une locale non traduite (ou `C`, `POSIX`) donne l'anglais.
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .or_else(system_locale)
+            .and_then(|locale| Self::from_locale(&locale))
+            .unwrap_or(Self::English)
+    }
+
+    fn catalog(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Self::English => en::MESSAGES,
+            Self::French => fr::MESSAGES,
+        }
+    }
+}
+
+/// Locale de l'utilisateur Windows (`fr-FR`)
+#[cfg(windows)]
+fn system_locale() -> Option<String> {
+    use windows::Win32::Globalization::GetUserDefaultLocaleName;
+
+    // LOCALE_NAME_MAX_LENGTH, zéro final compris
+    let mut buffer = [0u16; 85];
+    let length = unsafe { GetUserDefaultLocaleName(&mut buffer) };
+    // 0 en cas d'échec, sinon la longueur avec le zéro final
+    let length = usize::try_from(length).ok().filter(|length| *length > 1)?;
+    Some(String::from_utf16_lossy(&buffer[..length - 1]))
+}
+
+#[cfg(not(windows))]
+fn system_locale() -> Option<String> {
+    None
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Langue courante : 0 = pas encore choisie (détection au premier message)
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Choisit la langue des messages ; None revient à la langue de l'environnement
+pub fn set_language(language: Option<Language>) {
+    let language = language.unwrap_or_else(Language::detect);
+    CURRENT.store(language as u8 + 1, Ordering::Relaxed);
+}
+
+/// Langue courante des messages
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        0 => {
+            set_language(None);
+            language()
+        }
+        value => Language::ALL[usize::from(value - 1)],
+    }
+}
+
+/// Texte brut d'un message dans une langue, avec repli sur l'anglais puis sur la clé
+pub fn message(language: Language, key: &'static str) -> &'static str {
+    let lookup = |language: Language| {
+        language
+            .catalog()
+            .iter()
+            .find(|(name, _)| *name == key)
+            .map(|(_, text)| *text)
+    };
+    lookup(language).or_else(|| lookup(Language::English)).unwrap_or(key)
+}
+
+/// Remplit les paramètres `{nom}` d'un message de la langue courante
+pub fn format(key: &'static str, arguments: &[(&str, &dyn fmt::Display)]) -> String {
+    let mut text = message(language(), key).to_string();
+    for (name, value) in arguments {
+        text = text.replace(&format!("{{{}}}", name), &value.to_string());
+    }
+    text
+}
+
+/// Message traduit dans la langue courante
+///
+/// ```ignore
+/// println!("{}", tr!("app.reloading"));
+/// eprintln!("{}", tr!("config.load_error", path = path.display(), error = e));
+/// ```
+#[macro_export]
+macro_rules! tr {
+    ($key:expr $(,)?) => {
+        $crate::i18n::format($key, &[])
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format($key, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+
+    /// Paramètres `{nom}` d'un message
+    fn placeholders(text: &str) -> BTreeSet<&str> {
+        text.split('{').skip(1).filter_map(|part| part.split_once('}')).map(|(name, _)| name).collect()
+    }
+
+    #[test]
+    fn catalogs_have_the_same_messages() {
+        let keys = |language: Language| language.catalog().iter().map(|(key, _)| *key).collect::<BTreeSet<_>>();
+        for language in Language::ALL {
+            assert_eq!(keys(language), keys(Language::English), "clés différentes pour {}", language);
+            assert_eq!(keys(language).len(), language.catalog().len(), "clé en double pour {}", language);
+            for (key, text) in language.catalog() {
+                assert_eq!(
+                    placeholders(text),
+                    placeholders(message(Language::English, key)),
+                    "paramètres différents pour {} ({})",
+                    key,
+                    language
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn locales_are_recognized() {
+        assert_eq!(Language::from_locale("fr_FR.UTF-8"), Some(Language::French));
+        assert_eq!(Language::from_locale("en-US"), Some(Language::English));
+        assert_eq!(Language::from_locale("FR"), Some(Language::French));
+        assert_eq!(Language::from_locale("C"), None);
+        assert_eq!(Language::from_locale(""), None);
+    }
+
+    #[test]
+    fn unknown_keys_fall_back_to_the_key() {
+        assert_eq!(message(Language::French, "missing.key"), "missing.key");
+    }
+}
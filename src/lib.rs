@@ -0,0 +1,13 @@
+//! Crosshair overlay : configuration, styles de crosshair et rendu
+//!
+//! La bibliothèque ne dépend pas de la fenêtre : elle sert au binaire, aux
+//! tests et aux cibles de fuzzing (dossier `fuzz/`).
+
+pub mod cli;
+pub mod clock;
+pub mod config;
+pub mod crosshair;
+pub mod follow;
+pub mod i18n;
+pub mod monitor;
+pub mod render;
@@ -1,83 +1,70 @@
+use clap::Parser;
 use std::num::NonZeroU32;
+use std::process::ExitCode;
 use std::time::Instant;
 use winit::{
     application::ApplicationHandler,
     event::{ElementState, KeyEvent, WindowEvent},
     event_loop::{ActiveEventLoop, ControlFlow, EventLoop},
     keyboard::{Key, NamedKey},
+    monitor::MonitorHandle,
     window::{Window, WindowId, WindowAttributes},
-    dpi::{LogicalPosition, LogicalSize},
+    dpi::{PhysicalPosition, PhysicalSize},
 };
 use softbuffer::{Context, Surface};
 
-mod config;
-mod crosshair;
+mod backend;
+mod controller;
+mod focus;
+mod hotkeys;
 
-use config::CrosshairConfig;
-use config::effects::mouse::{get_global_mouse_state, initialize_global_mouse_capture, shutdown_global_mouse_capture, has_mouse_state_changed};
+use crosshair_overlay::{cli, config, render};
+use crosshair_overlay::clock::{Clock, RealClock};
+use crosshair_overlay::follow::CursorFollower;
+use crosshair_overlay::monitor::{self, MonitorInfo, MonitorLayout};
+use crosshair_overlay::tr;
+
+use cli::{Cli, Command, Overrides};
+use config::ConfigLocation;
+use config::hotkeys::HotkeyAction;
+use controller::{AppEvent, Changes, Controller, EventRelay, OPACITY_STEP};
+use config::window::{Backend, MonitorSelection};
+use config::effects::mouse::{initialize_global_mouse_capture, shutdown_global_mouse_capture, has_mouse_state_changed};
+use config::effects::mouse::{get_global_cursor_position, has_cursor_moved, query_cursor_position};
+
+/// Intervalle de vérification des écrans (branchement, résolution, disposition)
+const MONITOR_CHECK_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
 
 struct App {
     window: Option<std::sync::Arc<Window>>,
     surface: Option<Surface<std::sync::Arc<Window>, std::sync::Arc<Window>>>,
     context: Option<Context<std::sync::Arc<Window>>>,
-    config: CrosshairConfig,
-    start_time: Instant,
+    controller: Controller,
+    clock: RealClock,
+    follower: CursorFollower,
+    monitors: MonitorLayout,
+    next_monitor_check: Instant,
     last_frame_time: Instant,
     frame_rate_limit: std::time::Duration,
     needs_redraw: bool,
 }
 
-impl ApplicationHandler for App {
+impl ApplicationHandler<AppEvent> for App {
     fn resumed(&mut self, event_loop: &ActiveEventLoop) {
-        // Obtenir la taille de l'écran pour centrer la fenêtre
-        let screen_size = event_loop.primary_monitor()
-            .unwrap()
-            .size();
-        
-        let window_size = LogicalSize::new(self.config.window.size, self.config.window.size);
-        
-        let window_pos = if self.config.window.position.center_screen {
-            LogicalPosition::new(
-                (screen_size.width as f64 - window_size.width as f64) / 2.0,
-                (screen_size.height as f64 - window_size.height as f64) / 2.0,
-            )
-        } else {
-            LogicalPosition::new(
-                self.config.window.position.x.unwrap_or(0) as f64,
-                self.config.window.position.y.unwrap_or(0) as f64,
-            )
-        };
-
-        // Créer les attributs de la fenêtre
-        let window_attributes = WindowAttributes::default()
-            .with_title("Crosshair Overlay Pro")
-            .with_inner_size(window_size)
-            .with_position(window_pos)
-            .with_decorations(false)
-            .with_transparent(true)
-            .with_window_level(winit::window::WindowLevel::AlwaysOnTop)
-            .with_resizable(false);
-
-        let window = std::sync::Arc::new(
-            event_loop.create_window(window_attributes).unwrap()
-        );
-
-        // Activer le click-through - les clics passent à travers la fenêtre !
-        if let Err(e) = window.set_cursor_hittest(false) {
-            eprintln!("Attention: Click-through non supporté sur cette plateforme: {:?}", e);
+        // Placer la fenêtre sur l'écran choisi, en pixels physiques
+        self.monitors = monitor_layout(event_loop.available_monitors(), event_loop.primary_monitor());
+        if let Err(e) = self.create_window(event_loop) {
+            eprintln!("{}", tr!("app.window_failed", error = e));
+            event_loop.exit();
+            return;
         }
 
-        // Initialiser softbuffer pour le rendu
-        let context = Context::new(window.clone()).unwrap();
-        let surface = Surface::new(&context, window.clone()).unwrap();
-
-        self.window = Some(window);
-        self.context = Some(context);
-        self.surface = Some(surface);
-
         // Démarrer la capture de souris
         if let Err(e) = initialize_global_mouse_capture() {
-            eprintln!("❌ Erreur lors du démarrage de la capture de souris: {}", e);
+            eprintln!("{}", tr!("app.mouse_capture_failed", error = e));
+            if self.controller.config.window.position.follow_cursor {
+                eprintln!("{}", tr!("app.follow_cursor_unavailable"));
+            }
         }
 
         // Premier rendu
@@ -92,28 +79,73 @@ impl ApplicationHandler for App {
             },
             WindowEvent::KeyboardInput {
                 event: KeyEvent {
-                    logical_key: Key::Named(NamedKey::Escape),
+                    logical_key: Key::Named(NamedKey::F5),
                     state: ElementState::Pressed,
                     ..
                 },
                 ..
             } => {
-                event_loop.exit();
+                // Recharger la configuration avec F5
+                self.handle_action(event_loop, HotkeyAction::Reload);
             },
             WindowEvent::KeyboardInput {
                 event: KeyEvent {
-                    logical_key: Key::Named(NamedKey::F5),
+                    logical_key: Key::Named(NamedKey::F6),
                     state: ElementState::Pressed,
                     ..
                 },
                 ..
             } => {
-                // Recharger la configuration avec F5
-                println!("🔄 Rechargement de la configuration...");
-                self.config = CrosshairConfig::load_or_default();
-                
+                // Profil suivant avec F6
+                self.handle_action(event_loop, HotkeyAction::NextProfile);
+            },
+            WindowEvent::KeyboardInput {
+                event: KeyEvent {
+                    logical_key: Key::Named(NamedKey::F7),
+                    state: ElementState::Pressed,
+                    ..
+                },
+                ..
+            } => {
+                // Profil précédent avec F7
+                self.handle_action(event_loop, HotkeyAction::PreviousProfile);
+            },
+            WindowEvent::KeyboardInput {
+                event: KeyEvent {
+                    logical_key: Key::Character(ref key),
+                    state: ElementState::Pressed,
+                    ..
+                },
+                ..
+            } => {
+                // Opacité globale avec + et - (= et _ sur les claviers sans pavé numérique)
+                if let Some(step) = match key.as_str() {
+                    "+" | "=" => Some(OPACITY_STEP),
+                    "-" | "_" => Some(-OPACITY_STEP),
+                    _ => None,
+                } {
+                    let changes = self.controller.adjust_opacity(step);
+                    self.apply_changes(event_loop, changes);
+                }
+                // Accès direct aux profils avec les touches 1 à 9
+                else if let Ok(digit @ 1..=9) = key.parse::<usize>() {
+                    let changes = self.controller.select_profile(digit - 1);
+                    self.apply_changes(event_loop, changes);
+                }
+            },
+            WindowEvent::ScaleFactorChanged { scale_factor, mut inner_size_writer } => {
+                // Garder la taille logique de la fenêtre avec la nouvelle mise à l'échelle
+                let side = self.controller.config.window_side(scale_factor);
+                let _ = inner_size_writer.request_inner_size(PhysicalSize::new(side, side));
+                self.monitors = monitor_layout(event_loop.available_monitors(), event_loop.primary_monitor());
+                self.place_window(event_loop);
+            },
+            WindowEvent::Resized(_) => {
+                // Nouvelle taille appliquée : la surface est redimensionnée au prochain rendu
                 self.needs_redraw = true;
-                self.redraw();
+                if let Some(window) = &self.window {
+                    window.request_redraw();
+                }
             },
             WindowEvent::RedrawRequested => {
                 self.redraw();
@@ -122,13 +154,33 @@ impl ApplicationHandler for App {
         }
     }
 
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: AppEvent) {
+        // Raccourci global (reçu même quand le jeu a le focus) ou changement de fenêtre active
+        let changes = self.controller.handle(event);
+        self.apply_changes(event_loop, changes);
+    }
+
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
+        // Écran branché, débranché ou changement de résolution : replacer la fenêtre
+        if Instant::now() >= self.next_monitor_check {
+            self.next_monitor_check = Instant::now() + MONITOR_CHECK_INTERVAL;
+            let layout = monitor_layout(event_loop.available_monitors(), event_loop.primary_monitor());
+            if layout != self.monitors {
+                self.monitors = layout;
+                self.place_window(event_loop);
+            }
+        }
+
         let has_animations = self.has_animated_effects();
+        let following = self.controller.config.window.position.follow_cursor;
+        if following {
+            self.follow_cursor();
+        }
         
         if has_animations {
             // Effets animés continus (pulse, shake, rainbow) - 30 FPS
             let next_frame = self.last_frame_time + self.frame_rate_limit;
-            event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame));
+            event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame.min(self.next_monitor_check)));
             
             let now = Instant::now();
             if now >= next_frame {
@@ -139,12 +191,19 @@ impl ApplicationHandler for App {
                 self.last_frame_time = now;
             }
         } else {
-            // Pas d'animation continue - attendre indéfiniment jusqu'au prochain événement
+            // Pas d'animation continue - attendre le prochain événement, au plus tard la prochaine
+            // vérification des écrans : une fenêtre click-through ne reçoit presque aucun événement
             // Les effets de souris seront gérés par des redraws déclenchés lors des clics
-            event_loop.set_control_flow(ControlFlow::Wait);
+            // En mode follow_cursor, la position du curseur est relevée à chaque image
+            let deadline = if following {
+                (Instant::now() + self.frame_rate_limit).min(self.next_monitor_check)
+            } else {
+                self.next_monitor_check
+            };
+            event_loop.set_control_flow(ControlFlow::WaitUntil(deadline));
             
             // Vérifier s'il y a eu un changement de souris et redessiner si nécessaire
-            if self.config.effects.has_mouse_effects() && has_mouse_state_changed() {
+            if self.controller.config.effects.has_mouse_effects() && has_mouse_state_changed() {
                 self.needs_redraw = true;
                 if let Some(window) = &self.window {
                     window.request_redraw();
@@ -155,9 +214,7 @@ impl ApplicationHandler for App {
 }
 
 impl App {
-    fn new() -> Self {
-        let config = CrosshairConfig::load_or_default();
-        
+    fn new(controller: Controller) -> Self {
         // Limiter à 30 FPS pour les animations (au lieu de redessiner en continu)
         let frame_rate_limit = std::time::Duration::from_millis(33); // ~30 FPS
         
@@ -165,30 +222,47 @@ impl App {
             window: None,
             surface: None,
             context: None,
-            config,
-            start_time: Instant::now(),
+            controller,
+            clock: RealClock::new(),
+            follower: CursorFollower::new(),
+            monitors: MonitorLayout::default(),
+            next_monitor_check: Instant::now() + MONITOR_CHECK_INTERVAL,
             last_frame_time: Instant::now(),
             frame_rate_limit,
             needs_redraw: true, // Initialiser à true pour le premier dessin
         }
     }
 
+    /// Applique à la fenêtre le résultat d'une action du contrôleur
+    fn apply_changes(&mut self, event_loop: &ActiveEventLoop, changes: Changes) {
+        if changes.quit {
+            event_loop.exit();
+            return;
+        }
+        if changes.visibility {
+            if let Some(window) = &self.window {
+                window.set_visible(self.controller.is_visible());
+            }
+        }
+        if changes.placement {
+            // Replacer la fenêtre sans lissage avec les nouveaux décalages
+            self.follower.reset();
+            self.place_window(event_loop);
+        }
+        if changes.redraw {
+            self.needs_redraw = true;
+            if let Some(window) = &self.window {
+                window.request_redraw();
+            }
+        }
+    }
+
     fn redraw(&mut self) {
         // Ne redessiner que si nécessaire
         if !self.needs_redraw {
             return;
         }
 
-        // Calculer le temps écoulé pour les animations
-        let elapsed = self.start_time.elapsed().as_secs_f32();
-        
-        // Créer des copies pour éviter les problèmes d'emprunt
-        let crosshair = self.config.crosshair.clone();
-        let effects = self.config.effects.clone();
-        let background_enabled = self.config.window.background.enabled;
-        let background_color = self.config.window.background.color.clone();
-        let background_alpha = self.config.window.background.alpha;
-        
         if let (Some(window), Some(surface)) = (&self.window, &mut self.surface) {
             let size = window.inner_size();
             if size.width > 0 && size.height > 0 {
@@ -198,22 +272,12 @@ impl App {
                 ).unwrap();
 
                 let mut buffer = surface.buffer_mut().unwrap();
-                
-                // Remplir avec du noir transparent ou la couleur de fond
-                if background_enabled {
-                    let bg_color = Self::parse_color_with_alpha(&background_color, background_alpha);
-                    buffer.fill(bg_color);
-                } else {
-                    buffer.fill(0x00000000);
-                }
-
-                // Dessiner le crosshair selon la configuration avec effets
-                crosshair.draw(
-                    &mut buffer, 
-                    size.width as usize, 
-                    size.height as usize, 
-                    &effects,
-                    elapsed
+                render::render_frame(
+                    &self.controller.config,
+                    &mut buffer,
+                    size.width as usize,
+                    size.height as usize,
+                    &self.clock
                 );
 
                 buffer.present().unwrap();
@@ -224,70 +288,321 @@ impl App {
         self.needs_redraw = self.has_animated_effects();
     }
 
-    fn parse_color_with_alpha(hex: &str, alpha: f32) -> u32 {
-        let hex = hex.trim_start_matches('#');
-        let alpha_u8 = (alpha.clamp(0.0, 1.0) * 255.0) as u32;
-        
-        if hex.len() == 6 {
-            if let Ok(rgb) = u32::from_str_radix(hex, 16) {
-                return (alpha_u8 << 24) | rgb;
+    /// Position (None = au choix du système) et taille de la fenêtre en pixels physiques
+    fn placement(&self) -> (Option<(i32, i32)>, (u32, u32)) {
+        let config = &self.controller.config;
+        let window = &config.window;
+        let cursor = match window.monitor {
+            MonitorSelection::Cursor => query_cursor_position(),
+            _ => None,
+        };
+        let (selected, warning) = self.monitors.select(&window.monitor, cursor);
+        if let Some(monitor) = warning {
+            eprintln!("{}", tr!("app.monitor_not_found", monitor = monitor));
+        }
+        match selected {
+            Some(selected) => {
+                let (position, size) = monitor::window_placement(config, selected);
+                (Some(position), size)
+            }
+            None => {
+                let side = config.window_side(1.0);
+                (None, (side, side))
             }
         }
-        alpha_u8 << 24 // Noir transparent par défaut
+    }
+
+    /// Crée la fenêtre et sa surface de rendu à la place configurée
+    ///
+    /// En cas d'échec, la fenêtre et la surface précédentes sont gardées.
+    fn create_window(&mut self, event_loop: &ActiveEventLoop) -> Result<(), Box<dyn std::error::Error>> {
+        let (window_pos, (width, height)) = self.placement();
+
+        // Créer les attributs de la fenêtre
+        let mut window_attributes = WindowAttributes::default()
+            .with_title("Crosshair Overlay Pro")
+            .with_inner_size(PhysicalSize::new(width, height))
+            .with_decorations(false)
+            .with_transparent(true)
+            .with_window_level(winit::window::WindowLevel::AlwaysOnTop)
+            .with_resizable(false)
+            .with_visible(self.controller.is_visible());
+        // Sans écran connu, le système choisit la position
+        if let Some((x, y)) = window_pos {
+            window_attributes = window_attributes.with_position(PhysicalPosition::new(x, y));
+        }
+
+        let window = std::sync::Arc::new(event_loop.create_window(window_attributes)?);
+
+        // Activer le click-through - les clics passent à travers la fenêtre !
+        if let Err(e) = window.set_cursor_hittest(false) {
+            eprintln!("{}", tr!("app.click_through_unsupported", error = format!("{:?}", e)));
+        }
+
+        // Initialiser softbuffer pour le rendu
+        let context = Context::new(window.clone())?;
+        let surface = Surface::new(&context, window.clone())?;
+
+        // Libérer l'ancienne surface avant sa fenêtre
+        self.surface = Some(surface);
+        self.context = Some(context);
+        self.window = Some(window);
+        self.follower.reset();
+        self.needs_redraw = true;
+        Ok(())
+    }
+
+    /// Replace la fenêtre (nouvelle configuration, disposition des écrans ou mise à l'échelle)
+    ///
+    /// Si le système refuse la nouvelle taille, la fenêtre et sa surface sont recréées.
+    fn place_window(&mut self, event_loop: &ActiveEventLoop) {
+        let (position, (width, height)) = self.placement();
+        let Some(window) = &self.window else {
+            return;
+        };
+        let size = PhysicalSize::new(width, height);
+        // Some : taille appliquée tout de suite, None : un événement Resized suivra
+        if window.request_inner_size(size).is_some_and(|applied| applied != size) {
+            if let Err(e) = self.create_window(event_loop) {
+                eprintln!("{}", tr!("app.window_recreate_failed", error = e));
+                self.needs_redraw = true;
+            }
+            if let Some(window) = &self.window {
+                window.request_redraw();
+            }
+            return;
+        }
+        if self.controller.config.window.position.follow_cursor {
+            // La position suit le curseur : recalculer avec la nouvelle taille
+            self.follower.reset();
+        } else if let Some((x, y)) = position {
+            window.set_outer_position(PhysicalPosition::new(x, y));
+        }
+        self.needs_redraw = true;
+        window.request_redraw();
+    }
+
+    /// Déplace la fenêtre sur le curseur si celui-ci a bougé (ou si le lissage est en cours)
+    fn follow_cursor(&mut self) {
+        let Some(window) = &self.window else {
+            return;
+        };
+        if !has_cursor_moved() && !self.follower.is_settling() {
+            return;
+        }
+        let Some(cursor) = get_global_cursor_position() else {
+            return;
+        };
+
+        let size = window.outer_size();
+        let position = &self.controller.config.window.position;
+        if let Some((x, y)) = self.follower.update(cursor, position, (size.width, size.height), window.scale_factor(), self.clock.now()) {
+            window.set_outer_position(PhysicalPosition::new(x, y));
+        }
+    }
+
+    fn handle_action(&mut self, event_loop: &ActiveEventLoop, action: HotkeyAction) {
+        let changes = self.controller.handle_action(action);
+        self.apply_changes(event_loop, changes);
     }
 
     fn has_animated_effects(&self) -> bool {
-        self.config.effects.has_animated_effects()
+        self.controller.config.effects.has_animated_effects()
     }
 }
 
-fn main() {
-    let event_loop = EventLoop::new().unwrap();
-    // Utiliser Wait pour économiser le CPU - ne se réveille que sur événements
-    event_loop.set_control_flow(ControlFlow::Wait);
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+
+    // Trouver le fichier de configuration (--config, CROSSHAIR_CONFIG, dossiers standards)
+    let config_location = config::locations::discover(cli.config);
+
+    // L'overlay choisit sa langue en chargeant sa configuration
+    if !matches!(cli.command, None | Some(Command::Run(_))) {
+        cli::set_language(&config_location);
+    }
+
+    match cli.command {
+        None => run(config_location, Overrides::default()),
+        Some(Command::Run(args)) => run(config_location, args.overrides),
+        Some(Command::Render(args)) => cli::render(&config_location, &args),
+        Some(Command::Validate(args)) => cli::validate(&config_location, &args),
+        Some(Command::Explain(args)) => cli::explain(&config_location, &args),
+        Some(Command::PrintDefault) => cli::print_default(),
+        Some(Command::PrintSchema) => cli::print_schema(),
+        Some(Command::Convert(args)) => cli::convert(&args),
+        Some(Command::Presets(args)) => cli::presets(&config_location, &args),
+        Some(Command::ExportCode) => cli::export_code(&config_location),
+        Some(Command::ImportCode(args)) => cli::import_code(&args),
+        Some(Command::ImportCs2(args)) => cli::import_cs2(&args),
+        Some(Command::ExportCs2) => cli::export_cs2(&config_location),
+        Some(Command::ImportValorant(args)) => cli::import_valorant(&args),
+        Some(Command::ExportValorant) => cli::export_valorant(&config_location),
+    }
+}
+
+/// Commande `run` : lance l'overlay
+fn run(config_location: ConfigLocation, overrides: Overrides) -> ExitCode {
+    println!("{}", tr!("app.config_file", location = config_location));
 
-    let mut app = App::new();
+    let mut controller = Controller::new(config_location, overrides);
     
     // Arrêter la capture de souris quand l'application se termine
     std::panic::set_hook(Box::new(|_| {
         shutdown_global_mouse_capture();
     }));
     
-    println!("🎯 Crosshair Overlay Pro - Version Performance Maximale !");
-    println!("📋 Fonctionnalités :");
-    println!("   ✅ Configuration modulaire via config.toml");
-    println!("   ✅ Fenêtre transparente configurable");
-    println!("   ✅ Crosshair personnalisable (style: {:?})", app.config.crosshair.style);
-    println!("   ✅ Effets visuels (pulse, shake, rainbow)");
-    println!("   ✅ Rotation et alpha configurables");
-    println!("   ✅ Outline et centre dot avancés");
-    println!("   ✅ Click-through activé");
-    println!("   🖱️  Capture de souris (clic gauche, droit, molette)");
-    println!("   ⚡ Performance maximale - usage CPU ultra minimal");
+    let config = &controller.config;
+    println!("{}", tr!("banner.title"));
+    println!("{}", tr!("banner.features"));
+    println!("{}", tr!("banner.feature_config", path = controller.config_location.path.display()));
+    println!("{}", tr!("banner.feature_window"));
+    println!("{}", tr!("banner.feature_style", style = format!("{:?}", config.crosshair.style)));
+    println!("{}", tr!("banner.feature_effects"));
+    println!("{}", tr!("banner.feature_rotation"));
+    println!("{}", tr!("banner.feature_outline"));
+    println!("{}", tr!("banner.feature_click_through"));
+    println!("{}", tr!("banner.feature_mouse"));
+    println!("{}", tr!("banner.feature_performance"));
     println!();
-    println!("🎨 Styles disponibles : classic, dot, cross, circle, t, plus, x");
-    println!("✨ Effets disponibles :");
-    println!("   • Pulse: {:?} (alpha {:.1}-{:.1})", 
-        app.config.effects.pulse.enabled, 
-        app.config.effects.pulse.min_alpha, 
-        app.config.effects.pulse.max_alpha);
-    println!("   • Shake: {:?} (intensité: {:.1}, vitesse: {:.1})", 
-        app.config.effects.shake.enabled,
-        app.config.effects.shake.intensity,
-        app.config.effects.shake.speed);
-    println!("   • Rainbow: {:?} (saturation: {:.1}, luminosité: {:.1})", 
-        app.config.effects.rainbow.enabled,
-        app.config.effects.rainbow.saturation,
-        app.config.effects.rainbow.brightness);
-    println!("   • Effets Souris: {:?}", app.config.effects.mouse.enabled);
+    println!("{}", tr!("banner.styles", styles = "classic, dot, circle, t, x, square, diamond"));
+    println!("{}", tr!("banner.effects"));
+    println!("{}", tr!("banner.pulse",
+        enabled = config.effects.pulse.enabled,
+        min = format!("{:.1}", config.effects.pulse.min_alpha),
+        max = format!("{:.1}", config.effects.pulse.max_alpha)));
+    println!("{}", tr!("banner.shake",
+        enabled = config.effects.shake.enabled,
+        intensity = format!("{:.1}", config.effects.shake.intensity),
+        speed = format!("{:.1}", config.effects.shake.speed)));
+    println!("{}", tr!("banner.rainbow",
+        enabled = config.effects.rainbow.enabled,
+        saturation = format!("{:.1}", config.effects.rainbow.saturation),
+        brightness = format!("{:.1}", config.effects.rainbow.brightness)));
+    println!("{}", tr!("banner.mouse_effects", enabled = config.effects.mouse.enabled));
     println!();
-    println!("⌨️  Contrôles :");
-    println!("   • Échap : Quitter");
-    println!("   • F5 : Recharger la configuration");
+    println!("{}", tr!("banner.controls"));
+    for (hotkey, action) in config.hotkeys.bindings() {
+        println!("{}", tr!("banner.control_hotkey", hotkey = hotkey, action = hotkey_label(action)));
+    }
+    println!("{}", tr!("banner.control_reload"));
+    println!("{}", tr!("banner.control_profiles"));
+    println!("{}", tr!("banner.control_digits"));
+    println!("{}", tr!("banner.control_opacity"));
     println!();
-    println!("💡 Modifiez config.toml pour explorer toutes les options !");
-    println!("💡 Pour activer shake: [effects.shake] enabled = true");
-    
+    if !controller.profiles.names().is_empty() {
+        let active = controller.profiles.current_name().map_or_else(|| tr!("app.base_configuration"), str::to_string);
+        println!("{}", tr!("banner.profiles", names = controller.profiles.names().join(", ")));
+        println!("{}", tr!("app.active_profile", name = active));
+        println!();
+    }
+    println!("{}", tr!("banner.edit_hint", path = controller.config_location.path.display()));
+    println!("{}", tr!("banner.shake_hint"));
+
+    // Les raccourcis globaux et la surveillance du focus démarrent avant le choix
+    // du backend : leurs événements attendent dans le relais que la boucle
+    // d'affichage s'y connecte
+    let relay = EventRelay::default();
+    controller.start_hotkeys(&relay);
+    controller.start_focus(&relay);
+
+    // Surface layer-shell sous Wayland, fenêtre override-redirect sous X11, sinon fenêtre winit
+    if let Some(native) = backend::native_backend(controller.config.window.backend) {
+        if let Some(code) = run_native(native, &mut controller, &relay) {
+            controller.shutdown();
+            return code;
+        }
+    }
+
+    let event_loop = EventLoop::<AppEvent>::with_user_event().build().unwrap();
+    // Utiliser Wait pour économiser le CPU - ne se réveille que sur événements
+    event_loop.set_control_flow(ControlFlow::Wait);
+
+    // Les raccourcis globaux et la surveillance du focus réveillent la boucle
+    // d'événements depuis le thread de leur backend
+    let proxy = std::sync::Mutex::new(event_loop.create_proxy());
+    relay.connect(move |event| {
+        if let Ok(proxy) = proxy.lock() {
+            let _ = proxy.send_event(event);
+        }
+    });
+
+    let mut app = App::new(controller);
     event_loop.run_app(&mut app).unwrap();
+    app.controller.shutdown();
+    ExitCode::SUCCESS
+}
+
+/// Description traduite d'une action, pour la liste des contrôles
+fn hotkey_label(action: HotkeyAction) -> String {
+    match action {
+        HotkeyAction::ToggleVisibility => tr!("hotkey_action.toggle_visibility"),
+        HotkeyAction::Reload => tr!("hotkey_action.reload"),
+        HotkeyAction::NextProfile => tr!("hotkey_action.next_profile"),
+        HotkeyAction::PreviousProfile => tr!("hotkey_action.previous_profile"),
+        HotkeyAction::CycleStyle => tr!("hotkey_action.cycle_style"),
+        HotkeyAction::OpacityUp => tr!("hotkey_action.opacity_up"),
+        HotkeyAction::OpacityDown => tr!("hotkey_action.opacity_down"),
+        HotkeyAction::Quit => tr!("hotkey_action.quit"),
+    }
+}
+
+/// Overlay natif connecté, prêt à être affiché
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+enum NativeOverlay {
+    LayerShell(Box<backend::layer_shell::LayerOverlay>, smithay_client_toolkit::reexports::client::EventQueue<backend::layer_shell::LayerOverlay>),
+    X11(Box<backend::x11::X11Overlay>),
 }
 
+/// Affiche l'overlay avec un backend natif ; None s'il est indisponible (revenir à winit)
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android"))))]
+fn run_native(native: Backend, controller: &mut Controller, relay: &EventRelay) -> Option<ExitCode> {
+    let config = controller.config.clone();
+    let connected = match native {
+        Backend::X11 => backend::x11::X11Overlay::connect(config).map(|overlay| NativeOverlay::X11(Box::new(overlay))),
+        _ => backend::layer_shell::LayerOverlay::connect(config).map(|(overlay, queue)| NativeOverlay::LayerShell(Box::new(overlay), queue)),
+    };
+    let overlay = match connected {
+        Ok(overlay) => overlay,
+        Err(e) => {
+            eprintln!("{}", tr!("app.native_fallback", backend = native, error = e));
+            return None;
+        }
+    };
+    println!("{}", tr!("app.native_controls", backend = native));
+    let result = match overlay {
+        NativeOverlay::LayerShell(overlay, event_queue) => (*overlay).run(event_queue, controller, relay),
+        NativeOverlay::X11(overlay) => (*overlay).run(controller, relay),
+    };
+    match result {
+        Ok(()) => Some(ExitCode::SUCCESS),
+        Err(e) => {
+            eprintln!("{}", tr!("app.native_failed", backend = native, error = e));
+            Some(ExitCode::FAILURE)
+        }
+    }
+}
+
+/// Ni Wayland ni X11 sur cette plateforme
+#[cfg(not(all(unix, not(any(target_os = "macos", target_os = "ios", target_os = "android")))))]
+fn run_native(native: Backend, _controller: &mut Controller, _relay: &EventRelay) -> Option<ExitCode> {
+    eprintln!("{}", tr!("app.native_fallback", backend = native, error = tr!("layer_shell.unsupported_platform")));
+    None
+}
+
+/// Description des écrans pour le placement de la fenêtre
+fn monitor_layout(monitors: impl Iterator<Item = MonitorHandle>, primary: Option<MonitorHandle>) -> MonitorLayout {
+    let handles: Vec<MonitorHandle> = monitors.collect();
+    MonitorLayout {
+        primary: primary.and_then(|primary| handles.iter().position(|handle| *handle == primary)),
+        monitors: handles
+            .iter()
+            .map(|handle| MonitorInfo {
+                name: handle.name(),
+                position: handle.position().into(),
+                size: handle.size().into(),
+                scale_factor: handle.scale_factor(),
+            })
+            .collect(),
+    }
+}
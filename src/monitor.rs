@@ -0,0 +1,201 @@
+use crate::config::window::{Anchor, MonitorSelection, Offset};
+use crate::config::CrosshairConfig;
+
+/// Description d'un écran, indépendante du système de fenêtres
+///
+/// Position et taille sont en pixels physiques, dans l'espace de l'ensemble
+/// des écrans (l'écran principal commence en général à 0, 0).
+#[derive(Debug, Clone, PartialEq)]
+pub struct MonitorInfo {
+    pub name: Option<String>,
+    pub position: (i32, i32),
+    pub size: (u32, u32),
+    pub scale_factor: f64,
+}
+
+impl MonitorInfo {
+    /// Le point (pixels physiques) est sur cet écran
+    pub fn contains(&self, point: (i32, i32)) -> bool {
+        let (x, y) = (i64::from(point.0), i64::from(point.1));
+        let (left, top) = (i64::from(self.position.0), i64::from(self.position.1));
+        x >= left && y >= top && x < left + i64::from(self.size.0) && y < top + i64::from(self.size.1)
+    }
+}
+
+/// Écrans disponibles, dans l'ordre du système
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MonitorLayout {
+    pub monitors: Vec<MonitorInfo>,
+    pub primary: Option<usize>,
+}
+
+impl MonitorLayout {
+    /// Écran choisi par la configuration
+    ///
+    /// Un écran introuvable (numéro trop grand, nom inconnu, curseur hors des
+    /// écrans) donne l'écran principal, ou à défaut le premier écran. Retourne
+    /// aussi un avertissement si la sélection n'a pas pu être respectée.
+    pub fn select(&self, selection: &MonitorSelection, cursor: Option<(i32, i32)>) -> (Option<&MonitorInfo>, Option<String>) {
+        let found = match selection {
+            MonitorSelection::Primary => self.primary,
+            MonitorSelection::Cursor => cursor.and_then(|cursor| self.monitors.iter().position(|m| m.contains(cursor))),
+            MonitorSelection::Index(index) => index.checked_sub(1).map(|i| i as usize).filter(|&i| i < self.monitors.len()),
+            MonitorSelection::Name(name) => self.find_by_name(name),
+        };
+        let fallback = || self.primary.or((!self.monitors.is_empty()).then_some(0));
+
+        match found {
+            Some(index) => (self.monitors.get(index), None),
+            // Pas d'écran principal signalé (Wayland) : pas d'avertissement
+            None if *selection == MonitorSelection::Primary => (fallback().and_then(|i| self.monitors.get(i)), None),
+            None => (
+                fallback().and_then(|i| self.monitors.get(i)),
+                Some(selection.to_string()),
+            ),
+        }
+    }
+
+    /// Nom exact (sans tenir compte de la casse), sinon nom contenant le texte
+    fn find_by_name(&self, name: &str) -> Option<usize> {
+        let name = name.to_lowercase();
+        let names: Vec<Option<String>> = self.monitors.iter().map(|m| m.name.as_ref().map(|n| n.to_lowercase())).collect();
+        names
+            .iter()
+            .position(|n| n.as_deref() == Some(name.as_str()))
+            .or_else(|| names.iter().position(|n| n.as_ref().is_some_and(|n| n.contains(&name))))
+    }
+}
+
+/// Position et taille de la fenêtre (pixels physiques) sur un écran
+///
+/// La taille vient de [`CrosshairConfig::window_side`] ; les décalages en
+/// pixels sont logiques et suivent la mise à l'échelle de l'écran. Avec
+/// `center_screen`, le point visé de la fenêtre (`aim_x` / `aim_y` depuis son
+/// centre) est placé sur l'ancre de l'écran décalée de `x` / `y` ; sinon le
+/// coin supérieur gauche de la fenêtre est placé à `x` / `y` du coin
+/// supérieur gauche de l'écran.
+pub fn window_placement(config: &CrosshairConfig, monitor: &MonitorInfo) -> ((i32, i32), (u32, u32)) {
+    let scale = if monitor.scale_factor.is_finite() && monitor.scale_factor > 0.0 { monitor.scale_factor } else { 1.0 };
+    let side = config.window_side(scale);
+    let position = &config.window.position;
+    let (width, height) = (f64::from(monitor.size.0), f64::from(monitor.size.1));
+    let offset = |offset: Option<Offset>, reference: f64| offset.unwrap_or_default().to_physical(reference, scale);
+
+    // Point de l'écran visé, relatif à son coin supérieur gauche
+    let (anchor, aim) = if position.center_screen {
+        (position.anchor, (position.aim_x, position.aim_y))
+    } else {
+        // Mode historique : le coin de la fenêtre sur le coin de l'écran
+        (Anchor::TopLeft, (Offset::Percent(-50.0), Offset::Percent(-50.0)))
+    };
+    let (fraction_x, fraction_y) = anchor.fractions();
+    let target = (
+        fraction_x * width + offset(position.x, width),
+        fraction_y * height + offset(position.y, height),
+    );
+
+    let half = f64::from(side) / 2.0;
+    let top_left = (
+        target.0 - half - aim.0.to_physical(f64::from(side), scale),
+        target.1 - half - aim.1.to_physical(f64::from(side), scale),
+    );
+    let clamp = |origin: i32, value: f64| (f64::from(origin) + value.round()).clamp(f64::from(i32::MIN), f64::from(i32::MAX)) as i32;
+    (
+        (clamp(monitor.position.0, top_left.0), clamp(monitor.position.1, top_left.1)),
+        (side, side),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout() -> MonitorLayout {
+        MonitorLayout {
+            monitors: vec![
+                MonitorInfo { name: Some("\\\\.\\DISPLAY1".to_string()), position: (0, 0), size: (1920, 1080), scale_factor: 1.0 },
+                MonitorInfo { name: Some("DELL U2720Q".to_string()), position: (1920, -200), size: (3840, 2160), scale_factor: 2.0 },
+            ],
+            primary: Some(0),
+        }
+    }
+
+    #[test]
+    fn monitors_are_selected_by_number_name_or_cursor() {
+        let layout = layout();
+        let name = |selection: MonitorSelection, cursor| layout.select(&selection, cursor).0.and_then(|m| m.name.clone());
+        assert_eq!(name(MonitorSelection::Index(2), None).as_deref(), Some("DELL U2720Q"));
+        assert_eq!(name(MonitorSelection::Name("dell u2720q".to_string()), None).as_deref(), Some("DELL U2720Q"));
+        assert_eq!(name(MonitorSelection::Name("u2720".to_string()), None).as_deref(), Some("DELL U2720Q"));
+        assert_eq!(name(MonitorSelection::Cursor, Some((2500, 0))).as_deref(), Some("DELL U2720Q"));
+        assert_eq!(name(MonitorSelection::Cursor, Some((100, 100))), name(MonitorSelection::Primary, None));
+    }
+
+    #[test]
+    fn missing_monitors_fall_back_to_the_primary_one() {
+        let layout = layout();
+        for selection in [MonitorSelection::Index(0), MonitorSelection::Index(3), MonitorSelection::Name("HDMI".to_string())] {
+            let (monitor, warning) = layout.select(&selection, None);
+            assert_eq!(monitor, layout.monitors.first());
+            assert!(warning.is_some());
+        }
+        assert_eq!(MonitorLayout::default().select(&MonitorSelection::Primary, None), (None, None));
+
+        // Sans écran principal signalé : le premier écran
+        let layout = MonitorLayout { primary: None, ..layout };
+        assert_eq!(layout.select(&MonitorSelection::Primary, None), (layout.monitors.first(), None));
+    }
+
+    #[test]
+    fn window_is_centered_in_physical_pixels() {
+        let layout = layout();
+        let config = CrosshairConfig::default();
+        assert_eq!(window_placement(&config, &layout.monitors[0]), ((910, 490), (100, 100)));
+        // Écran à 200 % : fenêtre deux fois plus grande, centrée sur cet écran
+        assert_eq!(window_placement(&config, &layout.monitors[1]), ((1920 + 1820, -200 + 980), (200, 200)));
+    }
+
+    #[test]
+    fn manual_position_is_relative_to_the_monitor_and_scaled() {
+        let layout = layout();
+        let mut config = CrosshairConfig::default();
+        config.window.position.center_screen = false;
+        config.window.position.x = Some(Offset::Pixels(10));
+        config.window.position.y = Some(Offset::Pixels(-5));
+        assert_eq!(window_placement(&config, &layout.monitors[1]).0, (1940, -210));
+    }
+
+    #[test]
+    fn anchors_place_the_window_center_on_the_screen() {
+        let layout = layout();
+        let mut config = CrosshairConfig::default();
+        config.window.position.anchor = Anchor::BottomRight;
+        assert_eq!(window_placement(&config, &layout.monitors[0]).0, (1870, 1030));
+        config.window.position.anchor = Anchor::Top;
+        config.window.position.y = Some(Offset::Pixels(100));
+        assert_eq!(window_placement(&config, &layout.monitors[0]).0, (910, 50));
+        // Décalage logique doublé sur l'écran à 200 %
+        assert_eq!(window_placement(&config, &layout.monitors[1]).0, (1920 + 1820, -200 + 100));
+    }
+
+    #[test]
+    fn percent_offsets_follow_the_resolution() {
+        let layout = layout();
+        let mut config = CrosshairConfig::default();
+        config.window.position.y = Some(Offset::Percent(10.0));
+        // 10 % de 1080 et de 2160 : même place relative sur les deux écrans
+        assert_eq!(window_placement(&config, &layout.monitors[0]).0, (910, 490 + 108));
+        assert_eq!(window_placement(&config, &layout.monitors[1]).0, (1920 + 1820, -200 + 980 + 216));
+    }
+
+    #[test]
+    fn aim_point_is_placed_on_the_anchor() {
+        let layout = layout();
+        let mut config = CrosshairConfig::default();
+        // Point visé 20 px à droite du centre de la fenêtre : la fenêtre part à gauche
+        config.window.position.aim_x = Offset::Pixels(20);
+        config.window.position.aim_y = Offset::Percent(-50.0);
+        assert_eq!(window_placement(&config, &layout.monitors[0]).0, (890, 540));
+        assert_eq!(window_placement(&config, &layout.monitors[1]).0, (1920 + 1780, -200 + 1080));
+    }
+}
@@ -0,0 +1,349 @@
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+use crate::clock::Clock;
+use crate::config::effects::mouse::{get_global_mouse_state, MouseState};
+use crate::config::CrosshairConfig;
+use crate::crosshair::frame_pixels;
+use crate::tr;
+
+/// Dessine une image complète (fond + crosshair avec effets) dans le buffer
+pub fn render_frame(config: &CrosshairConfig, buffer: &mut [u32], width: usize, height: usize, clock: &dyn Clock) {
+    render_frame_with_mouse_state(config, buffer, width, height, clock, &get_global_mouse_state(clock));
+}
+
+/// Dessine une image complète pour un état de souris donné (rendu reproductible)
+///
+/// Seuls les `width * height` premiers pixels du buffer sont modifiés. Un
+/// buffer trop petit pour ces dimensions n'est pas modifié du tout.
+pub fn render_frame_with_mouse_state(
+    config: &CrosshairConfig,
+    buffer: &mut [u32],
+    width: usize,
+    height: usize,
+    clock: &dyn Clock,
+    mouse_state: &MouseState,
+) {
+    let Some(buffer) = frame_pixels(buffer, width, height) else {
+        return;
+    };
+    let background = &config.window.background;
+
+    // Remplir avec du noir transparent ou la couleur de fond
+    if background.enabled {
+        buffer.fill(background.color.to_argb(background.alpha));
+    } else {
+        buffer.fill(0x00000000);
+    }
+
+    // Dessiner le crosshair selon la configuration avec effets
+    config.crosshair.draw(buffer, width, height, &config.effects, clock, mouse_state);
+
+    // Opacité globale, éventuellement animée par la pulsation, sur toute l'image
+    let opacity = config.window.opacity * config.effects.pulse.window_opacity(clock.now());
+    apply_opacity(buffer, opacity);
+}
+
+/// Multiplie l'alpha de chaque pixel par l'opacité (valeur non finie : pas de changement)
+fn apply_opacity(buffer: &mut [u32], opacity: f32) {
+    if !opacity.is_finite() || opacity >= 1.0 {
+        return;
+    }
+    let opacity = opacity.max(0.0);
+    for pixel in buffer.iter_mut() {
+        let alpha = ((*pixel >> 24) as f32 * opacity).round() as u32;
+        *pixel = (alpha << 24) | (*pixel & 0x00FFFFFF);
+    }
+}
+
+/// Côté maximal d'une image rendue par `render_to_png`, en pixels
+pub const MAX_IMAGE_SIZE: u32 = 4096;
+
+/// Rend la configuration dans une image PNG, sans fenêtre
+///
+/// Une taille nulle ou supérieure à `MAX_IMAGE_SIZE` est refusée.
+pub fn render_to_png<P: AsRef<Path>>(
+    config: &CrosshairConfig,
+    path: P,
+    size: u32,
+    clock: &dyn Clock,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if size == 0 || size > MAX_IMAGE_SIZE {
+        return Err(tr!("render.image_size", size = size, max = MAX_IMAGE_SIZE).into());
+    }
+    let side = size as usize;
+    let mut buffer = vec![0u32; side * side];
+    render_frame(config, &mut buffer, side, side, clock);
+
+    // Conversion ARGB (u32) vers RGBA (octets)
+    let pixels: Vec<u8> = buffer
+        .iter()
+        .flat_map(|pixel| {
+            let [a, r, g, b] = pixel.to_be_bytes();
+            [r, g, b, a]
+        })
+        .collect();
+
+    let file = File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), size, size);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&pixels)?;
+    writer.finish()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::ManualClock;
+    use crate::config::effects::PulseEffect;
+    use crate::crosshair::CrosshairStyle;
+    use proptest::prelude::*;
+
+    /// Valeur témoin placée après l'image pour détecter les écritures hors limites
+    const SENTINEL: u32 = 0xDEAD_BEEF;
+
+    fn mouse_state(buttons: (bool, bool, bool), press_time: Option<f32>) -> MouseState {
+        MouseState {
+            left_pressed: buttons.0,
+            right_pressed: buttons.1,
+            middle_pressed: buttons.2,
+            press_time,
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn render_stays_in_bounds_and_is_deterministic(
+            bytes in prop::collection::vec(any::<u8>(), 0..512),
+            width in 0usize..48,
+            height in 0usize..48,
+            time in prop::num::f32::ANY,
+            buttons in any::<(bool, bool, bool)>(),
+            press_time in prop::option::of(prop::num::f32::ANY),
+        ) {
+            let config = CrosshairConfig::from_arbitrary_bytes(&bytes);
+            let mouse = mouse_state(buttons, press_time);
+            let clock = ManualClock::new(time);
+            let frame = width * height;
+
+            let mut first = vec![SENTINEL; frame + 16];
+            render_frame_with_mouse_state(&config, &mut first, width, height, &clock, &mouse);
+            prop_assert!(first[frame..].iter().all(|&pixel| pixel == SENTINEL));
+
+            // Même rendu quel que soit le contenu précédent du buffer
+            let mut second = vec![0x1234_5678; frame + 16];
+            render_frame_with_mouse_state(&config, &mut second, width, height, &clock, &mouse);
+            prop_assert_eq!(&first[..frame], &second[..frame]);
+        }
+
+        #[test]
+        fn render_ignores_too_small_buffers(
+            bytes in prop::collection::vec(any::<u8>(), 0..512),
+            width in 1usize..48,
+            height in 1usize..48,
+        ) {
+            let config = CrosshairConfig::from_arbitrary_bytes(&bytes);
+            let mut buffer = vec![SENTINEL; width * height - 1];
+            render_frame_with_mouse_state(&config, &mut buffer, width, height, &ManualClock::default(), &MouseState::default());
+            prop_assert!(buffer.iter().all(|&pixel| pixel == SENTINEL));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn auto_sized_window_contains_the_crosshair(
+            bytes in prop::collection::vec(any::<u8>(), 0..512),
+            time in prop::num::f32::ANY,
+            buttons in any::<(bool, bool, bool)>(),
+            press_time in prop::option::of(prop::num::f32::ANY),
+        ) {
+            let mut config = CrosshairConfig::from_arbitrary_bytes(&bytes);
+            // Tailles raisonnables pour garder un rendu rapide
+            let crosshair = &mut config.crosshair;
+            crosshair.size %= 24;
+            crosshair.gap %= 24;
+            crosshair.thickness %= 12;
+            crosshair.center_dot.size %= 12;
+            config.effects.shake.intensity = config.effects.shake.intensity.clamp(-6.0, 6.0);
+            config.effects.mouse.gap_effect.intensity = config.effects.mouse.gap_effect.intensity.clamp(-3.0, 3.0);
+            config.window.background.enabled = false;
+            config.window.auto_size = true;
+
+            // Rendu dans une image plus grande : rien ne doit dépasser le carré calculé
+            let side = config.window_side(1.0) as usize;
+            let padding = 8;
+            let full = side + 2 * padding;
+            let mut buffer = vec![0; full * full];
+            let clock = ManualClock::new(time);
+            render_frame_with_mouse_state(&config, &mut buffer, full, full, &clock, &mouse_state(buttons, press_time));
+            let inside = padding..padding + side;
+            for (index, &pixel) in buffer.iter().enumerate() {
+                let (x, y) = (index % full, index / full);
+                prop_assert!(pixel == 0 || (inside.contains(&x) && inside.contains(&y)), "pixel ({}, {}) hors de la fenêtre de {}", x, y, side);
+            }
+        }
+    }
+
+    #[test]
+    fn auto_size_follows_the_crosshair() {
+        let mut config = CrosshairConfig::default();
+        config.window.auto_size = true;
+        // Lignes de 25 px : 2 * 25 + 2 pixels de marge, quelle que soit la mise à l'échelle
+        assert_eq!(config.window_side(1.0), 52);
+        assert_eq!(config.window_side(2.0), 52);
+
+        config.crosshair.rotation = 45.0;
+        assert_eq!(config.window_side(1.0), 2 * 26 + 2);
+        config.effects.shake.enabled = true;
+        config.effects.shake.intensity = 3.0;
+        assert_eq!(config.window_side(1.0), 2 * 29 + 2);
+
+        config.window.auto_size = false;
+        assert_eq!(config.window_side(2.0), 200);
+    }
+
+    #[test]
+    fn png_size_is_bounded() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("crosshair.png");
+        let config = CrosshairConfig::default();
+        for size in [0, MAX_IMAGE_SIZE + 1, u32::MAX] {
+            assert!(render_to_png(&config, &path, size, &ManualClock::new(0.0)).is_err(), "{}", size);
+            assert!(!path.exists());
+        }
+        render_to_png(&config, &path, 16, &ManualClock::new(0.0)).unwrap();
+        assert!(path.exists());
+    }
+
+    #[test]
+    fn window_opacity_applies_to_the_whole_frame() {
+        let mut config = CrosshairConfig::default();
+        config.window.background.enabled = true;
+        config.window.background.alpha = 1.0;
+        let render = |config: &CrosshairConfig, time: f32| {
+            let mut buffer = vec![0; 32 * 32];
+            render_frame_with_mouse_state(config, &mut buffer, 32, 32, &ManualClock::new(time), &MouseState::default());
+            buffer
+        };
+        let opaque = render(&config, 0.0);
+
+        config.window.opacity = 0.5;
+        let faded = render(&config, 0.0);
+        for (before, after) in opaque.iter().zip(&faded) {
+            assert_eq!(after & 0x00FFFFFF, before & 0x00FFFFFF);
+            assert_eq!(after >> 24, ((before >> 24) as f32 * 0.5).round() as u32);
+        }
+
+        // Pulsation de toute la fenêtre : le fond pulse aussi
+        config.window.opacity = 1.0;
+        config.effects.pulse = PulseEffect { enabled: true, whole_window: true, ..PulseEffect::default() };
+        let background = |buffer: &[u32]| buffer[0] >> 24;
+        assert_eq!(background(&render(&config, 0.125)), 255);
+        assert_eq!(background(&render(&config, 0.375)), (255.0 * 0.3f32).round() as u32);
+    }
+
+    /// Plus petit rectangle (x, y, largeur, hauteur) contenant les pixels dessinés
+    fn bounding_box(buffer: &[u32], width: usize) -> Option<(usize, usize, usize, usize)> {
+        let drawn: Vec<(usize, usize)> = buffer
+            .iter()
+            .enumerate()
+            .filter(|(_, &pixel)| pixel != 0)
+            .map(|(index, _)| (index % width, index / width))
+            .collect();
+        let min_x = drawn.iter().map(|(x, _)| *x).min()?;
+        let max_x = drawn.iter().map(|(x, _)| *x).max()?;
+        let min_y = drawn.iter().map(|(_, y)| *y).min()?;
+        let max_y = drawn.iter().map(|(_, y)| *y).max()?;
+        Some((min_x, min_y, max_x - min_x + 1, max_y - min_y + 1))
+    }
+
+    #[test]
+    fn triangle_bars_with_gap_equal_to_size() {
+        let mut config = CrosshairConfig::default();
+        config.crosshair.style = CrosshairStyle::Classic;
+        config.crosshair.triangle_bars = true;
+        config.crosshair.gap = config.crosshair.size;
+        config.crosshair.center_dot.enabled = false;
+        let mut buffer = vec![0; 64 * 64];
+        render_frame_with_mouse_state(&config, &mut buffer, 64, 64, &ManualClock::default(), &MouseState::default());
+
+        // Chaque barre est réduite à sa pointe, à `size` pixels du centre
+        let size = config.crosshair.size as usize;
+        let color = config.crosshair.get_color();
+        let mut tips = vec![(32, 32 - size), (32 - size, 32), (32 + size, 32), (32, 32 + size)];
+        tips.sort_by_key(|&(x, y)| y * 64 + x);
+        let drawn: Vec<_> = buffer.iter().enumerate().filter(|(_, &pixel)| pixel != 0).collect();
+        assert_eq!(drawn.iter().map(|(index, _)| (index % 64, index / 64)).collect::<Vec<_>>(), tips);
+        assert!(drawn.iter().all(|(_, &pixel)| pixel == color));
+
+        // Le point central s'ajoute au centre, sans toucher aux pointes
+        config.crosshair.center_dot.enabled = true;
+        render_frame_with_mouse_state(&config, &mut buffer, 64, 64, &ManualClock::default(), &MouseState::default());
+        for &(x, y) in &tips {
+            buffer[y * 64 + x] = 0;
+        }
+        let radius = config.crosshair.center_dot.size as usize;
+        assert_eq!(bounding_box(&buffer, 64), Some((32 - radius, 32 - radius, 2 * radius + 1, 2 * radius + 1)));
+    }
+
+    #[test]
+    fn huge_shake_does_not_overflow() {
+        for style in ["classic", "circle", "t", "x", "square", "diamond"] {
+            let mut config = CrosshairConfig::default();
+            config.crosshair.style = style.parse().unwrap();
+            config.crosshair.outline.enabled = true;
+            config.effects.shake.enabled = true;
+            let mut buffer = vec![0; 32 * 32];
+
+            config.effects.shake.intensity = 0.0;
+            render_frame_with_mouse_state(&config, &mut buffer, 32, 32, &ManualClock::default(), &MouseState::default());
+            assert!(bounding_box(&buffer, 32).is_some(), "{} : rien n'est dessiné sans tremblement", style);
+
+            // Au temps 0, le tremblement vertical vaut toute l'intensité : le crosshair sort de l'image
+            for intensity in [f32::MAX, -f32::MAX] {
+                config.effects.shake.intensity = intensity;
+                render_frame_with_mouse_state(&config, &mut buffer, 32, 32, &ManualClock::default(), &MouseState::default());
+                assert_eq!(bounding_box(&buffer, 32), None, "{} ({})", style, intensity);
+            }
+        }
+    }
+
+    #[test]
+    fn huge_circle_does_not_overflow() {
+        let mut config = CrosshairConfig::default();
+        config.crosshair.style = "circle".parse().unwrap();
+        config.crosshair.size = i32::MAX as u32 + 1;
+        config.crosshair.center_dot.enabled = false;
+        let mut buffer = vec![0; 32 * 32];
+        render_frame_with_mouse_state(&config, &mut buffer, 32, 32, &ManualClock::default(), &MouseState::default());
+        assert!(buffer.iter().all(|&pixel| pixel == 0), "le cercle entoure toute l'image");
+    }
+
+    #[test]
+    fn mouse_effects_follow_the_clock() {
+        let mut config = CrosshairConfig::default();
+        config.effects.mouse.enabled = true;
+        config.effects.mouse.gap_effect.enabled = true;
+        config.effects.mouse.gap_effect.smooth_transition = true;
+        config.effects.mouse.gap_effect.intensity = 2.0;
+        let mouse = mouse_state((true, false, false), Some(1.0));
+        let clock = ManualClock::new(1.0);
+        let render = |clock: &ManualClock| {
+            let mut buffer = vec![0; 64 * 64];
+            render_frame_with_mouse_state(&config, &mut buffer, 64, 64, clock, &mouse);
+            buffer
+        };
+
+        // Début du clic : pas encore d'effet
+        let pressed = render(&clock);
+        clock.advance(0.5);
+        let expanded = render(&clock);
+        assert_ne!(pressed, expanded, "le gap doit s'élargir pendant le clic");
+        // Même instant de l'horloge, même image
+        assert_eq!(expanded, render(&clock));
+    }
+}